@@ -0,0 +1,318 @@
+extern crate proc_macro;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Fields};
+
+/// Checks a `#[repr(C)]` struct against the std140 rules at compile time,
+/// see `ash_urn::memory_alignment::Std140`.
+#[proc_macro_derive(Std140)]
+pub fn derive_std140(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    block_layout(&input, "Std140")
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Checks a `#[repr(C)]` struct against the std430 rules at compile time,
+/// see `ash_urn::memory_alignment::Std430`.
+#[proc_macro_derive(Std430)]
+pub fn derive_std430(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    block_layout(&input, "Std430")
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn is_repr_c(input: &DeriveInput) -> bool {
+    let mut repr_c = false;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") {
+                repr_c = true;
+            }
+            Ok(())
+        });
+    }
+    repr_c
+}
+
+fn block_layout(input: &DeriveInput, layout: &str) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let member_trait = format_ident!("{}Member", layout);
+    let block_trait = format_ident!("{}", layout);
+    let rule = layout.to_lowercase();
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            format!("{} can not be derived for generic structs", layout),
+        ));
+    }
+    if !is_repr_c(input) {
+        return Err(syn::Error::new_spanned(
+            name,
+            format!("{} needs the struct to be #[repr(C)]", layout),
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    format!("{} needs a struct with named fields", layout),
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                format!("{} can only be derived for structs", layout),
+            ))
+        }
+    };
+    if fields.is_empty() {
+        return Err(syn::Error::new_spanned(
+            name,
+            format!("{} needs at least one field", layout),
+        ));
+    }
+
+    let path = quote!(::ash_urn::memory_alignment);
+
+    // expected offset of every field, following the rules of the layout
+    let checks = fields.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let message = format!(
+            "`{}::{}` is not at its {} offset, check the alignment of the previous fields",
+            name, ident, rule
+        );
+        quote! {
+            offset = #path::round_up(offset, <#ty as #path::#member_trait>::ALIGN);
+            assert!(::core::mem::offset_of!(#name, #ident) == offset, #message);
+            offset += <#ty as #path::#member_trait>::SIZE;
+        }
+    });
+
+    let aligns = fields.iter().map(|field| {
+        let ty = &field.ty;
+        quote!(align = #path::max(align, <#ty as #path::#member_trait>::ALIGN);)
+    });
+    let min_align: usize = if layout == "Std140" { 16 } else { 1 };
+
+    let last = fields.last().unwrap();
+    let last_ident = last.ident.as_ref().unwrap();
+    let last_ty = &last.ty;
+
+    let offsets = fields.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let field_name = ident.to_string();
+        quote!((#field_name, ::core::mem::offset_of!(#name, #ident)))
+    });
+
+    let size_message = format!(
+        "the size of `{}` does not match its {} array stride, adjust the alignment of the struct",
+        name, rule
+    );
+
+    Ok(quote! {
+        const _: () = {
+            let mut offset = 0usize;
+            #(#checks)*
+            let _ = offset;
+        };
+
+        unsafe impl #path::#member_trait for #name {
+            const ALIGN: usize = {
+                let mut align = #min_align;
+                #(#aligns)*
+                align
+            };
+            const SIZE: usize = {
+                let end = ::core::mem::offset_of!(#name, #last_ident)
+                    + <#last_ty as #path::#member_trait>::SIZE;
+                let size = #path::round_up(end, <Self as #path::#member_trait>::ALIGN);
+                assert!(::core::mem::size_of::<#name>() == size, #size_message);
+                size
+            };
+        }
+
+        impl #path::#block_trait for #name {
+            const OFFSETS: &'static [(&'static str, usize)] = &[#(#offsets),*];
+        }
+    })
+}
+
+/// Implements `ash_urn::Vertex` for a `#[repr(C)]` struct, one binding with
+/// an attribute per field. Formats come from `ash_urn::urn_mesh::VertexFormat`.
+///
+/// Struct attributes: `#[vertex(binding = 1, rate = "instance", location = 4)]`,
+/// where `location` is the first location that gets assigned.
+/// Field attributes: `#[vertex(location = 2)]`, `#[vertex(format = "R8G8B8A8_UNORM")]`
+/// and `#[vertex(skip)]`.
+#[proc_macro_derive(Vertex, attributes(vertex))]
+pub fn derive_vertex(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    vertex(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct VertexAttr {
+    binding: Option<u32>,
+    rate: Option<syn::LitStr>,
+    location: Option<u32>,
+    format: Option<syn::LitStr>,
+    skip: bool,
+}
+
+fn vertex_attr(attrs: &[syn::Attribute]) -> syn::Result<VertexAttr> {
+    let mut res = VertexAttr::default();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("vertex")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("binding") {
+                res.binding = Some(meta.value()?.parse::<syn::LitInt>()?.base10_parse()?);
+            } else if meta.path.is_ident("rate") {
+                res.rate = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("location") {
+                res.location = Some(meta.value()?.parse::<syn::LitInt>()?.base10_parse()?);
+            } else if meta.path.is_ident("format") {
+                res.format = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("skip") {
+                res.skip = true;
+            } else {
+                return Err(meta.error("unknown vertex attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(res)
+}
+
+fn vertex(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "Vertex can not be derived for generic structs",
+        ));
+    }
+    if !is_repr_c(input) {
+        return Err(syn::Error::new_spanned(
+            name,
+            "Vertex needs the struct to be #[repr(C)]",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "Vertex needs a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "Vertex can only be derived for structs",
+            ))
+        }
+    };
+
+    let struct_attr = vertex_attr(&input.attrs)?;
+    if struct_attr.format.is_some() || struct_attr.skip {
+        return Err(syn::Error::new_spanned(
+            name,
+            "format and skip are only allowed on fields",
+        ));
+    }
+    let binding = struct_attr.binding.unwrap_or(0);
+    let first_location = struct_attr.location.unwrap_or(0);
+    let rate = match struct_attr.rate {
+        None => quote!(VERTEX),
+        Some(rate) => match rate.value().as_str() {
+            "vertex" => quote!(VERTEX),
+            "instance" => quote!(INSTANCE),
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    rate,
+                    "rate is either \"vertex\" or \"instance\"",
+                ))
+            }
+        },
+    };
+
+    let vk = quote!(::ash_urn::ash::vk);
+    let format_trait = quote!(::ash_urn::urn_mesh::VertexFormat);
+
+    let mut attributes = Vec::new();
+    for field in fields {
+        let attr = vertex_attr(&field.attrs)?;
+        if attr.binding.is_some() || attr.rate.is_some() {
+            return Err(syn::Error::new_spanned(
+                field,
+                "binding and rate are only allowed on the struct",
+            ));
+        }
+        if attr.skip {
+            continue;
+        }
+
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let (format, locations) = match attr.format {
+            Some(format) => {
+                let format = syn::Ident::new(&format.value(), format.span());
+                (quote!(#vk::Format::#format), quote!(1u32))
+            }
+            None => (
+                quote!(<#ty as #format_trait>::FORMAT),
+                quote!(<#ty as #format_trait>::LOCATIONS),
+            ),
+        };
+        let set_location = attr.location.map(|l| quote!(location = #l;));
+
+        attributes.push(quote! {
+            #set_location
+            let locations = #locations;
+            let column_size = ::core::mem::size_of::<#ty>() as u32 / locations;
+            for i in 0..locations {
+                attributes.push(
+                    #vk::VertexInputAttributeDescription::builder()
+                        .binding(#binding)
+                        .location(location + i)
+                        .format(#format)
+                        .offset(::core::mem::offset_of!(#name, #ident) as u32 + i * column_size)
+                        .build(),
+                );
+            }
+            location += locations;
+        });
+    }
+
+    Ok(quote! {
+        impl ::ash_urn::Vertex for #name {
+            fn get_binding_description() -> Vec<#vk::VertexInputBindingDescription> {
+                vec![#vk::VertexInputBindingDescription::builder()
+                    .binding(#binding)
+                    .stride(::core::mem::size_of::<Self>() as u32)
+                    .input_rate(#vk::VertexInputRate::#rate)
+                    .build()]
+            }
+
+            #[allow(unused_assignments)]
+            fn get_attribute_description() -> Vec<#vk::VertexInputAttributeDescription> {
+                let mut attributes = Vec::new();
+                let mut location: u32 = #first_location;
+                #(#attributes)*
+                attributes
+            }
+        }
+    })
+}
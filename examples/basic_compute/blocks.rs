@@ -0,0 +1,34 @@
+use ash_urn::memory_alignment::{Std140, Std430};
+use ash_urn::pipeline::push_constant::PushConstant;
+
+// also compiled into tests/block_layout.rs, which checks the offsets against the shaders
+
+#[repr(C)]
+#[derive(Debug, Std140)]
+pub struct GraphicsUBO {
+    pub model: [[f32; 4]; 4],
+    pub view: [[f32; 4]; 4],
+    pub proj: [[f32; 4]; 4],
+}
+
+/// Shared by all compute shaders, particles outside of `cull_radius` are not drawn
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Std430)]
+pub struct Simulation {
+    pub n_particles: u32,
+    pub d_t: f32,
+    /// `G` in the shaders
+    pub g: f32,
+    pub cull_radius: f32,
+}
+
+unsafe impl PushConstant for Simulation {
+    const STAGES: ash::vk::ShaderStageFlags = ash::vk::ShaderStageFlags::COMPUTE;
+}
+
+#[repr(C)]
+#[derive(Std430)]
+pub struct Particle {
+    pub pos: [f32; 4],
+    pub vel: [f32; 4],
+}
@@ -11,7 +11,6 @@ pub enum AppError {
     IO(std::io::Error),
     NulError(std::ffi::NulError),
     SdlError(sdl::SdlError),
-    GltfError(gltf::Error),
 }
 
 impl From<std::ffi::NulError> for AppError {
@@ -49,9 +48,3 @@ impl From<sdl::SdlError> for AppError {
         AppError::SdlError(e)
     }
 }
-
-impl From<gltf::Error> for AppError {
-    fn from(e: gltf::Error) -> AppError {
-        AppError::GltfError(e)
-    }
-}
@@ -1,44 +1,33 @@
-pub mod assets;
+pub mod blocks;
 pub mod error;
 pub mod particles;
 pub mod run;
 pub mod sdl;
 pub mod setup;
 
+pub use blocks::{GraphicsUBO, Particle, Simulation};
 pub use error::AppError;
-pub use particles::{Particle, Particles};
+pub use particles::{ParticleInstance, Particles};
 pub use sdl::SDL;
 pub use setup::Setup;
 
-use ash_urn::memory_alignment::Align16;
+use ash_urn::import::gltf;
 use ash_urn::wait_device_idle;
 
-#[repr(C)]
-#[derive(Debug)]
-struct GraphicsUBO {
-    model: Align16<cgmath::Matrix4<f32>>,
-    view: Align16<cgmath::Matrix4<f32>>,
-    proj: Align16<cgmath::Matrix4<f32>>,
-}
-
-#[repr(C)]
-#[derive(Debug)]
-struct ComputeUBO {
-    n_particles: u32,
-    n_reference: u32,
-    scale: f32,
-    d_t: f32,
-    G: f32,
-}
-
 fn main() {
     println!("Starting basic_compute.");
 
     // create particles
     let particles = Particles::new(25);
 
-    // load reference mesh
-    let reference_mesh = &assets::load_mesh("examples/basic_graphics/assets/test.glb").unwrap();
+    // load reference mesh, the first primitive of the test scene
+    let reference_mesh = &gltf::load("examples/basic_graphics/assets/test.glb")
+        .unwrap()
+        .meshes
+        .swap_remove(0)
+        .primitives
+        .swap_remove(0)
+        .mesh;
 
     // create sdl context
     let mut sdl = sdl::SDL::new(sdl::WindowSettings {
@@ -1,13 +1,16 @@
+use crate::Particle;
+
 use cgmath::prelude::*;
 
-use ash_urn::memory_alignment::Align16;
-use ash_urn::UrnMesh;
-use ash_urn::UrnVertex;
+use ash_urn::Vertex;
 
-#[repr(C, align(32))]
-pub struct Particle {
-    pub pos: Align16<cgmath::Vector3<f32>>,
-    pub vel: Align16<cgmath::Vector3<f32>>,
+/// Position of a visible particle, the cull shader packs these
+/// at the front of the instance buffer
+#[repr(C)]
+#[derive(Copy, Clone, Vertex)]
+#[vertex(binding = 1, rate = "instance", location = 4)]
+pub struct ParticleInstance {
+    pub pos: [f32; 4],
 }
 
 pub struct Particles(pub Vec<Particle>);
@@ -23,7 +26,6 @@ impl Particles {
         for i in 0..res {
             for j in 0..res {
                 for k in 0..res {
-
                     let pos = cgmath::Vector3::<f32>::new(
                         (0.5 + i as f32) / res as f32 - 0.5,
                         (0.5 + j as f32) / res as f32 - 0.5,
@@ -31,8 +33,10 @@ impl Particles {
                     );
 
                     particles.push(Particle {
-                        pos: pos.into(),
-                        vel: cgmath::Vector3::<f32>::new(-15.0 * pos.y, 5.0 * pos.x, -pos.z).into(),
+                        pos: pos.extend(0.0).into(),
+                        vel: cgmath::Vector3::<f32>::new(-15.0 * pos.y, 5.0 * pos.x, -pos.z)
+                            .extend(0.0)
+                            .into(),
                     });
                 }
             }
@@ -41,34 +45,10 @@ impl Particles {
         Self(particles)
     }
 
-    pub fn as_mesh(&self, reference: &UrnMesh, scale: f32) -> UrnMesh {
-        let mut vertices = Vec::new();
-        let mut indices = Vec::new();
-
-        vertices.reserve(reference.vertices.len() * self.0.len());
-        indices.reserve(reference.indices.len() * self.0.len());
-
-        for p in &self.0 {
-            let idx_offset = vertices.len() as u32;
-            let offset = p.pos.0;
-            for v in &reference.vertices {
-                vertices.push(UrnVertex {
-                    pos: [
-                        v.pos.0[0] * scale + offset[0],
-                        v.pos.0[1] * scale + offset[1],
-                        v.pos.0[2] * scale + offset[2],
-                    ]
-                    .into(),
-                    nor: v.nor,
-                    col: v.col,
-                    tex: [offset[0], offset[1]].into(),
-                });
-            }
-            for i in &reference.indices {
-                indices.push(i + idx_offset);
-            }
-        }
-
-        UrnMesh { vertices, indices }
+    pub fn instances(&self) -> Vec<ParticleInstance> {
+        self.0
+            .iter()
+            .map(|p| ParticleInstance { pos: p.pos })
+            .collect()
     }
 }
@@ -29,8 +29,9 @@ pub fn submit(
         .signal_semaphore_values(&signal_values)
         .build();
     let graphics_wait_semaphores = [timeline.0, semaphore_image_acquired.0];
+    // the draw commands are read before any vertex
     let graphics_wait_stages_mask = [
-        ash::vk::PipelineStageFlags::VERTEX_INPUT,
+        ash::vk::PipelineStageFlags::DRAW_INDIRECT,
         ash::vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT,
     ];
     let graphics_signal_semaphores = [timeline.0, semaphore_rendering_finished.0];
@@ -1,6 +1,8 @@
 use crate::AppError;
+use crate::Simulation;
 
 use ash_urn::base::queue_families::{COMBINED, DEDICATED_TRANSFER};
+use ash_urn::command::recorder::{clear_color, clear_depth_stencil, CommandRecorder};
 use ash_urn::Base;
 use ash_urn::Descriptor;
 use ash_urn::DeviceBuffer;
@@ -8,13 +10,13 @@ use ash_urn::GraphicsPipeline;
 use ash_urn::RenderPass;
 use ash_urn::SwapChain;
 use ash_urn::Timestamp;
-use ash_urn::UrnMesh;
 use ash_urn::{Command, CommandBuffer, CommandSettings};
 use ash_urn::{ComputePipeline, PipelineLayout};
 
-use ash::version::DeviceV1_0;
-
-pub fn setup(base: &Base, n_buffer: u32) -> Result<(Command, Vec<CommandBuffer>, Command, CommandBuffer, Command), AppError> {
+pub fn setup(
+    base: &Base,
+    n_buffer: u32,
+) -> Result<(Command, Vec<CommandBuffer>, Command, CommandBuffer, Command), AppError> {
     let graphics_command = setup_graphics(base, n_buffer)?;
     let graphics_command_buffers = CommandBuffer::alloc_vec(
         base,
@@ -30,7 +32,13 @@ pub fn setup(base: &Base, n_buffer: u32) -> Result<(Command, Vec<CommandBuffer>,
     )?;
     let transfer_command = setup_transfer(base)?;
 
-    Ok((graphics_command, graphics_command_buffers, compute_command, compute_command_buffer, transfer_command))
+    Ok((
+        graphics_command,
+        graphics_command_buffers,
+        compute_command,
+        compute_command_buffer,
+        transfer_command,
+    ))
 }
 
 pub fn setup_graphics(base: &Base, n_buffer: u32) -> Result<Command, AppError> {
@@ -42,6 +50,7 @@ pub fn setup_graphics(base: &Base, n_buffer: u32) -> Result<Command, AppError> {
         &CommandSettings {
             queue_family_idx: combined_queue_family_idx,
             queue_idx: 0,
+            pool_flags: ash::vk::CommandPoolCreateFlags::empty(),
             name: "GraphicsCommand".to_string(),
         },
     )?;
@@ -58,6 +67,7 @@ pub fn setup_compute(base: &Base) -> Result<Command, AppError> {
         &CommandSettings {
             queue_family_idx: combined_queue_family_idx,
             queue_idx: 0,
+            pool_flags: ash::vk::CommandPoolCreateFlags::empty(),
             name: "ComputeCommand".to_string(),
         },
     )?;
@@ -74,6 +84,7 @@ pub fn setup_transfer(base: &Base) -> Result<Command, AppError> {
         &CommandSettings {
             queue_family_idx: transfer_queue_family_idx,
             queue_idx: 0,
+            pool_flags: ash::vk::CommandPoolCreateFlags::TRANSIENT,
             name: "TransferCommand".to_string(),
         },
     )?;
@@ -92,102 +103,51 @@ pub fn write_graphics(
     pipeline_layout: &PipelineLayout,
     descriptor: &Descriptor,
     vertex_buffer: &DeviceBuffer,
+    instance_buffer: &DeviceBuffer,
     index_buffer: &DeviceBuffer,
-    mesh: &UrnMesh,
+    draw_command_buffer: &DeviceBuffer,
 ) -> Result<(), AppError> {
-    for (i, command_buffer) in command_buffers.iter().enumerate() {
-        let command_buffer = command_buffer.0;
-
-        let clear_values = [
-            ash::vk::ClearValue {
-                color: ash::vk::ClearColorValue {
-                    float32: [0.0, 0.0, 0.0, 1.0],
-                },
-            },
-            ash::vk::ClearValue {
-                depth_stencil: ash::vk::ClearDepthStencilValue {
-                    depth: 1.0,
-                    stencil: 0,
-                },
-            },
-        ];
-
-        let render_pass_info = ash::vk::RenderPassBeginInfo::builder()
-            .render_pass(render_pass.0)
-            .framebuffer(swap_chain.elements[i].frame_buffer)
-            .render_area(ash::vk::Rect2D {
-                offset: ash::vk::Offset2D { x: 0, y: 0 },
-                extent: swap_chain.extent.0,
-            })
-            .clear_values(&clear_values);
-
-        let vertex_buffers = [vertex_buffer.buffer.0];
-        let offsets = [0];
-        let dynamic_offsets = [];
-        let descriptor_sets = [descriptor.sets[i].0];
-        let begin_info = ash::vk::CommandBufferBeginInfo::builder();
+    let clear_values = [
+        clear_color([0.0, 0.0, 0.0, 1.0]),
+        clear_depth_stencil(1.0, 0),
+    ];
 
-        unsafe {
-            base.logical_device
-                .0
-                .begin_command_buffer(command_buffer, &begin_info)?;
-
-            timestamp.mark(
-                base,
-                command_buffer,
+    for (i, command_buffer) in command_buffers.iter().enumerate() {
+        let mut recorder = CommandRecorder::begin(
+            base,
+            command_buffer.0,
+            ash::vk::CommandBufferUsageFlags::empty(),
+        )?;
+        recorder
+            .timestamp(
+                timestamp,
                 ash::vk::PipelineStageFlags::TOP_OF_PIPE,
                 "RENDER_START",
-            );
-
-            base.logical_device.0.cmd_begin_render_pass(
-                command_buffer,
-                &render_pass_info,
-                ash::vk::SubpassContents::INLINE,
-            );
-            base.logical_device.0.cmd_bind_pipeline(
-                command_buffer,
-                ash::vk::PipelineBindPoint::GRAPHICS,
-                pipeline.0,
-            );
-            base.logical_device.0.cmd_bind_vertex_buffers(
-                command_buffer,
-                0,
-                &vertex_buffers,
-                &offsets,
-            );
-            base.logical_device.0.cmd_bind_index_buffer(
-                command_buffer,
-                index_buffer.buffer.0,
-                0,
-                ash::vk::IndexType::UINT32,
-            );
-            base.logical_device.0.cmd_bind_descriptor_sets(
-                command_buffer,
+            )
+            .begin_render_pass(
+                render_pass.0,
+                swap_chain.elements[i].frame_buffer,
+                swap_chain.extent.0,
+                &clear_values,
+            )
+            .bind_pipeline(ash::vk::PipelineBindPoint::GRAPHICS, pipeline.0)
+            .bind_vertex_buffers(0, &[vertex_buffer.buffer.0, instance_buffer.buffer.0])
+            .bind_index_buffer(index_buffer.buffer.0, ash::vk::IndexType::UINT32)
+            .bind_descriptor_sets(
                 ash::vk::PipelineBindPoint::GRAPHICS,
                 pipeline_layout.0,
                 0,
-                &descriptor_sets,
-                &dynamic_offsets,
-            );
-            base.logical_device.0.cmd_draw_indexed(
-                command_buffer,
-                mesh.indices.len() as u32,
-                1,
-                0,
-                0,
-                0,
-            );
-            base.logical_device.0.cmd_end_render_pass(command_buffer);
-
-            timestamp.mark(
-                base,
-                command_buffer,
+                &[descriptor.sets[i].0],
+                &[],
+            )
+            .draw_indexed_indirect(draw_command_buffer.buffer.0, 0, 1)
+            .end_render_pass()
+            .timestamp(
+                timestamp,
                 ash::vk::PipelineStageFlags::BOTTOM_OF_PIPE,
                 "RENDER_DONE",
             );
-
-            base.logical_device.0.end_command_buffer(command_buffer)?;
-        }
+        recorder.end()?;
     }
 
     Ok(())
@@ -199,99 +159,74 @@ pub fn write_compute(
     pipeline_layout: &PipelineLayout,
     calculate_pipeline: &ComputePipeline,
     integrate_pipeline: &ComputePipeline,
+    cull_pipeline: &ComputePipeline,
     command: &Command,
     command_buffer: &CommandBuffer,
     descriptor: &Descriptor,
-    n_particles: u32,
+    draw_command_buffer: &DeviceBuffer,
+    simulation: &Simulation,
 ) -> Result<(), AppError> {
-    let command_buffer = command_buffer.0;
-
-    let begin_info = ash::vk::CommandBufferBeginInfo::builder();
-
-    let descriptor_sets = [descriptor.sets[0].0];
-    let dynamic_offsets = [];
-
-    let memory_barriers = [ash::vk::MemoryBarrier::builder()
-        .src_access_mask(ash::vk::AccessFlags::SHADER_READ | ash::vk::AccessFlags::SHADER_WRITE)
-        .dst_access_mask(ash::vk::AccessFlags::SHADER_READ | ash::vk::AccessFlags::SHADER_WRITE)
-        .build()];
-    let buffer_memory_barriers = [];
-    let image_memory_barriers = [];
-
-    unsafe {
-        base.logical_device
-            .0
-            .begin_command_buffer(command_buffer, &begin_info)?;
-
-        timestamp.reset_pool(base, command_buffer);
+    let read_write = ash::vk::AccessFlags::SHADER_READ | ash::vk::AccessFlags::SHADER_WRITE;
+    let n_groups = 1 + simulation.n_particles / 512;
+    // offset of `instance_count` in the draw command
+    let instance_count_offset = 4;
 
-        base.logical_device.0.cmd_bind_descriptor_sets(
-            command_buffer,
+    let mut recorder = CommandRecorder::begin(
+        base,
+        command_buffer.0,
+        ash::vk::CommandBufferUsageFlags::empty(),
+    )?;
+    recorder
+        .reset_timestamps(timestamp)
+        .bind_descriptor_sets(
             ash::vk::PipelineBindPoint::COMPUTE,
             pipeline_layout.0,
             0,
-            &descriptor_sets,
-            &dynamic_offsets,
-        );
-
-        base.logical_device.0.cmd_bind_pipeline(
-            command_buffer,
-            ash::vk::PipelineBindPoint::COMPUTE,
-            calculate_pipeline.0,
-        );
-
-        timestamp.mark(
-            base,
-            command_buffer,
+            &[descriptor.sets[0].0],
+            &[],
+        )
+        .push_constants(pipeline_layout.0, simulation)
+        .bind_pipeline(ash::vk::PipelineBindPoint::COMPUTE, calculate_pipeline.0)
+        .timestamp(
+            timestamp,
             ash::vk::PipelineStageFlags::TOP_OF_PIPE,
             "CALCULATE_START",
-        );
-
-        base.logical_device
-            .0
-            .cmd_dispatch(command_buffer, 1 + n_particles / 512, 1, 1);
-
-        timestamp.mark(
-            base,
-            command_buffer,
+        )
+        .dispatch(n_groups, 1, 1)
+        .timestamp(
+            timestamp,
             ash::vk::PipelineStageFlags::COMPUTE_SHADER,
             "CALCULATE_DONE",
-        );
-
-        timestamp.mark(
-            base,
-            command_buffer,
+        )
+        .timestamp(
+            timestamp,
             ash::vk::PipelineStageFlags::COMPUTE_SHADER,
             "INTEGRATE_START",
-        );
-
-        base.logical_device.0.cmd_pipeline_barrier(
-            command_buffer,
+        )
+        .memory_barrier(
             ash::vk::PipelineStageFlags::COMPUTE_SHADER,
+            read_write,
             ash::vk::PipelineStageFlags::COMPUTE_SHADER,
-            ash::vk::DependencyFlags::default(),
-            &memory_barriers,
-            &buffer_memory_barriers,
-            &image_memory_barriers,
-        );
-
-        base.logical_device.0.cmd_bind_pipeline(
-            command_buffer,
-            ash::vk::PipelineBindPoint::COMPUTE,
-            integrate_pipeline.0,
-        );
-        base.logical_device
-            .0
-            .cmd_dispatch(command_buffer, 1 + n_particles / 512, 1, 1);
-
-        timestamp.mark(
-            base,
-            command_buffer,
+            read_write,
+        )
+        .bind_pipeline(ash::vk::PipelineBindPoint::COMPUTE, integrate_pipeline.0)
+        .dispatch(n_groups, 1, 1)
+        .timestamp(
+            timestamp,
             ash::vk::PipelineStageFlags::COMPUTE_SHADER,
             "INTEGRATE_DONE",
-        );
+        )
+        // count the visible particles from zero again
+        .fill_buffer(draw_command_buffer.buffer.0, instance_count_offset, 4, 0)
+        .memory_barrier(
+            ash::vk::PipelineStageFlags::COMPUTE_SHADER | ash::vk::PipelineStageFlags::TRANSFER,
+            read_write | ash::vk::AccessFlags::TRANSFER_WRITE,
+            ash::vk::PipelineStageFlags::COMPUTE_SHADER,
+            read_write,
+        )
+        .bind_pipeline(ash::vk::PipelineBindPoint::COMPUTE, cull_pipeline.0)
+        .dispatch(n_groups, 1, 1);
+    recorder.end()?;
 
-        base.logical_device.0.end_command_buffer(command_buffer)?;
-    }
     Ok(())
 }
@@ -19,6 +19,7 @@ pub fn setup_graphics(
         descriptor::Setup {
             ty: ash::vk::DescriptorType::UNIFORM_BUFFER,
             stage: ash::vk::ShaderStageFlags::VERTEX,
+            count: 1,
         },
     );
     setup_map.insert(
@@ -26,6 +27,7 @@ pub fn setup_graphics(
         descriptor::Setup {
             ty: ash::vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
             stage: ash::vk::ShaderStageFlags::FRAGMENT,
+            count: 1,
         },
     );
 
@@ -60,54 +62,34 @@ pub fn setup_graphics(
 
 pub fn setup_compute(
     base: &Base,
-    uniform_buffer: &DeviceBuffer,
-    reference_device_buffer: &DeviceBuffer,
     particle_device_buffer: &DeviceBuffer,
-    vertex_device_buffer: &DeviceBuffer,
+    draw_command_buffer: &DeviceBuffer,
+    instance_device_buffer: &DeviceBuffer,
 ) -> Result<Descriptor, AppError> {
     let mut setup_map = HashMap::new();
-    setup_map.insert(
-        0,
-        descriptor::Setup {
-            ty: ash::vk::DescriptorType::UNIFORM_BUFFER,
-            stage: ash::vk::ShaderStageFlags::COMPUTE,
-        },
-    );
-    setup_map.insert(
-        1,
-        descriptor::Setup {
-            ty: ash::vk::DescriptorType::STORAGE_BUFFER,
-            stage: ash::vk::ShaderStageFlags::COMPUTE,
-        },
-    );
-    setup_map.insert(
-        2,
-        descriptor::Setup {
-            ty: ash::vk::DescriptorType::STORAGE_BUFFER,
-            stage: ash::vk::ShaderStageFlags::COMPUTE,
-        },
-    );
-    setup_map.insert(
-        3,
-        descriptor::Setup {
-            ty: ash::vk::DescriptorType::STORAGE_BUFFER,
-            stage: ash::vk::ShaderStageFlags::COMPUTE,
-        },
-    );
+    for binding in 0..3 {
+        setup_map.insert(
+            binding,
+            descriptor::Setup {
+                ty: ash::vk::DescriptorType::STORAGE_BUFFER,
+                stage: ash::vk::ShaderStageFlags::COMPUTE,
+                count: 1,
+            },
+        );
+    }
 
     let mut set_usages = Vec::new();
 
     let mut usages = HashMap::new();
-    usages.insert(0, descriptor::Usage::Buffer(uniform_buffer.buffer.0));
     usages.insert(
-        1,
-        descriptor::Usage::Buffer(reference_device_buffer.buffer.0),
+        0,
+        descriptor::Usage::Buffer(particle_device_buffer.buffer.0),
     );
+    usages.insert(1, descriptor::Usage::Buffer(draw_command_buffer.buffer.0));
     usages.insert(
         2,
-        descriptor::Usage::Buffer(particle_device_buffer.buffer.0),
+        descriptor::Usage::Buffer(instance_device_buffer.buffer.0),
     );
-    usages.insert(3, descriptor::Usage::Buffer(vertex_device_buffer.buffer.0));
     set_usages.push(descriptor::SetUsage {
         usages,
         name: "ComputeDescriptorSet".to_string(),
@@ -11,11 +11,12 @@ mod textures;
 mod uniform_buffers;
 
 use crate::AppError;
-use crate::ComputeUBO;
 use crate::Particles;
+use crate::Simulation;
 use crate::SDL;
 
 use ash_urn::sync::wait_device_idle;
+use ash_urn::transfer::create_indirect_device_buffer;
 use ash_urn::Base;
 use ash_urn::Command;
 use ash_urn::CommandBuffer;
@@ -41,7 +42,6 @@ pub struct Setup<'a> {
     pub depth_device_image: DeviceImage,
 
     pub graphics_uniform_buffers: Vec<DeviceBuffer>,
-    pub compute_uniform_buffer: DeviceBuffer,
 
     pub graphics_descriptor: Descriptor,
     pub compute_descriptor: Descriptor,
@@ -55,8 +55,9 @@ pub struct Setup<'a> {
     pub vertex_device_buffer: DeviceBuffer,
     pub index_device_buffer: DeviceBuffer,
 
-    pub reference_buffer: DeviceBuffer,
     pub particle_buffer: DeviceBuffer,
+    pub instance_buffer: DeviceBuffer,
+    pub draw_command_buffer: DeviceBuffer,
 
     pub graphics_pipeline_layout: PipelineLayout,
     pub graphics_pipeline: GraphicsPipeline,
@@ -64,6 +65,7 @@ pub struct Setup<'a> {
     pub compute_pipeline_layout: PipelineLayout,
     pub calculate_pipeline: ComputePipeline,
     pub integrate_pipeline: ComputePipeline,
+    pub cull_pipeline: ComputePipeline,
 
     pub timeline: Timeline,
     pub semaphore_image_acquired: Semaphore,
@@ -86,9 +88,6 @@ impl<'a> Setup<'a> {
     ) -> Result<Self, AppError> {
         wait_device_idle(base)?;
 
-        // replicate reference mesh for each particle
-        let mesh = particles.as_mesh(reference_mesh, 0.1);
-
         // get swap chain + renderpass & depth image
         // this is also a bit entangled
         let (swap_chain, render_pass, depth_device_image) =
@@ -97,18 +96,6 @@ impl<'a> Setup<'a> {
         // an uniform buffer per swapchain image
         let graphics_uniform_buffers =
             uniform_buffers::setup_graphics(base, swap_chain.image_count)?;
-        let compute_uniform_buffer = uniform_buffers::setup_compute(base)?;
-        // write to the compute ubo
-        compute_uniform_buffer.write(
-            &base,
-            &ComputeUBO {
-                n_particles: particles.0.len() as u32,
-                n_reference: reference_mesh.vertices.len() as u32,
-                scale: 0.01,
-                d_t: 0.001,
-                G: 0.001,
-            },
-        )?;
 
         // get the structures for commands,
         // they will be filled out later
@@ -117,14 +104,14 @@ impl<'a> Setup<'a> {
             graphics_command_buffers,
             compute_command,
             compute_command_buffer,
-            transfer_command
+            transfer_command,
         ) = command::setup(base, swap_chain.image_count)?;
 
-        // create device buffers from the mesh & load the textures
+        // create device buffers from the reference mesh & load the textures
         // the transfer is done with the transfer command,
         // ownership is transferred afterwards
         let (vertex_device_buffer, index_device_buffer) =
-            mesh_buffers::setup(base, &mesh, &graphics_command, &transfer_command)?;
+            mesh_buffers::setup(base, reference_mesh, &graphics_command, &transfer_command)?;
         let textures = textures::setup(
             base,
             &[(
@@ -135,34 +122,43 @@ impl<'a> Setup<'a> {
             &transfer_command,
         )?;
 
-        // prepare storage buffers
-        let (reference_buffer, particle_buffer) = storage_buffers::setup(
+        // prepare the particles & the instance buffer the reference mesh is drawn with
+        let (particle_buffer, instance_buffer) =
+            storage_buffers::setup(base, particles, &compute_command, &transfer_command)?;
+
+        // a single instanced draw, the cull shader writes its instance count
+        let draw_command_buffer = create_indirect_device_buffer(
             base,
-            reference_mesh,
-            particles,
-            &compute_command,
-            &transfer_command,
+            &[ash::vk::DrawIndexedIndirectCommand {
+                index_count: reference_mesh.indices.len() as u32,
+                instance_count: 0,
+                first_index: 0,
+                vertex_offset: 0,
+                first_instance: 0,
+            }],
+            graphics_command.queue.0,
+            graphics_command.pool.0,
+            "DrawCommandBuffer".to_string(),
         )?;
 
         // these sets contain the respective UBOs & combined image samplers
         let graphics_descriptor =
             descriptor::setup_graphics(base, &graphics_uniform_buffers, &textures[0])?;
 
-        // this set contains the compute UBO & storage buffers
+        // this set contains the storage buffers
         let compute_descriptor = descriptor::setup_compute(
             base,
-            &compute_uniform_buffer,
-            &reference_buffer,
             &particle_buffer,
-            &vertex_device_buffer,
+            &draw_command_buffer,
+            &instance_buffer,
         )?;
 
         // just one pipeline, using the vert & frag shader
         let (graphics_pipeline_layout, graphics_pipeline) =
             pipeline::setup_graphics(base, &graphics_descriptor, &swap_chain, &render_pass)?;
 
-        // and just one pipeline for the particle update
-        let (compute_pipeline_layout, calculate_pipeline, integrate_pipeline) =
+        // the particle update and the culling share one layout
+        let (compute_pipeline_layout, calculate_pipeline, integrate_pipeline, cull_pipeline) =
             pipeline::setup_compute(base, &compute_descriptor)?;
 
         // get timestamp for profiling
@@ -194,8 +190,9 @@ impl<'a> Setup<'a> {
             &graphics_pipeline_layout,
             &graphics_descriptor,
             &vertex_device_buffer,
+            &instance_buffer,
             &index_device_buffer,
-            &mesh,
+            &draw_command_buffer,
         )?;
 
         // write to the one compute buffer
@@ -205,10 +202,17 @@ impl<'a> Setup<'a> {
             &compute_pipeline_layout,
             &calculate_pipeline,
             &integrate_pipeline,
+            &cull_pipeline,
             &compute_command,
             &compute_command_buffer,
             &compute_descriptor,
-            particles.0.len() as u32,
+            &draw_command_buffer,
+            &Simulation {
+                n_particles: particles.0.len() as u32,
+                d_t: 0.001,
+                g: 0.001,
+                cull_radius: 0.75,
+            },
         )?;
 
         // create all synchronization structs
@@ -227,7 +231,6 @@ impl<'a> Setup<'a> {
             render_pass,
             depth_device_image,
             graphics_uniform_buffers,
-            compute_uniform_buffer,
             graphics_descriptor,
             compute_descriptor,
             graphics_command,
@@ -237,13 +240,15 @@ impl<'a> Setup<'a> {
             transfer_command,
             vertex_device_buffer,
             index_device_buffer,
-            reference_buffer,
             particle_buffer,
+            instance_buffer,
+            draw_command_buffer,
             graphics_pipeline_layout,
             graphics_pipeline,
             compute_pipeline_layout,
             calculate_pipeline,
             integrate_pipeline,
+            cull_pipeline,
             timeline,
             semaphore_image_acquired,
             semaphore_rendering_finished,
@@ -272,13 +277,13 @@ impl Drop for Setup<'_> {
         self.fence_rendering_finished.destroy(&self.base);
         self.vertex_device_buffer.destroy(&self.base);
         self.index_device_buffer.destroy(&self.base);
-        self.reference_buffer.destroy(&self.base);
         self.particle_buffer.destroy(&self.base);
+        self.instance_buffer.destroy(&self.base);
+        self.draw_command_buffer.destroy(&self.base);
         self.depth_device_image.destroy(&self.base);
         for uniform_buffer in &self.graphics_uniform_buffers {
             uniform_buffer.destroy(&self.base);
         }
-        self.compute_uniform_buffer.destroy(&self.base);
         self.swap_chain.destroy(&self.base);
         self.graphics_descriptor.destroy(&self.base);
         self.compute_descriptor.destroy(&self.base);
@@ -287,6 +292,7 @@ impl Drop for Setup<'_> {
         self.compute_pipeline_layout.destroy(&self.base);
         self.calculate_pipeline.destroy(&self.base);
         self.integrate_pipeline.destroy(&self.base);
+        self.cull_pipeline.destroy(&self.base);
         self.render_pass.destroy(&self.base);
     }
 }
@@ -1,5 +1,8 @@
 use crate::AppError;
+use crate::ParticleInstance;
+use crate::Simulation;
 
+use ash_urn::pipeline::push_constant::push_constant_range;
 use ash_urn::Base;
 use ash_urn::Descriptor;
 use ash_urn::RenderPass;
@@ -24,7 +27,7 @@ pub fn setup_graphics(
         },
     )?;
 
-    let pipeline = GraphicsPipeline::new::<UrnVertex>(
+    let pipeline = GraphicsPipeline::new::<(UrnVertex, ParticleInstance)>(
         &base,
         &GraphicsPipelineSettings {
             layout: pipeline_layout.0,
@@ -42,12 +45,20 @@ pub fn setup_graphics(
 pub fn setup_compute(
     base: &Base,
     descriptor: &Descriptor,
-) -> Result<(PipelineLayout, ComputePipeline, ComputePipeline), AppError> {
+) -> Result<
+    (
+        PipelineLayout,
+        ComputePipeline,
+        ComputePipeline,
+        ComputePipeline,
+    ),
+    AppError,
+> {
     let pipeline_layout = PipelineLayout::new(
         &base,
         &PipelineLayoutSettings {
             set_layouts: vec![descriptor.layout.0],
-            push_constant_ranges: vec![],
+            push_constant_ranges: vec![push_constant_range::<Simulation>(base)?],
             name: "ComputePipelineLayout".to_string(),
         },
     )?;
@@ -70,5 +81,19 @@ pub fn setup_compute(
         },
     )?;
 
-    Ok((pipeline_layout, calculate_pipeline, integrate_pipeline))
+    let cull_pipeline = ComputePipeline::new(
+        &base,
+        &ComputePipelineSettings {
+            layout: pipeline_layout.0,
+            comp_spv: &std::path::Path::new("examples/basic_compute/shaders/cull.spv"),
+            name: "CullPipeline".to_string(),
+        },
+    )?;
+
+    Ok((
+        pipeline_layout,
+        calculate_pipeline,
+        integrate_pipeline,
+        cull_pipeline,
+    ))
 }
@@ -1,45 +1,45 @@
 use crate::AppError;
 
-use crate::{Particle, Particles};
+use crate::{Particle, ParticleInstance, Particles};
 
-use ash_urn::transfer::{create_storage_device_buffer, ownership};
+use ash_urn::transfer::{
+    create_instance_storage_device_buffer, create_storage_device_buffer, ownership,
+};
 use ash_urn::Base;
 use ash_urn::Command;
 use ash_urn::DeviceBuffer;
-use ash_urn::UrnMesh;
-use ash_urn::UrnVertex;
 
 pub fn setup(
     base: &Base,
-    reference_mesh: &UrnMesh,
     particles: &Particles,
     combined_command: &Command,
     transfer_command: &Command,
 ) -> Result<(DeviceBuffer, DeviceBuffer), AppError> {
-    let reference_buffer = create_storage_device_buffer::<UrnVertex>(
+    let particle_buffer = create_storage_device_buffer::<Particle>(
         &base,
-        reference_mesh.vertices.as_slice(),
+        particles.0.as_slice(),
         transfer_command.queue.0,
         transfer_command.pool.0,
-        "ReferenceBuffer".to_string(),
+        "ParticleBuffer".to_string(),
     )?;
 
-    let particle_buffer = create_storage_device_buffer::<Particle>(
+    // room for every particle, the cull shader overwrites it each frame
+    let instance_buffer = create_instance_storage_device_buffer::<ParticleInstance>(
         &base,
-        particles.0.as_slice(),
+        particles.instances().as_slice(),
         transfer_command.queue.0,
         transfer_command.pool.0,
-        "ParticleBuffer".to_string(),
+        "InstanceBuffer".to_string(),
     )?;
 
     // transfer the ownership to the combined queue family
     ownership::transfer_to_combined(
         &base,
-        &[&reference_buffer, &particle_buffer],
+        &[&particle_buffer, &instance_buffer],
         &[],
         &transfer_command,
         &combined_command,
     )?;
 
-    Ok((reference_buffer, particle_buffer))
+    Ok((particle_buffer, instance_buffer))
 }
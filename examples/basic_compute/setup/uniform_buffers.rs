@@ -1,5 +1,4 @@
 use crate::AppError;
-use crate::ComputeUBO;
 use crate::GraphicsUBO;
 
 use ash_urn::Base;
@@ -24,20 +23,3 @@ pub fn setup_graphics(base: &Base, n_buffer: u32) -> Result<Vec<DeviceBuffer>, A
 
     Ok(uniform_buffers)
 }
-
-pub fn setup_compute(base: &Base) -> Result<DeviceBuffer, AppError> {
-    let uniform_buffer = DeviceBuffer::new(
-        &base,
-        &DeviceBufferSettings {
-            size: std::mem::size_of::<ComputeUBO>() as ash::vk::DeviceSize,
-            usage: ash::vk::BufferUsageFlags::UNIFORM_BUFFER,
-            properties: ash::vk::MemoryPropertyFlags::HOST_VISIBLE
-                | ash::vk::MemoryPropertyFlags::HOST_COHERENT,
-            shared: false,
-            map: true,
-            name: "ComputeUBO".to_string(),
-        },
-    )?;
-
-    Ok(uniform_buffer)
-}
@@ -18,5 +18,9 @@ while read -r directory events filename; do
     glslc --target-env=vulkan1.2 --target-spv=spv1.3 -fshader-stage=comp integrate.comp -o integrate.spv
     echo "recompiled integrate shader."
   fi
+  if [ "$filename" = "cull.comp" ]; then
+    glslc --target-env=vulkan1.2 --target-spv=spv1.3 -fshader-stage=comp cull.comp -o cull.spv
+    echo "recompiled cull shader."
+  fi
 
 done
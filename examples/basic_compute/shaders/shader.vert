@@ -12,6 +12,11 @@ layout(location = 1) in vec3 inNormal;
 layout(location = 2) in vec4 inColor;
 layout(location = 3) in vec2 inTexCoord;
 
+// per instance, the position of a visible particle
+layout(location = 4) in vec4 inOffset;
+
+const float scale = 0.01;
+
 layout(location = 0) out vec3 fragNormal;
 layout(location = 1) out vec4 fragColor;
 layout(location = 2) out vec2 fragTexCoord;
@@ -20,8 +25,9 @@ void main() {
 
     fragNormal = inNormal;
     fragColor = inColor;
-    fragTexCoord = inTexCoord;
+    fragTexCoord = inOffset.xy;
 
-    gl_Position = ubo.proj * ubo.view * ubo.model * vec4(inPosition, 1.0);
+    vec3 position = scale * inPosition + inOffset.xyz;
+    gl_Position = ubo.proj * ubo.view * ubo.model * vec4(position, 1.0);
 
 }
@@ -1,4 +1,3 @@
-pub mod assets;
 pub mod error;
 pub mod run;
 pub mod sdl;
@@ -8,21 +7,30 @@ pub use error::AppError;
 pub use sdl::SDL;
 pub use setup::Setup;
 
-use ash_urn::memory_alignment::Align16;
+use ash_urn::import::gltf;
+use ash_urn::memory_alignment::{Pod, Std140};
 use ash_urn::wait_device_idle;
 
 #[repr(C)]
-struct UBO {
-    model: Align16<cgmath::Matrix4<f32>>,
-    view: Align16<cgmath::Matrix4<f32>>,
-    proj: Align16<cgmath::Matrix4<f32>>,
+#[derive(Copy, Clone, Std140)]
+pub struct UBO {
+    model: [[f32; 4]; 4],
+    view: [[f32; 4]; 4],
+    proj: [[f32; 4]; 4],
 }
+unsafe impl Pod for UBO {}
 
 fn main() {
     println!("Starting basic_graphics.");
 
-    // create a mesh to render
-    let mesh = assets::load_mesh("examples/basic_graphics/assets/test.glb").unwrap();
+    // create a mesh to render, the first primitive of the test scene
+    let mesh = gltf::load("examples/basic_graphics/assets/test.glb")
+        .unwrap()
+        .meshes
+        .swap_remove(0)
+        .primitives
+        .swap_remove(0)
+        .mesh;
 
     // create sdl context
     let mut sdl = sdl::SDL::new(sdl::WindowSettings {
@@ -2,12 +2,12 @@ use crate::AppError;
 use crate::UBO;
 
 use ash_urn::Base;
-use ash_urn::DeviceBuffer;
 use ash_urn::SwapChain;
+use ash_urn::TypedBuffer;
 
 pub fn update(
     base: &Base,
-    uniform_buffer: &DeviceBuffer,
+    uniform_buffer: &TypedBuffer<UBO>,
     swap_chain: &SwapChain,
     start_instant: &std::time::Instant,
 ) -> Result<(), AppError> {
@@ -34,6 +34,7 @@ pub fn update(
 
     uniform_buffer.write(
         &base,
+        0,
         &UBO {
             model: model.into(),
             view: view.into(),
@@ -4,8 +4,8 @@ use crate::SDL;
 const ENABLE_VALIDATION: bool = cfg!(debug_assertions);
 
 use ash_urn::base::{
-    Base, Entry, Instance, InstanceSettings, LogicalDevice, LogicalDeviceSettings, QueueSetting, PhysicalDevice,
-    PhysicalDeviceSettings, Validation,
+    Base, Entry, Instance, InstanceSettings, LogicalDevice, LogicalDeviceSettings, PhysicalDevice,
+    PhysicalDeviceSettings, QueueSetting, Validation,
 };
 
 pub fn setup(
@@ -60,6 +60,10 @@ pub fn setup(
         surface,
         PhysicalDeviceSettings {
             timelines,
+            descriptor_indexing: false,
+            index_type_uint8: false,
+            multi_draw_indirect: false,
+            draw_indirect_count: false,
             subgroups: true,
         },
     )?;
@@ -93,6 +97,10 @@ pub fn setup(
                 },
             ],
             timelines,
+            descriptor_indexing: false,
+            index_type_uint8: false,
+            multi_draw_indirect: false,
+            draw_indirect_count: false,
         },
     )?;
 
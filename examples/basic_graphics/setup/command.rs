@@ -4,7 +4,10 @@ use ash_urn::base::queue_families::{COMBINED, DEDICATED_TRANSFER};
 use ash_urn::Base;
 use ash_urn::{Command, CommandBuffer, CommandSettings};
 
-pub fn setup(base: &Base, n_buffer: u32) -> Result<(Command, Vec<CommandBuffer>, Command), AppError> {
+pub fn setup(
+    base: &Base,
+    n_buffer: u32,
+) -> Result<(Command, Vec<CommandBuffer>, Command), AppError> {
     let graphics_command = setup_graphics(base)?;
     let graphics_command_buffers = CommandBuffer::alloc_vec(
         base,
@@ -26,6 +29,7 @@ pub fn setup_graphics(base: &Base) -> Result<Command, AppError> {
         &CommandSettings {
             queue_family_idx: combined_queue_family_idx,
             queue_idx: 0,
+            pool_flags: ash::vk::CommandPoolCreateFlags::empty(),
             name: "GraphicsCommand".to_string(),
         },
     )?;
@@ -42,6 +46,7 @@ pub fn setup_transfer(base: &Base) -> Result<Command, AppError> {
         &CommandSettings {
             queue_family_idx: transfer_queue_family_idx,
             queue_idx: 0,
+            pool_flags: ash::vk::CommandPoolCreateFlags::TRANSIENT,
             name: "TransferCommand".to_string(),
         },
     )?;
@@ -1,8 +1,9 @@
 use crate::AppError;
+use crate::UBO;
 
 use ash_urn::descriptor;
 use ash_urn::Base;
-use ash_urn::DeviceBuffer;
+use ash_urn::TypedBuffer;
 use ash_urn::{Descriptor, DescriptorSettings};
 use ash_urn::{DeviceImage, Sampler};
 
@@ -10,7 +11,7 @@ use std::collections::HashMap;
 
 pub fn setup(
     base: &Base,
-    uniform_buffers: &[DeviceBuffer],
+    uniform_buffers: &[TypedBuffer<UBO>],
     texture: &(DeviceImage, Sampler),
 ) -> Result<Descriptor, AppError> {
     let mut setup_map = HashMap::new();
@@ -19,6 +20,7 @@ pub fn setup(
         descriptor::Setup {
             ty: ash::vk::DescriptorType::UNIFORM_BUFFER,
             stage: ash::vk::ShaderStageFlags::VERTEX,
+            count: 1,
         },
     );
     setup_map.insert(
@@ -26,13 +28,14 @@ pub fn setup(
         descriptor::Setup {
             ty: ash::vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
             stage: ash::vk::ShaderStageFlags::FRAGMENT,
+            count: 1,
         },
     );
 
     let mut set_usages = Vec::new();
     for (i, uniform_buffer) in uniform_buffers.iter().enumerate() {
         let mut usages = HashMap::new();
-        usages.insert(0, descriptor::Usage::Buffer(uniform_buffer.buffer.0));
+        usages.insert(0, descriptor::Usage::Buffer(uniform_buffer.buffer()));
         usages.insert(
             1,
             descriptor::Usage::ImageSampler(
@@ -1,6 +1,7 @@
 use crate::AppError;
 
-use ash_urn::transfer::{create_index_device_buffer, create_vertex_device_buffer, ownership};
+use ash_urn::transfer::UploadBatch;
+use ash_urn::urn_mesh::Indices;
 use ash_urn::Base;
 use ash_urn::Command;
 use ash_urn::DeviceBuffer;
@@ -12,32 +13,31 @@ pub fn setup(
     graphics_command: &Command,
     transfer_command: &Command,
 ) -> Result<(DeviceBuffer, DeviceBuffer), AppError> {
-    // create vertex buffer
-    let vertex_device_buffer = create_vertex_device_buffer(
-        &base,
+    // both buffers are staged together and copied in one submission
+    let mut batch = UploadBatch::new();
+    batch.add_buffer(
         mesh.vertices.as_slice(),
-        transfer_command.queue.0,
-        transfer_command.pool.0,
+        ash::vk::BufferUsageFlags::VERTEX_BUFFER,
         "VertexBuffer".to_string(),
     )?;
 
-    // create index buffer
-    let index_device_buffer = create_index_device_buffer(
-        &base,
-        mesh.indices.as_slice(),
-        transfer_command.queue.0,
-        transfer_command.pool.0,
-        "IndexBuffer".to_string(),
-    )?;
+    // index buffer, u16 if the mesh is small enough
+    let index_usage = ash::vk::BufferUsageFlags::INDEX_BUFFER;
+    let index_name = "IndexBuffer".to_string();
+    match mesh.compact_indices(false) {
+        Indices::U8(indices) => batch.add_buffer(&indices, index_usage, index_name),
+        Indices::U16(indices) => batch.add_buffer(&indices, index_usage, index_name),
+        Indices::U32(indices) => batch.add_buffer(&indices, index_usage, index_name),
+    }?;
 
-    // transfer the ownership to the combined queue family
-    ownership::transfer_to_combined(
-        &base,
-        &[&vertex_device_buffer, &index_device_buffer],
-        &[],
-        &transfer_command,
-        &graphics_command, // any command struct from the combined family is ok
-    )?;
+    // the ownership is transferred to the combined queue family on the way,
+    // any command struct from the combined family is ok
+    let (uploads, pending) = batch.submit(&base, &transfer_command, &graphics_command)?;
+    pending.finish(&base)?;
+
+    let mut buffers = uploads.buffers.into_iter();
+    let vertex_device_buffer = buffers.next().unwrap();
+    let index_device_buffer = buffers.next().unwrap();
 
     Ok((vertex_device_buffer, index_device_buffer))
 }
@@ -11,6 +11,7 @@ mod uniform_buffers;
 
 use crate::AppError;
 use crate::SDL;
+use crate::UBO;
 
 use ash_urn::sync::wait_device_idle;
 use ash_urn::Base;
@@ -28,6 +29,7 @@ use ash_urn::Semaphore;
 use ash_urn::SwapChain;
 use ash_urn::Timeline;
 use ash_urn::Timestamp;
+use ash_urn::TypedBuffer;
 use ash_urn::UrnMesh;
 
 pub struct Setup<'a> {
@@ -35,7 +37,7 @@ pub struct Setup<'a> {
     pub swap_chain: SwapChain,
     pub render_pass: RenderPass,
     pub depth_device_image: DeviceImage,
-    pub uniform_buffers: Vec<DeviceBuffer>,
+    pub uniform_buffers: Vec<TypedBuffer<UBO>>,
     pub descriptor: Descriptor,
     pub graphics_command: Command,
     pub graphics_command_buffers: Vec<CommandBuffer>,
@@ -72,7 +74,8 @@ impl<'a> Setup<'a> {
 
         // get the structures for commands,
         // they will be filled out later
-        let (graphics_command, graphics_command_buffers, transfer_command) = command::setup(base, swap_chain.image_count)?;
+        let (graphics_command, graphics_command_buffers, transfer_command) =
+            command::setup(base, swap_chain.image_count)?;
 
         // create device buffers from the mesh & load the textures
         // the transfer is done with the transfer command,
@@ -116,9 +119,13 @@ impl<'a> Setup<'a> {
                     graphics_pipeline: graphics_pipeline.0,
                     graphics_pipeline_layout: graphics_pipeline_layout.0,
                     descriptor_set: descriptor.sets[i].0,
+                    dynamic_offsets: &[],
                     vertex_buffer: vertex_device_buffer.buffer.0,
+                    instance_buffers: &[],
                     index_buffer: index_device_buffer.buffer.0,
+                    index_type: mesh.compact_index_type(false),
                     n_indices: mesh.indices.len() as u32,
+                    n_instances: 1,
                 },
             )?;
         }
@@ -26,6 +26,7 @@ pub fn setup(
             support: swap_chain_support,
             surface: surface,
             image_count: 3,
+            capture: false,
             name: "SwapChain".to_string(),
         },
     )?;
@@ -34,6 +35,7 @@ pub fn setup(
         &base,
         &RenderPassSettings {
             swap_chain_format: swap_chain.surface_format.0.format,
+            final_layout: ash::vk::ImageLayout::PRESENT_SRC_KHR,
             name: "RenderPass".to_string(),
         },
     )?;
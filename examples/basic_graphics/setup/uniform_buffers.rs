@@ -2,15 +2,15 @@ use crate::AppError;
 use crate::UBO;
 
 use ash_urn::Base;
-use ash_urn::{DeviceBuffer, DeviceBufferSettings};
+use ash_urn::{TypedBuffer, TypedBufferSettings};
 
-pub fn setup(base: &Base, n_buffer: u32) -> Result<Vec<DeviceBuffer>, AppError> {
+pub fn setup(base: &Base, n_buffer: u32) -> Result<Vec<TypedBuffer<UBO>>, AppError> {
     let mut uniform_buffers = Vec::new();
     for i in 0..n_buffer {
-        uniform_buffers.push(DeviceBuffer::new(
+        uniform_buffers.push(TypedBuffer::new(
             &base,
-            &DeviceBufferSettings {
-                size: std::mem::size_of::<UBO>() as ash::vk::DeviceSize,
+            &TypedBufferSettings {
+                len: 1,
                 usage: ash::vk::BufferUsageFlags::UNIFORM_BUFFER,
                 properties: ash::vk::MemoryPropertyFlags::HOST_VISIBLE
                     | ash::vk::MemoryPropertyFlags::HOST_COHERENT,
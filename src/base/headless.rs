@@ -0,0 +1,211 @@
+use super::queue_families::{COMBINED, DEDICATED_TRANSFER};
+use super::{Base, Entry, Instance, InstanceSettings, LogicalDevice, LogicalDeviceSettings};
+use super::{PhysicalDevice, QueueFamily, QueueSetting, Validation};
+use crate::UrnError;
+
+use ash::version::InstanceV1_0;
+
+pub struct HeadlessSettings {
+    pub name: String,
+    pub enable_validation: bool,
+    pub timelines: bool,
+    /// Prefer a software implementation like lavapipe or SwiftShader,
+    /// their output does not depend on the GPU of the machine
+    pub prefer_cpu: bool,
+}
+
+impl Base {
+    /// A `Base` without surface or swapchain for offscreen rendering and compute.
+    /// `COMBINED` is a graphics and compute family, `DEDICATED_TRANSFER` a transfer
+    /// only family if there is one, the combined family otherwise.
+    /// Without a working driver this is `UrnError::NoDevice` as well.
+    pub fn headless(settings: &HeadlessSettings) -> Result<Self, UrnError> {
+        let entry = Entry::new()?;
+
+        let mut instance_extension_names = vec![];
+        if settings.enable_validation {
+            instance_extension_names.push(
+                ash::extensions::ext::DebugUtils::name()
+                    .to_str()
+                    .unwrap()
+                    .to_string(),
+            );
+        }
+        let instance = Instance::new(
+            InstanceSettings {
+                name: settings.name.clone(),
+                version_major: 1,
+                version_minor: 2,
+                version_patch: 0,
+                extension_names: instance_extension_names,
+                enable_validation: settings.enable_validation,
+                validation_layer_names: vec!["VK_LAYER_KHRONOS_validation".to_string()],
+            },
+            &entry.0,
+        )
+        .map_err(missing_driver)?;
+
+        let created = create_device(&entry.0, &instance.0, settings);
+        let (physical_device, combined_idx, transfer_idx, validation, logical_device) =
+            match created {
+                Ok(created) => created,
+                Err(e) => {
+                    unsafe { instance.0.destroy_instance(None) };
+                    return Err(e);
+                }
+            };
+
+        let families = unsafe {
+            instance
+                .0
+                .get_physical_device_queue_family_properties(physical_device.0)
+        };
+        let mut queue_map = std::collections::HashMap::new();
+        queue_map.insert(
+            COMBINED,
+            QueueFamily {
+                idx: combined_idx,
+                properties: families[combined_idx as usize],
+            },
+        );
+        queue_map.insert(
+            DEDICATED_TRANSFER,
+            QueueFamily {
+                idx: transfer_idx,
+                properties: families[transfer_idx as usize],
+            },
+        );
+
+        let timeline_loader = ash::extensions::khr::TimelineSemaphore::new(&entry.0, &instance.0);
+
+        Ok(Self {
+            entry,
+            instance,
+            validation,
+            physical_device,
+            logical_device,
+            timeline_loader,
+            queue_map,
+        })
+    }
+}
+
+/// Everything between instance and `Base`, destroys what it created on errors.
+fn create_device(
+    entry: &ash::Entry,
+    instance: &ash::Instance,
+    settings: &HeadlessSettings,
+) -> Result<(PhysicalDevice, u32, u32, Option<Validation>, LogicalDevice), UrnError> {
+    let (physical_device, combined_idx, transfer_idx) =
+        pick_headless(instance, settings)?.ok_or(UrnError::NoDevice)?;
+    let validation = if settings.enable_validation {
+        Some(Validation::new(entry, instance)?)
+    } else {
+        None
+    };
+
+    let mut queue_settings = vec![QueueSetting {
+        family_idx: combined_idx,
+        priorities: vec![1.0],
+    }];
+    if transfer_idx != combined_idx {
+        queue_settings.push(QueueSetting {
+            family_idx: transfer_idx,
+            priorities: vec![1.0],
+        });
+    }
+    let mut extension_names = vec![];
+    if settings.timelines {
+        extension_names.push("VK_KHR_timeline_semaphore".to_string());
+    }
+    let logical_device = LogicalDevice::new(
+        instance,
+        physical_device.0,
+        LogicalDeviceSettings {
+            extension_names,
+            enable_validation: settings.enable_validation,
+            validation_layer_names: vec!["VK_LAYER_KHRONOS_validation".to_string()],
+            queue_settings,
+            timelines: settings.timelines,
+            descriptor_indexing: false,
+            index_type_uint8: false,
+            multi_draw_indirect: false,
+            draw_indirect_count: false,
+        },
+    );
+    match logical_device {
+        Ok(logical_device) => Ok((
+            physical_device,
+            combined_idx,
+            transfer_idx,
+            validation,
+            logical_device,
+        )),
+        Err(e) => {
+            if let Some(v) = validation {
+                unsafe {
+                    v.debug_utils_loader
+                        .destroy_debug_utils_messenger(v.debug_messenger, None)
+                };
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Device with its combined and transfer family
+fn pick_headless(
+    instance: &ash::Instance,
+    settings: &HeadlessSettings,
+) -> Result<Option<(PhysicalDevice, u32, u32)>, UrnError> {
+    let mut physical_devices = PhysicalDevice::enumerate(instance).map_err(missing_driver)?;
+    if settings.prefer_cpu {
+        // stable sort keeps the driver order otherwise
+        physical_devices.sort_by_key(|pd| {
+            let properties = unsafe { instance.get_physical_device_properties(pd.0) };
+            properties.device_type != ash::vk::PhysicalDeviceType::CPU
+        });
+    }
+
+    for pd in physical_devices {
+        if settings.timelines && !pd.check_timeline_feature(instance) {
+            continue;
+        }
+        let families = unsafe { instance.get_physical_device_queue_family_properties(pd.0) };
+        let usable = |idx: &usize| families[*idx].queue_count > 0;
+        let combined = (0..families.len()).filter(usable).find(|idx| {
+            families[*idx]
+                .queue_flags
+                .contains(ash::vk::QueueFlags::GRAPHICS | ash::vk::QueueFlags::COMPUTE)
+        });
+        let combined = match combined {
+            Some(combined) => combined,
+            None => continue,
+        };
+        let transfer = (0..families.len())
+            .filter(usable)
+            .find(|idx| {
+                let flags = families[*idx].queue_flags;
+                flags.contains(ash::vk::QueueFlags::TRANSFER)
+                    && !flags
+                        .intersects(ash::vk::QueueFlags::GRAPHICS | ash::vk::QueueFlags::COMPUTE)
+            })
+            .unwrap_or(combined);
+        return Ok(Some((pd, combined as u32, transfer as u32)));
+    }
+    Ok(None)
+}
+
+/// A loader without a usable driver has no device either.
+fn missing_driver(e: UrnError) -> UrnError {
+    match e {
+        UrnError::AshInstanceError(ash::InstanceError::VkError(result))
+        | UrnError::AshError(result)
+            if result == ash::vk::Result::ERROR_INCOMPATIBLE_DRIVER
+                || result == ash::vk::Result::ERROR_INITIALIZATION_FAILED =>
+        {
+            UrnError::NoDevice
+        }
+        e => e,
+    }
+}
@@ -3,7 +3,18 @@ use crate::UrnError;
 
 use ash::version::InstanceV1_0;
 
-pub struct LogicalDevice(pub ash::Device);
+/// The device with the extensions and optional features it was created with
+pub struct LogicalDevice(pub ash::Device, pub Vec<String>, pub EnabledFeatures);
+
+/// Optional features of `LogicalDeviceSettings` that were enabled
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EnabledFeatures {
+    pub timelines: bool,
+    pub descriptor_indexing: bool,
+    pub index_type_uint8: bool,
+    pub multi_draw_indirect: bool,
+    pub draw_indirect_count: bool,
+}
 
 pub struct QueueSetting {
     pub family_idx: u32,
@@ -16,6 +27,14 @@ pub struct LogicalDeviceSettings {
     pub validation_layer_names: Vec<String>,
     pub queue_settings: Vec<QueueSetting>,
     pub timelines: bool,
+    /// Adds `VK_EXT_descriptor_indexing` to the extensions on devices below Vulkan 1.2
+    pub descriptor_indexing: bool,
+    /// Needs `VK_EXT_index_type_uint8` in `extension_names`
+    pub index_type_uint8: bool,
+    /// Indirect draws with a `draw_count` above one
+    pub multi_draw_indirect: bool,
+    /// The Vulkan 1.2 core feature, not needed with `VK_KHR_draw_indirect_count`
+    pub draw_indirect_count: bool,
 }
 
 impl LogicalDevice {
@@ -33,22 +52,93 @@ impl LogicalDevice {
             queue_create_infos.push(queue_create_info);
         }
 
-        let extension_names_cs = StringContainer::new(settings.extension_names.clone());
+        // descriptor indexing is core since 1.2, an extension before
+        let mut extension_names = settings.extension_names;
+        let api_version =
+            unsafe { instance.get_physical_device_properties(physical_device) }.api_version;
+        if settings.descriptor_indexing && api_version < ash::vk::make_version(1, 2, 0) {
+            for name in &["VK_KHR_maintenance3", "VK_EXT_descriptor_indexing"] {
+                if !extension_names.iter().any(|e| e == name) {
+                    extension_names.push(name.to_string());
+                }
+            }
+        }
+        let extension_names_cs = StringContainer::new(extension_names.clone());
 
         let mut timeline_feature = ash::vk::PhysicalDeviceTimelineSemaphoreFeatures::builder()
             .timeline_semaphore(true)
             .build();
-        let next_ptr = &mut timeline_feature
-            as *mut ash::vk::PhysicalDeviceTimelineSemaphoreFeatures
-            as *mut ash::vk::BaseOutStructure;
-        let mut physical_device_features_2 = ash::vk::PhysicalDeviceFeatures2::builder().build();
+        let mut indexing_feature = ash::vk::PhysicalDeviceDescriptorIndexingFeatures::builder()
+            .shader_sampled_image_array_non_uniform_indexing(true)
+            .shader_storage_buffer_array_non_uniform_indexing(true)
+            .descriptor_binding_sampled_image_update_after_bind(true)
+            .descriptor_binding_storage_buffer_update_after_bind(true)
+            .descriptor_binding_update_unused_while_pending(true)
+            .descriptor_binding_partially_bound(true)
+            .descriptor_binding_variable_descriptor_count(true)
+            .runtime_descriptor_array(true)
+            .build();
+        let mut uint8_feature = ash::vk::PhysicalDeviceIndexTypeUint8FeaturesEXT::builder()
+            .index_type_uint8(true)
+            .build();
+        // must not be chained together with the timeline and indexing structs
+        let vulkan12 = settings.draw_indirect_count;
+        let mut vulkan12_feature = ash::vk::PhysicalDeviceVulkan12Features::builder()
+            .draw_indirect_count(true)
+            .timeline_semaphore(settings.timelines)
+            .shader_sampled_image_array_non_uniform_indexing(settings.descriptor_indexing)
+            .shader_storage_buffer_array_non_uniform_indexing(settings.descriptor_indexing)
+            .descriptor_binding_sampled_image_update_after_bind(settings.descriptor_indexing)
+            .descriptor_binding_storage_buffer_update_after_bind(settings.descriptor_indexing)
+            .descriptor_binding_update_unused_while_pending(settings.descriptor_indexing)
+            .descriptor_binding_partially_bound(settings.descriptor_indexing)
+            .descriptor_binding_variable_descriptor_count(settings.descriptor_indexing)
+            .runtime_descriptor_array(settings.descriptor_indexing)
+            .build();
+
+        // chain the enabled features behind features2
+        let mut next_ptr: *mut ash::vk::BaseOutStructure = std::ptr::null_mut();
+        if settings.index_type_uint8 {
+            uint8_feature.p_next = next_ptr as _;
+            next_ptr = &mut uint8_feature as *mut ash::vk::PhysicalDeviceIndexTypeUint8FeaturesEXT
+                as *mut ash::vk::BaseOutStructure;
+        }
+        if settings.descriptor_indexing && !vulkan12 {
+            indexing_feature.p_next = next_ptr as _;
+            next_ptr = &mut indexing_feature
+                as *mut ash::vk::PhysicalDeviceDescriptorIndexingFeatures
+                as *mut ash::vk::BaseOutStructure;
+        }
+        if settings.timelines && !vulkan12 {
+            timeline_feature.p_next = next_ptr as _;
+            next_ptr = &mut timeline_feature
+                as *mut ash::vk::PhysicalDeviceTimelineSemaphoreFeatures
+                as *mut ash::vk::BaseOutStructure;
+        }
+        if vulkan12 {
+            vulkan12_feature.p_next = next_ptr as _;
+            next_ptr = &mut vulkan12_feature as *mut ash::vk::PhysicalDeviceVulkan12Features
+                as *mut ash::vk::BaseOutStructure;
+        }
+        let mut physical_device_features_2 = ash::vk::PhysicalDeviceFeatures2::builder()
+            .features(
+                ash::vk::PhysicalDeviceFeatures::builder()
+                    .multi_draw_indirect(settings.multi_draw_indirect)
+                    .build(),
+            )
+            .build();
         physical_device_features_2.p_next = next_ptr as _;
 
         let device_create_info = ash::vk::DeviceCreateInfo::builder()
             .queue_create_infos(queue_create_infos.as_slice())
             .enabled_extension_names(extension_names_cs.pointer.as_slice());
 
-        let device_create_info = if settings.timelines {
+        let device_create_info = if settings.timelines
+            || settings.descriptor_indexing
+            || settings.index_type_uint8
+            || settings.multi_draw_indirect
+            || settings.draw_indirect_count
+        {
             device_create_info.push_next(&mut physical_device_features_2)
         } else {
             device_create_info
@@ -65,6 +155,17 @@ impl LogicalDevice {
         let logical_device =
             unsafe { instance.create_device(physical_device, &device_create_info, None)? };
 
-        Ok(Self(logical_device))
+        let features = EnabledFeatures {
+            timelines: settings.timelines,
+            descriptor_indexing: settings.descriptor_indexing,
+            index_type_uint8: settings.index_type_uint8,
+            multi_draw_indirect: settings.multi_draw_indirect,
+            draw_indirect_count: settings.draw_indirect_count,
+        };
+        Ok(Self(logical_device, extension_names, features))
+    }
+
+    pub fn has_extension(&self, name: &std::ffi::CStr) -> bool {
+        self.1.iter().any(|e| e.as_bytes() == name.to_bytes())
     }
 }
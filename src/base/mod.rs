@@ -1,6 +1,7 @@
 use crate::UrnError;
 
 pub mod entry;
+pub mod headless;
 pub mod instance;
 pub mod logical_device;
 pub mod physical_device;
@@ -9,8 +10,9 @@ pub mod swapchain;
 pub mod validation;
 
 pub use entry::Entry;
+pub use headless::HeadlessSettings;
 pub use instance::{Instance, InstanceSettings};
-pub use logical_device::{LogicalDevice, LogicalDeviceSettings, QueueSetting};
+pub use logical_device::{EnabledFeatures, LogicalDevice, LogicalDeviceSettings, QueueSetting};
 pub use physical_device::{PhysicalDevice, PhysicalDeviceSettings};
 pub use queue_families::{QueueFamily, QueueFamilyKey};
 pub use swapchain::SwapChainSupportDetail;
@@ -13,6 +13,11 @@ pub struct PhysicalDevice(pub ash::vk::PhysicalDevice);
 pub struct PhysicalDeviceSettings {
     pub timelines: bool,
     pub subgroups: bool,
+    pub descriptor_indexing: bool,
+    pub index_type_uint8: bool,
+    pub multi_draw_indirect: bool,
+    /// The Vulkan 1.2 core feature, not needed with `VK_KHR_draw_indirect_count`
+    pub draw_indirect_count: bool,
 }
 
 impl PhysicalDevice {
@@ -90,6 +95,11 @@ impl PhysicalDevice {
         Ok(device_properties.limits.timestamp_period)
     }
 
+    pub fn limits(&self, instance: &ash::Instance) -> ash::vk::PhysicalDeviceLimits {
+        let device_properties = unsafe { instance.get_physical_device_properties(self.0) };
+        device_properties.limits
+    }
+
     pub fn check_timeline_feature(&self, instance: &ash::Instance) -> bool {
         let mut timeline_feature = ash::vk::PhysicalDeviceTimelineSemaphoreFeatures::builder()
             .timeline_semaphore(false)
@@ -103,6 +113,57 @@ impl PhysicalDevice {
         timeline_feature.timeline_semaphore != 0
     }
 
+    /// Everything the bindless descriptor set relies on
+    pub fn check_descriptor_indexing_feature(&self, instance: &ash::Instance) -> bool {
+        let mut indexing_feature =
+            ash::vk::PhysicalDeviceDescriptorIndexingFeatures::builder().build();
+        let mut physical_device_features2 = ash::vk::PhysicalDeviceFeatures2::builder().build();
+        let next_ptr = &mut indexing_feature
+            as *mut ash::vk::PhysicalDeviceDescriptorIndexingFeatures
+            as *mut ash::vk::BaseOutStructure;
+        physical_device_features2.p_next = next_ptr as _;
+        unsafe { instance.get_physical_device_features2(self.0, &mut physical_device_features2) };
+        indexing_feature.shader_sampled_image_array_non_uniform_indexing != 0
+            && indexing_feature.shader_storage_buffer_array_non_uniform_indexing != 0
+            && indexing_feature.descriptor_binding_sampled_image_update_after_bind != 0
+            && indexing_feature.descriptor_binding_storage_buffer_update_after_bind != 0
+            && indexing_feature.descriptor_binding_update_unused_while_pending != 0
+            && indexing_feature.descriptor_binding_partially_bound != 0
+            && indexing_feature.descriptor_binding_variable_descriptor_count != 0
+            && indexing_feature.runtime_descriptor_array != 0
+    }
+
+    /// Also needs `VK_EXT_index_type_uint8` in the device extensions
+    pub fn check_index_type_uint8_feature(&self, instance: &ash::Instance) -> bool {
+        let mut uint8_feature = ash::vk::PhysicalDeviceIndexTypeUint8FeaturesEXT::builder().build();
+        let mut physical_device_features2 = ash::vk::PhysicalDeviceFeatures2::builder().build();
+        let next_ptr = &mut uint8_feature as *mut ash::vk::PhysicalDeviceIndexTypeUint8FeaturesEXT
+            as *mut ash::vk::BaseOutStructure;
+        physical_device_features2.p_next = next_ptr as _;
+        unsafe { instance.get_physical_device_features2(self.0, &mut physical_device_features2) };
+        uint8_feature.index_type_uint8 != 0
+    }
+
+    pub fn check_multi_draw_indirect_feature(&self, instance: &ash::Instance) -> bool {
+        let device_features = unsafe { instance.get_physical_device_features(self.0) };
+        device_features.multi_draw_indirect != 0
+    }
+
+    /// Devices below Vulkan 1.2 only have `VK_KHR_draw_indirect_count`
+    pub fn check_draw_indirect_count_feature(&self, instance: &ash::Instance) -> bool {
+        let device_properties = unsafe { instance.get_physical_device_properties(self.0) };
+        if device_properties.api_version < ash::vk::make_version(1, 2, 0) {
+            return false;
+        }
+        let mut vulkan12_feature = ash::vk::PhysicalDeviceVulkan12Features::builder().build();
+        let mut physical_device_features2 = ash::vk::PhysicalDeviceFeatures2::builder().build();
+        let next_ptr = &mut vulkan12_feature as *mut ash::vk::PhysicalDeviceVulkan12Features
+            as *mut ash::vk::BaseOutStructure;
+        physical_device_features2.p_next = next_ptr as _;
+        unsafe { instance.get_physical_device_features2(self.0, &mut physical_device_features2) };
+        vulkan12_feature.draw_indirect_count != 0
+    }
+
     pub fn query_subgroup_properties(
         &self,
         instance: &ash::Instance,
@@ -234,6 +295,26 @@ impl PhysicalDevice {
                 device_ok = false;
             }
 
+            if settings.descriptor_indexing && !pd.check_descriptor_indexing_feature(instance) {
+                println!("Descriptor indexing not available.");
+                device_ok = false;
+            }
+
+            if settings.index_type_uint8 && !pd.check_index_type_uint8_feature(instance) {
+                println!("Uint8 indices not available.");
+                device_ok = false;
+            }
+
+            if settings.multi_draw_indirect && !pd.check_multi_draw_indirect_feature(instance) {
+                println!("Multi draw indirect not available.");
+                device_ok = false;
+            }
+
+            if settings.draw_indirect_count && !pd.check_draw_indirect_count_feature(instance) {
+                println!("Draw indirect count not available.");
+                device_ok = false;
+            }
+
             if settings.subgroups {
                 let subgroup_properties = pd.query_subgroup_properties(instance);
                 if !subgroup_properties
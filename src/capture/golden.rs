@@ -0,0 +1,116 @@
+use crate::UrnError;
+
+use std::path::{Path, PathBuf};
+
+/// Set to overwrite the stored references with the current output.
+pub const BLESS_VAR: &str = "URN_BLESS";
+
+pub struct Comparison {
+    /// Pixels with a channel off by more than the tolerance
+    pub mismatched: usize,
+    pub max_difference: u8,
+    /// Mismatches in red, scaled by their difference, the rest dimmed
+    pub diff: ::image::RgbaImage,
+}
+
+pub fn compare(
+    actual: &::image::RgbaImage,
+    expected: &::image::RgbaImage,
+    tolerance: u8,
+) -> Result<Comparison, UrnError> {
+    if actual.dimensions() != expected.dimensions() {
+        return Err(UrnError::GenericDynamic(format!(
+            "Image is {:?}, the reference {:?}.",
+            actual.dimensions(),
+            expected.dimensions()
+        )));
+    }
+
+    let (width, height) = actual.dimensions();
+    let mut diff = ::image::RgbaImage::new(width, height);
+    let mut mismatched = 0;
+    let mut max_difference = 0;
+    for ((a, e), d) in actual
+        .pixels()
+        .zip(expected.pixels())
+        .zip(diff.pixels_mut())
+    {
+        let difference =
+            a.0.iter()
+                .zip(e.0.iter())
+                .map(|(a, e)| (*a as i16 - *e as i16).unsigned_abs() as u8)
+                .max()
+                .unwrap_or(0);
+        max_difference = max_difference.max(difference);
+        *d = if difference > tolerance {
+            mismatched += 1;
+            ::image::Rgba([128 + difference / 2, 0, 0, 255])
+        } else {
+            let luma = ((e[0] as u16 + e[1] as u16 + e[2] as u16) / 12) as u8;
+            ::image::Rgba([luma, luma, luma, 255])
+        };
+    }
+
+    Ok(Comparison {
+        mismatched,
+        max_difference,
+        diff,
+    })
+}
+
+pub struct GoldenSettings {
+    pub reference: PathBuf,
+    /// Allowed difference per channel
+    pub tolerance: u8,
+    /// Pixels allowed to exceed the tolerance
+    pub max_mismatched: usize,
+}
+
+/// Path next to the reference, `scene.png` becomes `scene.<suffix>.png`.
+pub fn sibling(reference: &Path, suffix: &str) -> PathBuf {
+    let stem = reference
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    reference.with_file_name(format!("{}.{}.png", stem, suffix))
+}
+
+/// Compares against the stored reference, writes `.actual.png` and `.diff.png`
+/// next to it on failure. A missing reference is an error, while `BLESS_VAR`
+/// is set the reference is written instead and everything passes.
+pub fn check_golden(
+    actual: &::image::RgbaImage,
+    settings: &GoldenSettings,
+) -> Result<(), UrnError> {
+    if std::env::var_os(BLESS_VAR).is_some() {
+        if let Some(parent) = settings.reference.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        actual.save_with_format(&settings.reference, ::image::ImageFormat::Png)?;
+        println!("Wrote reference {:?}.", settings.reference);
+        return Ok(());
+    }
+    if !settings.reference.exists() {
+        return Err(UrnError::GenericDynamic(format!(
+            "Reference {:?} is missing, run with {} set to write it.",
+            settings.reference, BLESS_VAR
+        )));
+    }
+
+    let expected = ::image::open(&settings.reference)?.to_rgba();
+    let comparison = compare(actual, &expected, settings.tolerance)?;
+    if comparison.mismatched <= settings.max_mismatched {
+        return Ok(());
+    }
+
+    let actual_path = sibling(&settings.reference, "actual");
+    let diff_path = sibling(&settings.reference, "diff");
+    actual.save_with_format(&actual_path, ::image::ImageFormat::Png)?;
+    comparison
+        .diff
+        .save_with_format(&diff_path, ::image::ImageFormat::Png)?;
+    Err(UrnError::GenericDynamic(format!(
+        "{} pixels differ from {:?} by up to {}, see {:?}.",
+        comparison.mismatched, settings.reference, comparison.max_difference, diff_path
+    )))
+}
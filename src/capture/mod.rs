@@ -0,0 +1,144 @@
+pub mod golden;
+pub mod offscreen;
+pub mod sequence;
+
+pub use golden::{check_golden, GoldenSettings};
+pub use offscreen::{OffscreenTarget, OffscreenTargetSettings};
+pub use sequence::{FrameSequence, FrameSequenceSettings};
+
+use crate::transfer::{readback_image_bytes, to_rgb_f32, to_rgba, ReadbackImageSettings};
+use crate::Base;
+use crate::SwapChain;
+use crate::UrnError;
+
+use std::path::Path;
+
+/// Texels copied back from a color image, not yet converted.
+pub struct Capture {
+    pub width: u32,
+    pub height: u32,
+    pub format: ash::vk::Format,
+    pub bytes: Vec<u8>,
+}
+
+impl Capture {
+    /// Works for swapchain images and offscreen targets alike, see `readback_image`.
+    pub fn new(base: &Base, settings: &ReadbackImageSettings) -> Result<Self, UrnError> {
+        Ok(Self {
+            width: settings.width,
+            height: settings.height,
+            format: settings.format,
+            bytes: readback_image_bytes(base, settings)?,
+        })
+    }
+
+    /// Captures a rendered swapchain image before it is presented. `queue` has to be
+    /// the one that rendered it and the swapchain needs to be created with `capture`.
+    pub fn swap_chain_image(
+        base: &Base,
+        swap_chain: &SwapChain,
+        index: usize,
+        queue: ash::vk::Queue,
+        pool: ash::vk::CommandPool,
+    ) -> Result<Self, UrnError> {
+        let element = swap_chain
+            .elements
+            .get(index)
+            .ok_or(UrnError::Generic("Swapchain image index out of range."))?;
+        Self::new(
+            base,
+            &ReadbackImageSettings {
+                queue,
+                pool,
+                image: element.image,
+                width: swap_chain.extent.0.width,
+                height: swap_chain.extent.0.height,
+                format: swap_chain.surface_format.0.format,
+                layout: ash::vk::ImageLayout::PRESENT_SRC_KHR,
+            },
+        )
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(
+            self.format,
+            ash::vk::Format::R16G16B16A16_SFLOAT
+                | ash::vk::Format::R32G32B32A32_SFLOAT
+                | ash::vk::Format::R32_SFLOAT
+        )
+    }
+
+    pub fn to_rgba(&self) -> Result<::image::RgbaImage, UrnError> {
+        to_rgba(self.width, self.height, self.format, &self.bytes)
+    }
+
+    pub fn to_rgb_f32(&self) -> Result<Vec<::image::Rgb<f32>>, UrnError> {
+        to_rgb_f32(self.width, self.height, self.format, &self.bytes)
+    }
+
+    /// Float targets hold linear color, it is encoded as sRGB like a swapchain would
+    /// before it is clamped. Other formats are written as they are.
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> Result<(), UrnError> {
+        let mut rgba = self.to_rgba()?;
+        if self.is_float() {
+            for (pixel, linear) in rgba.pixels_mut().zip(self.to_rgb_f32()?) {
+                for (c, l) in pixel.0.iter_mut().zip(&linear.0) {
+                    *c = (linear_to_srgb(*l).clamp(0.0, 1.0) * 255.0).round() as u8;
+                }
+            }
+        }
+        rgba.save_with_format(path, ::image::ImageFormat::Png)?;
+        Ok(())
+    }
+
+    /// OpenEXR with linear float channels, alpha is dropped.
+    #[cfg(feature = "exr")]
+    pub fn save_exr<P: AsRef<Path>>(&self, path: P) -> Result<(), UrnError> {
+        let texels = self.to_rgb_f32()?;
+        let width = self.width as usize;
+        exr::prelude::write_rgb_file(path, width, self.height as usize, |x, y| {
+            let texel = texels[y * width + x].0;
+            (texel[0], texel[1], texel[2])
+        })?;
+        Ok(())
+    }
+
+    /// Radiance HDR keeps the range of float targets, with less precision than EXR.
+    pub fn save_hdr<P: AsRef<Path>>(&self, path: P) -> Result<(), UrnError> {
+        let texels = self.to_rgb_f32()?;
+        let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        ::image::hdr::HDREncoder::new(file).encode(
+            &texels,
+            self.width as usize,
+            self.height as usize,
+        )?;
+        Ok(())
+    }
+
+    /// Picks the encoder from the extension, `exr` or `hdr` for float data, `png` otherwise.
+    /// `exr` needs the feature of the same name.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), UrnError> {
+        match path.as_ref().extension().and_then(|e| e.to_str()) {
+            #[cfg(feature = "exr")]
+            Some("exr") => self.save_exr(path),
+            #[cfg(not(feature = "exr"))]
+            Some("exr") => Err(UrnError::Generic(
+                "Saving exr captures needs the exr feature.",
+            )),
+            Some("hdr") => self.save_hdr(path),
+            Some("png") => self.save_png(path),
+            _ => Err(UrnError::GenericDynamic(format!(
+                "Can not save capture to {:?}, expected a png, exr or hdr file.",
+                path.as_ref()
+            ))),
+        }
+    }
+}
+
+fn linear_to_srgb(linear: f32) -> f32 {
+    if linear <= 0.003_130_8 {
+        linear * 12.92
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    }
+}
@@ -0,0 +1,99 @@
+use super::Capture;
+use crate::device_image::create_depth_device_image;
+use crate::transfer::ReadbackImageSettings;
+use crate::Base;
+use crate::UrnError;
+use crate::{DeviceImage, DeviceImageSettings};
+
+use ash::version::DeviceV1_0;
+
+pub struct OffscreenTargetSettings {
+    pub extent: ash::vk::Extent2D,
+    pub format: ash::vk::Format,
+    /// Color and depth attachment like `RenderPass`
+    pub render_pass: ash::vk::RenderPass,
+    pub name: String,
+}
+
+/// Color and depth image with a frame buffer to render to instead of the swapchain.
+pub struct OffscreenTarget {
+    pub color: DeviceImage,
+    pub depth: DeviceImage,
+    pub frame_buffer: ash::vk::Framebuffer,
+    pub extent: ash::vk::Extent2D,
+    pub format: ash::vk::Format,
+}
+
+impl OffscreenTarget {
+    pub fn new(base: &Base, settings: &OffscreenTargetSettings) -> Result<Self, UrnError> {
+        let color = DeviceImage::new(
+            base,
+            &DeviceImageSettings {
+                width: settings.extent.width,
+                height: settings.extent.height,
+                format: settings.format,
+                tiling: ash::vk::ImageTiling::OPTIMAL,
+                usage: ash::vk::ImageUsageFlags::COLOR_ATTACHMENT
+                    | ash::vk::ImageUsageFlags::TRANSFER_SRC,
+                properties: ash::vk::MemoryPropertyFlags::DEVICE_LOCAL,
+                aspect_flags: ash::vk::ImageAspectFlags::COLOR,
+                name: format!("{}Color", settings.name),
+            },
+        )?;
+        let depth = create_depth_device_image(base, settings.extent)?;
+
+        let attachments = [color.view.0, depth.view.0];
+        let frame_buffer_info = ash::vk::FramebufferCreateInfo::builder()
+            .render_pass(settings.render_pass)
+            .attachments(&attachments)
+            .width(settings.extent.width)
+            .height(settings.extent.height)
+            .layers(1);
+        let frame_buffer = unsafe {
+            base.logical_device
+                .0
+                .create_framebuffer(&frame_buffer_info, None)?
+        };
+        base.name_object(frame_buffer, format!("{}FrameBuffer", settings.name))?;
+
+        Ok(Self {
+            color,
+            depth,
+            frame_buffer,
+            extent: settings.extent,
+            format: settings.format,
+        })
+    }
+
+    /// `layout` is the one the render pass left the color image in.
+    pub fn capture(
+        &self,
+        base: &Base,
+        queue: ash::vk::Queue,
+        pool: ash::vk::CommandPool,
+        layout: ash::vk::ImageLayout,
+    ) -> Result<Capture, UrnError> {
+        Capture::new(
+            base,
+            &ReadbackImageSettings {
+                queue,
+                pool,
+                image: self.color.image.0,
+                width: self.extent.width,
+                height: self.extent.height,
+                format: self.format,
+                layout,
+            },
+        )
+    }
+
+    pub fn destroy(&self, base: &Base) {
+        unsafe {
+            base.logical_device
+                .0
+                .destroy_framebuffer(self.frame_buffer, None)
+        };
+        self.color.destroy(base);
+        self.depth.destroy(base);
+    }
+}
@@ -0,0 +1,51 @@
+use super::Capture;
+use crate::UrnError;
+
+use std::path::PathBuf;
+
+pub struct FrameSequenceSettings {
+    pub directory: PathBuf,
+    pub prefix: String,
+    /// `png`, `exr` or `hdr`
+    pub extension: String,
+}
+
+/// Numbered captures `<prefix>_00000.<extension>`, ... to turn a run into a video.
+pub struct FrameSequence {
+    directory: PathBuf,
+    prefix: String,
+    extension: String,
+    frames: u32,
+}
+
+impl FrameSequence {
+    pub fn new(settings: &FrameSequenceSettings) -> Result<Self, UrnError> {
+        std::fs::create_dir_all(&settings.directory)?;
+        Ok(Self {
+            directory: settings.directory.clone(),
+            prefix: settings.prefix.clone(),
+            extension: settings.extension.clone(),
+            frames: 0,
+        })
+    }
+
+    /// Frames saved so far
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+
+    pub fn next_path(&self) -> PathBuf {
+        self.directory.join(format!(
+            "{}_{:05}.{}",
+            self.prefix, self.frames, self.extension
+        ))
+    }
+
+    /// Saves the capture as the next frame and returns its path.
+    pub fn save(&mut self, capture: &Capture) -> Result<PathBuf, UrnError> {
+        let path = self.next_path();
+        capture.save(&path)?;
+        self.frames += 1;
+        Ok(path)
+    }
+}
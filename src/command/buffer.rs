@@ -5,27 +5,110 @@ use ash::version::DeviceV1_0;
 
 pub struct CommandBuffer(pub ash::vk::CommandBuffer);
 
+fn allocate(
+    base: &Base,
+    pool: ash::vk::CommandPool,
+    level: ash::vk::CommandBufferLevel,
+    n_buffer: u32,
+) -> Result<Vec<ash::vk::CommandBuffer>, UrnError> {
+    let alloc_info = ash::vk::CommandBufferAllocateInfo::builder()
+        .command_pool(pool)
+        .level(level)
+        .command_buffer_count(n_buffer);
+
+    Ok(unsafe {
+        base.logical_device
+            .0
+            .allocate_command_buffers(&alloc_info)?
+    })
+}
+
 impl CommandBuffer {
     pub fn alloc(base: &Base, pool: ash::vk::CommandPool, name: String) -> Result<Self, UrnError> {
-        let alloc_info = ash::vk::CommandBufferAllocateInfo::builder()
-            .command_pool(pool)
-            .level(ash::vk::CommandBufferLevel::PRIMARY)
-            .command_buffer_count(1);
+        Self::alloc_level(base, pool, ash::vk::CommandBufferLevel::PRIMARY, name)
+    }
+
+    pub fn alloc_vec(
+        base: &Base,
+        pool: ash::vk::CommandPool,
+        n_buffer: u32,
+        name: String,
+    ) -> Result<Vec<Self>, UrnError> {
+        Self::alloc_level_vec(
+            base,
+            pool,
+            ash::vk::CommandBufferLevel::PRIMARY,
+            n_buffer,
+            name,
+        )
+    }
+
+    /// Recorded with `CommandRecorder::begin_secondary`, run with `execute_commands`.
+    pub fn alloc_secondary(
+        base: &Base,
+        pool: ash::vk::CommandPool,
+        name: String,
+    ) -> Result<Self, UrnError> {
+        Self::alloc_level(base, pool, ash::vk::CommandBufferLevel::SECONDARY, name)
+    }
 
-        let buffers = unsafe {
+    pub fn alloc_secondary_vec(
+        base: &Base,
+        pool: ash::vk::CommandPool,
+        n_buffer: u32,
+        name: String,
+    ) -> Result<Vec<Self>, UrnError> {
+        Self::alloc_level_vec(
+            base,
+            pool,
+            ash::vk::CommandBufferLevel::SECONDARY,
+            n_buffer,
+            name,
+        )
+    }
+
+    /// Needs a pool created with `RESET_COMMAND_BUFFER`,
+    /// otherwise reset the whole pool instead.
+    pub fn reset(&self, base: &Base) -> Result<(), UrnError> {
+        unsafe {
             base.logical_device
                 .0
-                .allocate_command_buffers(&alloc_info)?
+                .reset_command_buffer(self.0, ash::vk::CommandBufferResetFlags::empty())?
         };
+        Ok(())
+    }
+
+    /// `pool` has to be the one the buffer was allocated from.
+    pub fn free(self, base: &Base, pool: ash::vk::CommandPool) {
+        unsafe { base.logical_device.0.free_command_buffers(pool, &[self.0]) };
+    }
+
+    /// Counterpart of `alloc_vec`, all buffers have to come from `pool`.
+    pub fn free_vec(base: &Base, pool: ash::vk::CommandPool, buffers: Vec<Self>) {
+        if buffers.is_empty() {
+            return;
+        }
+        let buffers: Vec<ash::vk::CommandBuffer> = buffers.into_iter().map(|b| b.0).collect();
+        unsafe { base.logical_device.0.free_command_buffers(pool, &buffers) };
+    }
+
+    pub fn alloc_level(
+        base: &Base,
+        pool: ash::vk::CommandPool,
+        level: ash::vk::CommandBufferLevel,
+        name: String,
+    ) -> Result<Self, UrnError> {
+        let buffers = allocate(base, pool, level, 1)?;
         let buffer = buffers.first().unwrap();
         base.name_object(*buffer, name)?;
 
         Ok(Self(*buffer))
     }
 
-    pub fn alloc_vec(
+    pub fn alloc_level_vec(
         base: &Base,
         pool: ash::vk::CommandPool,
+        level: ash::vk::CommandBufferLevel,
         n_buffer: u32,
         name: String,
     ) -> Result<Vec<Self>, UrnError> {
@@ -33,16 +116,7 @@ impl CommandBuffer {
             return Ok(Vec::new());
         }
 
-        let alloc_info = ash::vk::CommandBufferAllocateInfo::builder()
-            .command_pool(pool)
-            .level(ash::vk::CommandBufferLevel::PRIMARY)
-            .command_buffer_count(n_buffer);
-
-        let buffers = unsafe {
-            base.logical_device
-                .0
-                .allocate_command_buffers(&alloc_info)?
-        };
+        let buffers = allocate(base, pool, level, n_buffer)?;
         for i in 0..n_buffer {
             base.name_object(buffers[i as usize], format!("{}_{}", name, i))?;
         }
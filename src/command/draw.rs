@@ -2,7 +2,7 @@ use crate::Base;
 use crate::Timestamp;
 use crate::UrnError;
 
-use ash::version::DeviceV1_0;
+use super::recorder::{clear_color, clear_depth_stencil, CommandRecorder};
 
 pub struct DrawIndexedSettings<'a> {
     pub command_buffer: ash::vk::CommandBuffer,
@@ -13,106 +13,64 @@ pub struct DrawIndexedSettings<'a> {
     pub graphics_pipeline: ash::vk::Pipeline,
     pub graphics_pipeline_layout: ash::vk::PipelineLayout,
     pub descriptor_set: ash::vk::DescriptorSet,
+    pub dynamic_offsets: &'a [u32],
     pub vertex_buffer: ash::vk::Buffer,
+    /// Bound to the bindings after the vertex buffer, in order
+    pub instance_buffers: &'a [ash::vk::Buffer],
     pub index_buffer: ash::vk::Buffer,
+    /// e.g. `Index::TYPE` or `Indices::index_type`
+    pub index_type: ash::vk::IndexType,
     pub n_indices: u32,
+    pub n_instances: u32,
 }
 
+/// Records the whole command buffer, clearing to black.
 pub fn indexed(base: &Base, settings: &DrawIndexedSettings) -> Result<(), UrnError> {
     let clear_values = [
-        ash::vk::ClearValue {
-            color: ash::vk::ClearColorValue {
-                float32: [0.0, 0.0, 0.0, 1.0],
-            },
-        },
-        ash::vk::ClearValue {
-            depth_stencil: ash::vk::ClearDepthStencilValue {
-                depth: 1.0,
-                stencil: 0,
-            },
-        },
+        clear_color([0.0, 0.0, 0.0, 1.0]),
+        clear_depth_stencil(1.0, 0),
     ];
+    let vertex_buffers: Vec<ash::vk::Buffer> = std::iter::once(settings.vertex_buffer)
+        .chain(settings.instance_buffers.iter().copied())
+        .collect();
 
-    let render_pass_info = ash::vk::RenderPassBeginInfo::builder()
-        .render_pass(settings.render_pass)
-        .framebuffer(settings.frame_buffer)
-        .render_area(ash::vk::Rect2D {
-            offset: ash::vk::Offset2D { x: 0, y: 0 },
-            extent: settings.extent,
-        })
-        .clear_values(&clear_values);
-
-    let vertex_buffers = [settings.vertex_buffer];
-    let offsets = [0];
-    let dynamic_offsets = [];
-    let descriptor_sets = [settings.descriptor_set];
-    let begin_info = ash::vk::CommandBufferBeginInfo::builder();
-    unsafe {
-        base.logical_device
-            .0
-            .begin_command_buffer(settings.command_buffer, &begin_info)?;
-
-        settings.timestamp.reset_pool(base, settings.command_buffer);
-        settings.timestamp.mark(
-            base,
-            settings.command_buffer,
+    let mut recorder = CommandRecorder::begin(
+        base,
+        settings.command_buffer,
+        ash::vk::CommandBufferUsageFlags::empty(),
+    )?;
+    recorder
+        .reset_timestamps(settings.timestamp)
+        .timestamp(
+            settings.timestamp,
             ash::vk::PipelineStageFlags::TOP_OF_PIPE,
             "Start",
-        );
-
-        base.logical_device.0.cmd_begin_render_pass(
-            settings.command_buffer,
-            &render_pass_info,
-            ash::vk::SubpassContents::INLINE,
-        );
-        base.logical_device.0.cmd_bind_pipeline(
-            settings.command_buffer,
+        )
+        .begin_render_pass(
+            settings.render_pass,
+            settings.frame_buffer,
+            settings.extent,
+            &clear_values,
+        )
+        .bind_pipeline(
             ash::vk::PipelineBindPoint::GRAPHICS,
             settings.graphics_pipeline,
-        );
-        base.logical_device.0.cmd_bind_vertex_buffers(
-            settings.command_buffer,
-            0,
-            &vertex_buffers,
-            &offsets,
-        );
-        base.logical_device.0.cmd_bind_index_buffer(
-            settings.command_buffer,
-            settings.index_buffer,
-            0,
-            ash::vk::IndexType::UINT32,
-        );
-        base.logical_device.0.cmd_bind_descriptor_sets(
-            settings.command_buffer,
+        )
+        .bind_vertex_buffers(0, &vertex_buffers)
+        .bind_index_buffer(settings.index_buffer, settings.index_type)
+        .bind_descriptor_sets(
             ash::vk::PipelineBindPoint::GRAPHICS,
             settings.graphics_pipeline_layout,
             0,
-            &descriptor_sets,
-            &dynamic_offsets,
-        );
-        base.logical_device.0.cmd_draw_indexed(
-            settings.command_buffer,
-            settings.n_indices,
-            1,
-            0,
-            0,
-            0,
-        );
-        base.logical_device
-            .0
-            .cmd_end_render_pass(settings.command_buffer);
-
-        settings.timestamp.mark(
-            base,
-            settings.command_buffer,
+            &[settings.descriptor_set],
+            settings.dynamic_offsets,
+        )
+        .draw_indexed(settings.n_indices, settings.n_instances, 0, 0, 0)
+        .end_render_pass()
+        .timestamp(
+            settings.timestamp,
             ash::vk::PipelineStageFlags::BOTTOM_OF_PIPE,
             "Done",
         );
-
-        base.logical_device
-            .0
-            .end_command_buffer(settings.command_buffer)?;
-    }
-
-    Ok(())
+    recorder.end()
 }
@@ -2,6 +2,7 @@ use crate::Base;
 use crate::UrnError;
 
 use super::single_time;
+use super::PendingSubmission;
 
 use ash::version::DeviceV1_0;
 
@@ -19,6 +20,14 @@ pub struct TransitionImageLayoutSettings {
 }
 
 pub fn transition(base: &Base, settings: &TransitionImageLayoutSettings) -> Result<(), UrnError> {
+    transition_pending(base, settings)?.finish(base)
+}
+
+/// Doesn't wait for the transition, see `PendingSubmission`.
+pub fn transition_pending(
+    base: &Base,
+    settings: &TransitionImageLayoutSettings,
+) -> Result<PendingSubmission, UrnError> {
     let command_buffer = single_time::begin(base, settings.pool, "ImageTransition".to_string())?;
 
     let barrier = ash::vk::ImageMemoryBarrier::builder()
@@ -54,7 +63,5 @@ pub fn transition(base: &Base, settings: &TransitionImageLayoutSettings) -> Resu
         )
     }
 
-    single_time::end(base, settings.queue, settings.pool, command_buffer)?;
-
-    Ok(())
+    single_time::submit(base, settings.queue, settings.pool, command_buffer)
 }
@@ -0,0 +1,15 @@
+use crate::memory_alignment::Pod;
+
+/// Command layout read by the indirect draw and dispatch calls,
+/// consecutive commands are tightly packed with `STRIDE`.
+pub trait IndirectCommand: Copy {
+    const STRIDE: u32 = std::mem::size_of::<Self>() as u32;
+}
+
+impl IndirectCommand for ash::vk::DrawIndirectCommand {}
+impl IndirectCommand for ash::vk::DrawIndexedIndirectCommand {}
+impl IndirectCommand for ash::vk::DispatchIndirectCommand {}
+
+unsafe impl Pod for ash::vk::DrawIndirectCommand {}
+unsafe impl Pod for ash::vk::DrawIndexedIndirectCommand {}
+unsafe impl Pod for ash::vk::DispatchIndirectCommand {}
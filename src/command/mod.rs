@@ -4,15 +4,22 @@ use crate::UrnError;
 pub mod buffer;
 pub mod draw;
 pub mod image_layout;
+pub mod indirect;
 pub mod pool;
 pub mod queue;
+pub mod recorder;
 pub mod single_time;
+pub mod thread_pools;
 
 pub use buffer::CommandBuffer;
 pub use draw::DrawIndexedSettings;
 pub use image_layout::TransitionImageLayoutSettings;
+pub use indirect::IndirectCommand;
 pub use pool::Pool;
 pub use queue::Queue;
+pub use recorder::{CommandRecorder, ImageTransition, Inheritance};
+pub use single_time::PendingSubmission;
+pub use thread_pools::{ThreadPools, ThreadPoolsSettings};
 
 use ash::version::DeviceV1_0;
 
@@ -25,6 +32,7 @@ pub struct Command {
 pub struct CommandSettings {
     pub queue_family_idx: u32,
     pub queue_idx: u32,
+    pub pool_flags: ash::vk::CommandPoolCreateFlags,
     pub name: String,
 }
 
@@ -37,9 +45,10 @@ impl Command {
             format!("{}Queue", settings.name.clone()),
         )?;
 
-        let pool = Pool::new(
+        let pool = Pool::new_with_flags(
             &base,
             settings.queue_family_idx,
+            settings.pool_flags,
             format!("{}Pool", settings.name.clone()),
         )?;
 
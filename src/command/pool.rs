@@ -8,8 +8,25 @@ pub struct Pool(pub ash::vk::CommandPool);
 /// All pools must be destroyed before the logical_device
 impl Pool {
     pub fn new(base: &Base, queue_family_index: u32, name: String) -> Result<Self, UrnError> {
-        let command_pool_info =
-            ash::vk::CommandPoolCreateInfo::builder().queue_family_index(queue_family_index);
+        Self::new_with_flags(
+            base,
+            queue_family_index,
+            ash::vk::CommandPoolCreateFlags::empty(),
+            name,
+        )
+    }
+
+    /// `TRANSIENT` for short lived buffers,
+    /// `RESET_COMMAND_BUFFER` to reset buffers individually.
+    pub fn new_with_flags(
+        base: &Base,
+        queue_family_index: u32,
+        flags: ash::vk::CommandPoolCreateFlags,
+        name: String,
+    ) -> Result<Self, UrnError> {
+        let command_pool_info = ash::vk::CommandPoolCreateInfo::builder()
+            .flags(flags)
+            .queue_family_index(queue_family_index);
         let pool = unsafe {
             base.logical_device
                 .0
@@ -19,4 +36,21 @@ impl Pool {
 
         Ok(Self(pool))
     }
+
+    /// Resets all buffers allocated from this pool at once,
+    /// none of them may be pending execution.
+    pub fn reset(&self, base: &Base) -> Result<(), UrnError> {
+        unsafe {
+            base.logical_device
+                .0
+                .reset_command_pool(self.0, ash::vk::CommandPoolResetFlags::empty())?
+        };
+        Ok(())
+    }
+
+    pub fn destroy(&self, base: &Base) {
+        unsafe {
+            base.logical_device.0.destroy_command_pool(self.0, None);
+        }
+    }
 }
@@ -0,0 +1,689 @@
+use crate::command::IndirectCommand;
+use crate::pipeline::push_constant::{bytes, PushConstant};
+use crate::Base;
+use crate::Timestamp;
+use crate::UrnError;
+
+use ash::version::DeviceV1_0;
+use ash::version::DeviceV1_2;
+use ash::version::InstanceV1_0;
+
+pub fn clear_color(color: [f32; 4]) -> ash::vk::ClearValue {
+    ash::vk::ClearValue {
+        color: ash::vk::ClearColorValue { float32: color },
+    }
+}
+
+pub fn clear_depth_stencil(depth: f32, stencil: u32) -> ash::vk::ClearValue {
+    ash::vk::ClearValue {
+        depth_stencil: ash::vk::ClearDepthStencilValue { depth, stencil },
+    }
+}
+
+/// Render pass a secondary command buffer continues,
+/// `frame_buffer` may be null if it isn't known at recording time.
+pub struct Inheritance {
+    pub render_pass: ash::vk::RenderPass,
+    pub subpass: u32,
+    pub frame_buffer: ash::vk::Framebuffer,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum RenderPassState {
+    Outside,
+    /// Begun with `SubpassContents::INLINE`
+    Inline,
+    /// Begun with `SubpassContents::SECONDARY_COMMAND_BUFFERS`
+    Secondary,
+    /// Secondary command buffer continuing a render pass
+    Inherited,
+}
+
+/// Recorded by `CommandRecorder::transition_image`
+pub struct ImageTransition {
+    pub image: ash::vk::Image,
+    pub aspect_mask: ash::vk::ImageAspectFlags,
+    pub old_layout: ash::vk::ImageLayout,
+    pub new_layout: ash::vk::ImageLayout,
+    pub src_access: ash::vk::AccessFlags,
+    pub dst_access: ash::vk::AccessFlags,
+    pub src_stage: ash::vk::PipelineStageFlags,
+    pub dst_stage: ash::vk::PipelineStageFlags,
+}
+
+/// Records into a command buffer between `begin` and `end`, the methods can be chained.
+/// Misuse like drawing outside of a render pass is caught by debug assertions,
+/// `end` fails if a render pass is still open.
+pub struct CommandRecorder<'a> {
+    base: &'a Base,
+    pub command_buffer: ash::vk::CommandBuffer,
+    render_pass: RenderPassState,
+    /// Loaded on the first `*_indirect_count` draw
+    indirect_count_khr: Option<ash::vk::KhrDrawIndirectCountFn>,
+}
+
+impl<'a> CommandRecorder<'a> {
+    pub fn begin(
+        base: &'a Base,
+        command_buffer: ash::vk::CommandBuffer,
+        flags: ash::vk::CommandBufferUsageFlags,
+    ) -> Result<Self, UrnError> {
+        let begin_info = ash::vk::CommandBufferBeginInfo::builder().flags(flags);
+        unsafe {
+            base.logical_device
+                .0
+                .begin_command_buffer(command_buffer, &begin_info)?
+        };
+        Ok(Self {
+            base,
+            command_buffer,
+            render_pass: RenderPassState::Outside,
+            indirect_count_khr: None,
+        })
+    }
+
+    /// Secondary command buffers continue the render pass in `inheritance`,
+    /// without one they can only record work outside of render passes.
+    pub fn begin_secondary(
+        base: &'a Base,
+        command_buffer: ash::vk::CommandBuffer,
+        inheritance: Option<&Inheritance>,
+        flags: ash::vk::CommandBufferUsageFlags,
+    ) -> Result<Self, UrnError> {
+        let (inheritance_info, flags, render_pass) = match inheritance {
+            Some(inheritance) => (
+                ash::vk::CommandBufferInheritanceInfo::builder()
+                    .render_pass(inheritance.render_pass)
+                    .subpass(inheritance.subpass)
+                    .framebuffer(inheritance.frame_buffer),
+                flags | ash::vk::CommandBufferUsageFlags::RENDER_PASS_CONTINUE,
+                RenderPassState::Inherited,
+            ),
+            None => (
+                ash::vk::CommandBufferInheritanceInfo::builder(),
+                flags,
+                RenderPassState::Outside,
+            ),
+        };
+        let begin_info = ash::vk::CommandBufferBeginInfo::builder()
+            .flags(flags)
+            .inheritance_info(&inheritance_info);
+        unsafe {
+            base.logical_device
+                .0
+                .begin_command_buffer(command_buffer, &begin_info)?
+        };
+        Ok(Self {
+            base,
+            command_buffer,
+            render_pass,
+            indirect_count_khr: None,
+        })
+    }
+
+    pub fn end(self) -> Result<(), UrnError> {
+        if self.render_pass == RenderPassState::Inline
+            || self.render_pass == RenderPassState::Secondary
+        {
+            return Err(UrnError::Generic(
+                "Command buffer ended inside of a render pass.",
+            ));
+        }
+        unsafe {
+            self.base
+                .logical_device
+                .0
+                .end_command_buffer(self.command_buffer)?
+        };
+        Ok(())
+    }
+
+    fn device(&self) -> &ash::Device {
+        &self.base.logical_device.0
+    }
+
+    /// The KHR entry points if `VK_KHR_draw_indirect_count` is enabled,
+    /// `None` for the Vulkan 1.2 core ones, an error if neither is enabled.
+    fn indirect_count_khr(&mut self) -> Result<Option<ash::vk::KhrDrawIndirectCountFn>, UrnError> {
+        let name = ash::vk::KhrDrawIndirectCountFn::name();
+        if self.indirect_count_khr.is_none() && self.base.logical_device.has_extension(name) {
+            let instance = &self.base.instance.0;
+            let device = self.base.logical_device.0.handle();
+            self.indirect_count_khr = Some(ash::vk::KhrDrawIndirectCountFn::load(|name| unsafe {
+                std::mem::transmute(instance.get_device_proc_addr(device, name.as_ptr()))
+            }));
+        }
+        if self.indirect_count_khr.is_none() && !self.base.logical_device.2.draw_indirect_count {
+            return Err(UrnError::Generic(
+                "Indirect count draws need VK_KHR_draw_indirect_count or the draw_indirect_count feature.",
+            ));
+        }
+        Ok(self.indirect_count_khr.clone())
+    }
+
+    fn outside(&self) -> bool {
+        self.render_pass == RenderPassState::Outside
+    }
+
+    fn can_draw(&self) -> bool {
+        self.render_pass == RenderPassState::Inline
+            || self.render_pass == RenderPassState::Inherited
+    }
+
+    /// Renders the whole `extent`, one clear value per attachment.
+    pub fn begin_render_pass(
+        &mut self,
+        render_pass: ash::vk::RenderPass,
+        frame_buffer: ash::vk::Framebuffer,
+        extent: ash::vk::Extent2D,
+        clear_values: &[ash::vk::ClearValue],
+    ) -> &mut Self {
+        self.begin_render_pass_contents(
+            render_pass,
+            frame_buffer,
+            extent,
+            clear_values,
+            ash::vk::SubpassContents::INLINE,
+        )
+    }
+
+    /// Like `begin_render_pass`, but the subpass is recorded
+    /// in secondary command buffers, see `execute_commands`.
+    pub fn begin_render_pass_secondary(
+        &mut self,
+        render_pass: ash::vk::RenderPass,
+        frame_buffer: ash::vk::Framebuffer,
+        extent: ash::vk::Extent2D,
+        clear_values: &[ash::vk::ClearValue],
+    ) -> &mut Self {
+        self.begin_render_pass_contents(
+            render_pass,
+            frame_buffer,
+            extent,
+            clear_values,
+            ash::vk::SubpassContents::SECONDARY_COMMAND_BUFFERS,
+        )
+    }
+
+    fn begin_render_pass_contents(
+        &mut self,
+        render_pass: ash::vk::RenderPass,
+        frame_buffer: ash::vk::Framebuffer,
+        extent: ash::vk::Extent2D,
+        clear_values: &[ash::vk::ClearValue],
+        contents: ash::vk::SubpassContents,
+    ) -> &mut Self {
+        debug_assert!(self.outside(), "Render pass is already open.");
+        let render_pass_info = ash::vk::RenderPassBeginInfo::builder()
+            .render_pass(render_pass)
+            .framebuffer(frame_buffer)
+            .render_area(ash::vk::Rect2D {
+                offset: ash::vk::Offset2D { x: 0, y: 0 },
+                extent,
+            })
+            .clear_values(clear_values);
+        unsafe {
+            self.device()
+                .cmd_begin_render_pass(self.command_buffer, &render_pass_info, contents)
+        };
+        self.render_pass = if contents == ash::vk::SubpassContents::INLINE {
+            RenderPassState::Inline
+        } else {
+            RenderPassState::Secondary
+        };
+        self
+    }
+
+    pub fn end_render_pass(&mut self) -> &mut Self {
+        debug_assert!(
+            self.render_pass == RenderPassState::Inline
+                || self.render_pass == RenderPassState::Secondary,
+            "No render pass to end."
+        );
+        unsafe { self.device().cmd_end_render_pass(self.command_buffer) };
+        self.render_pass = RenderPassState::Outside;
+        self
+    }
+
+    /// Inside of a render pass begun with `begin_render_pass_secondary` the
+    /// buffers need an `Inheritance` of that subpass.
+    pub fn execute_commands(&mut self, secondaries: &[ash::vk::CommandBuffer]) -> &mut Self {
+        debug_assert!(
+            self.outside() || self.render_pass == RenderPassState::Secondary,
+            "Executing secondaries needs a render pass with secondary contents."
+        );
+        unsafe {
+            self.device()
+                .cmd_execute_commands(self.command_buffer, secondaries)
+        };
+        self
+    }
+
+    pub fn bind_pipeline(
+        &mut self,
+        bind_point: ash::vk::PipelineBindPoint,
+        pipeline: ash::vk::Pipeline,
+    ) -> &mut Self {
+        unsafe {
+            self.device()
+                .cmd_bind_pipeline(self.command_buffer, bind_point, pipeline)
+        };
+        self
+    }
+
+    /// Bound from `first_binding` on, without offsets.
+    pub fn bind_vertex_buffers(
+        &mut self,
+        first_binding: u32,
+        buffers: &[ash::vk::Buffer],
+    ) -> &mut Self {
+        let offsets = vec![0; buffers.len()];
+        unsafe {
+            self.device().cmd_bind_vertex_buffers(
+                self.command_buffer,
+                first_binding,
+                buffers,
+                &offsets,
+            )
+        };
+        self
+    }
+
+    pub fn bind_index_buffer(
+        &mut self,
+        buffer: ash::vk::Buffer,
+        index_type: ash::vk::IndexType,
+    ) -> &mut Self {
+        unsafe {
+            self.device()
+                .cmd_bind_index_buffer(self.command_buffer, buffer, 0, index_type)
+        };
+        self
+    }
+
+    pub fn bind_descriptor_sets(
+        &mut self,
+        bind_point: ash::vk::PipelineBindPoint,
+        layout: ash::vk::PipelineLayout,
+        first_set: u32,
+        sets: &[ash::vk::DescriptorSet],
+        dynamic_offsets: &[u32],
+    ) -> &mut Self {
+        unsafe {
+            self.device().cmd_bind_descriptor_sets(
+                self.command_buffer,
+                bind_point,
+                layout,
+                first_set,
+                sets,
+                dynamic_offsets,
+            )
+        };
+        self
+    }
+
+    pub fn push_constants<P: PushConstant>(
+        &mut self,
+        layout: ash::vk::PipelineLayout,
+        value: &P,
+    ) -> &mut Self {
+        unsafe {
+            self.device().cmd_push_constants(
+                self.command_buffer,
+                layout,
+                P::STAGES,
+                P::OFFSET,
+                bytes(value),
+            )
+        };
+        self
+    }
+
+    pub fn set_viewport(&mut self, viewport: ash::vk::Viewport) -> &mut Self {
+        unsafe {
+            self.device()
+                .cmd_set_viewport(self.command_buffer, 0, &[viewport])
+        };
+        self
+    }
+
+    pub fn set_scissor(&mut self, scissor: ash::vk::Rect2D) -> &mut Self {
+        unsafe {
+            self.device()
+                .cmd_set_scissor(self.command_buffer, 0, &[scissor])
+        };
+        self
+    }
+
+    pub fn draw(
+        &mut self,
+        n_vertices: u32,
+        n_instances: u32,
+        first_vertex: u32,
+        first_instance: u32,
+    ) -> &mut Self {
+        debug_assert!(self.can_draw(), "Draw outside of an inline render pass.");
+        unsafe {
+            self.device().cmd_draw(
+                self.command_buffer,
+                n_vertices,
+                n_instances,
+                first_vertex,
+                first_instance,
+            )
+        };
+        self
+    }
+
+    pub fn draw_indexed(
+        &mut self,
+        n_indices: u32,
+        n_instances: u32,
+        first_index: u32,
+        vertex_offset: i32,
+        first_instance: u32,
+    ) -> &mut Self {
+        debug_assert!(self.can_draw(), "Draw outside of an inline render pass.");
+        unsafe {
+            self.device().cmd_draw_indexed(
+                self.command_buffer,
+                n_indices,
+                n_instances,
+                first_index,
+                vertex_offset,
+                first_instance,
+            )
+        };
+        self
+    }
+
+    /// `draw_count` above one needs `multi_draw_indirect` enabled on the device.
+    pub fn draw_indirect(
+        &mut self,
+        buffer: ash::vk::Buffer,
+        offset: ash::vk::DeviceSize,
+        draw_count: u32,
+    ) -> &mut Self {
+        debug_assert!(self.can_draw(), "Draw outside of an inline render pass.");
+        unsafe {
+            self.device().cmd_draw_indirect(
+                self.command_buffer,
+                buffer,
+                offset,
+                draw_count,
+                ash::vk::DrawIndirectCommand::STRIDE,
+            )
+        };
+        self
+    }
+
+    /// `draw_count` above one needs `multi_draw_indirect` enabled on the device.
+    pub fn draw_indexed_indirect(
+        &mut self,
+        buffer: ash::vk::Buffer,
+        offset: ash::vk::DeviceSize,
+        draw_count: u32,
+    ) -> &mut Self {
+        debug_assert!(self.can_draw(), "Draw outside of an inline render pass.");
+        unsafe {
+            self.device().cmd_draw_indexed_indirect(
+                self.command_buffer,
+                buffer,
+                offset,
+                draw_count,
+                ash::vk::DrawIndexedIndirectCommand::STRIDE,
+            )
+        };
+        self
+    }
+
+    /// The number of draws is read from `count_buffer`, clamped to `max_draw_count`.
+    /// Uses `VK_KHR_draw_indirect_count` if it is in the device extensions,
+    /// otherwise the Vulkan 1.2 command, fails if `draw_indirect_count` isn't enabled either.
+    pub fn draw_indirect_count(
+        &mut self,
+        buffer: ash::vk::Buffer,
+        offset: ash::vk::DeviceSize,
+        count_buffer: ash::vk::Buffer,
+        count_offset: ash::vk::DeviceSize,
+        max_draw_count: u32,
+    ) -> Result<&mut Self, UrnError> {
+        debug_assert!(self.can_draw(), "Draw outside of an inline render pass.");
+        let stride = ash::vk::DrawIndirectCommand::STRIDE;
+        match self.indirect_count_khr()? {
+            Some(khr) => {
+                (khr.cmd_draw_indirect_count_khr)(
+                    self.command_buffer,
+                    buffer,
+                    offset,
+                    count_buffer,
+                    count_offset,
+                    max_draw_count,
+                    stride,
+                );
+            }
+            None => unsafe {
+                self.device().cmd_draw_indirect_count(
+                    self.command_buffer,
+                    buffer,
+                    offset,
+                    count_buffer,
+                    count_offset,
+                    max_draw_count,
+                    stride,
+                )
+            },
+        }
+        Ok(self)
+    }
+
+    /// Indexed version of `draw_indirect_count`, same requirements.
+    pub fn draw_indexed_indirect_count(
+        &mut self,
+        buffer: ash::vk::Buffer,
+        offset: ash::vk::DeviceSize,
+        count_buffer: ash::vk::Buffer,
+        count_offset: ash::vk::DeviceSize,
+        max_draw_count: u32,
+    ) -> Result<&mut Self, UrnError> {
+        debug_assert!(self.can_draw(), "Draw outside of an inline render pass.");
+        let stride = ash::vk::DrawIndexedIndirectCommand::STRIDE;
+        match self.indirect_count_khr()? {
+            Some(khr) => {
+                (khr.cmd_draw_indexed_indirect_count_khr)(
+                    self.command_buffer,
+                    buffer,
+                    offset,
+                    count_buffer,
+                    count_offset,
+                    max_draw_count,
+                    stride,
+                );
+            }
+            None => unsafe {
+                self.device().cmd_draw_indexed_indirect_count(
+                    self.command_buffer,
+                    buffer,
+                    offset,
+                    count_buffer,
+                    count_offset,
+                    max_draw_count,
+                    stride,
+                )
+            },
+        }
+        Ok(self)
+    }
+
+    pub fn dispatch(&mut self, x: u32, y: u32, z: u32) -> &mut Self {
+        debug_assert!(self.outside(), "Dispatch inside of a render pass.");
+        unsafe { self.device().cmd_dispatch(self.command_buffer, x, y, z) };
+        self
+    }
+
+    /// Group counts from a `vk::DispatchIndirectCommand` at `offset`
+    pub fn dispatch_indirect(
+        &mut self,
+        buffer: ash::vk::Buffer,
+        offset: ash::vk::DeviceSize,
+    ) -> &mut Self {
+        debug_assert!(self.outside(), "Dispatch inside of a render pass.");
+        unsafe {
+            self.device()
+                .cmd_dispatch_indirect(self.command_buffer, buffer, offset)
+        };
+        self
+    }
+
+    pub fn copy_buffer(
+        &mut self,
+        src: ash::vk::Buffer,
+        dst: ash::vk::Buffer,
+        regions: &[ash::vk::BufferCopy],
+    ) -> &mut Self {
+        debug_assert!(self.outside(), "Copy inside of a render pass.");
+        unsafe {
+            self.device()
+                .cmd_copy_buffer(self.command_buffer, src, dst, regions)
+        };
+        self
+    }
+
+    /// Writes `size` bytes of repeated `data`, offset and size are multiples of 4.
+    pub fn fill_buffer(
+        &mut self,
+        buffer: ash::vk::Buffer,
+        offset: ash::vk::DeviceSize,
+        size: ash::vk::DeviceSize,
+        data: u32,
+    ) -> &mut Self {
+        debug_assert!(self.outside(), "Fill inside of a render pass.");
+        unsafe {
+            self.device()
+                .cmd_fill_buffer(self.command_buffer, buffer, offset, size, data)
+        };
+        self
+    }
+
+    /// `image` has to be in `TRANSFER_DST_OPTIMAL`
+    pub fn copy_buffer_to_image(
+        &mut self,
+        src: ash::vk::Buffer,
+        image: ash::vk::Image,
+        regions: &[ash::vk::BufferImageCopy],
+    ) -> &mut Self {
+        debug_assert!(self.outside(), "Copy inside of a render pass.");
+        unsafe {
+            self.device().cmd_copy_buffer_to_image(
+                self.command_buffer,
+                src,
+                image,
+                ash::vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                regions,
+            )
+        };
+        self
+    }
+
+    /// `image` has to be in `TRANSFER_SRC_OPTIMAL`
+    pub fn copy_image_to_buffer(
+        &mut self,
+        image: ash::vk::Image,
+        dst: ash::vk::Buffer,
+        regions: &[ash::vk::BufferImageCopy],
+    ) -> &mut Self {
+        debug_assert!(self.outside(), "Copy inside of a render pass.");
+        unsafe {
+            self.device().cmd_copy_image_to_buffer(
+                self.command_buffer,
+                image,
+                ash::vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                dst,
+                regions,
+            )
+        };
+        self
+    }
+
+    pub fn pipeline_barrier(
+        &mut self,
+        src_stage: ash::vk::PipelineStageFlags,
+        dst_stage: ash::vk::PipelineStageFlags,
+        memory_barriers: &[ash::vk::MemoryBarrier],
+        buffer_barriers: &[ash::vk::BufferMemoryBarrier],
+        image_barriers: &[ash::vk::ImageMemoryBarrier],
+    ) -> &mut Self {
+        unsafe {
+            self.device().cmd_pipeline_barrier(
+                self.command_buffer,
+                src_stage,
+                dst_stage,
+                ash::vk::DependencyFlags::empty(),
+                memory_barriers,
+                buffer_barriers,
+                image_barriers,
+            )
+        };
+        self
+    }
+
+    /// Global barrier, e.g. between a dispatch writing and a draw reading a buffer.
+    pub fn memory_barrier(
+        &mut self,
+        src_stage: ash::vk::PipelineStageFlags,
+        src_access: ash::vk::AccessFlags,
+        dst_stage: ash::vk::PipelineStageFlags,
+        dst_access: ash::vk::AccessFlags,
+    ) -> &mut Self {
+        let barrier = ash::vk::MemoryBarrier::builder()
+            .src_access_mask(src_access)
+            .dst_access_mask(dst_access)
+            .build();
+        self.pipeline_barrier(src_stage, dst_stage, &[barrier], &[], &[])
+    }
+
+    /// Layout transition of the first mip level and layer.
+    pub fn transition_image(&mut self, transition: &ImageTransition) -> &mut Self {
+        let barrier = ash::vk::ImageMemoryBarrier::builder()
+            .old_layout(transition.old_layout)
+            .new_layout(transition.new_layout)
+            .src_queue_family_index(ash::vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(ash::vk::QUEUE_FAMILY_IGNORED)
+            .image(transition.image)
+            .subresource_range(
+                ash::vk::ImageSubresourceRange::builder()
+                    .aspect_mask(transition.aspect_mask)
+                    .base_mip_level(0)
+                    .level_count(1)
+                    .base_array_layer(0)
+                    .layer_count(1)
+                    .build(),
+            )
+            .src_access_mask(transition.src_access)
+            .dst_access_mask(transition.dst_access)
+            .build();
+        self.pipeline_barrier(
+            transition.src_stage,
+            transition.dst_stage,
+            &[],
+            &[],
+            &[barrier],
+        )
+    }
+
+    pub fn reset_timestamps(&mut self, timestamp: &Timestamp) -> &mut Self {
+        debug_assert!(self.outside(), "Query reset inside of a render pass.");
+        timestamp.reset_pool(self.base, self.command_buffer);
+        self
+    }
+
+    pub fn timestamp(
+        &mut self,
+        timestamp: &Timestamp,
+        stage: ash::vk::PipelineStageFlags,
+        name: &str,
+    ) -> &mut Self {
+        timestamp.mark(self.base, self.command_buffer, stage, name);
+        self
+    }
+}
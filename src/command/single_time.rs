@@ -1,5 +1,7 @@
 use crate::Base;
+use crate::DeviceBuffer;
 use crate::UrnError;
+use crate::{Fence, Semaphore, Timeline};
 
 use ash::version::DeviceV1_0;
 
@@ -18,42 +20,266 @@ pub fn begin(
             .0
             .allocate_command_buffers(&alloc_info)?
     }[0];
-    base.name_object(command_buffer, name)?;
-
-    let begin_info = ash::vk::CommandBufferBeginInfo::builder()
-        .flags(ash::vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
-    unsafe {
-        base.logical_device
-            .0
-            .begin_command_buffer(command_buffer, &begin_info)?
-    };
+    let begun = base.name_object(command_buffer, name).and_then(|_| {
+        let begin_info = ash::vk::CommandBufferBeginInfo::builder()
+            .flags(ash::vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+        unsafe {
+            base.logical_device
+                .0
+                .begin_command_buffer(command_buffer, &begin_info)?
+        };
+        Ok(())
+    });
+    free_on_error(base, pool, command_buffer, begun)?;
 
     Ok(command_buffer)
 }
 
+/// Submits and waits for just this command buffer.
 pub fn end(
     base: &Base,
     queue: ash::vk::Queue,
     pool: ash::vk::CommandPool,
     command_buffer: ash::vk::CommandBuffer,
 ) -> Result<(), UrnError> {
+    submit(base, queue, pool, command_buffer)?.finish(base)
+}
+
+/// Submits without waiting, completion is signaled by a fence of its own.
+pub fn submit(
+    base: &Base,
+    queue: ash::vk::Queue,
+    pool: ash::vk::CommandPool,
+    command_buffer: ash::vk::CommandBuffer,
+) -> Result<PendingSubmission, UrnError> {
+    let fence = free_on_error(
+        base,
+        pool,
+        command_buffer,
+        submit_fenced(base, queue, command_buffer, &[], &[]),
+    )?;
+
+    Ok(PendingSubmission::new(
+        pool,
+        command_buffer,
+        Completion::Fence(fence),
+    ))
+}
+
+/// Submits without waiting, completion signals `value` on the `timeline`.
+/// The timeline has to outlive the pending submission.
+pub fn submit_timeline(
+    base: &Base,
+    queue: ash::vk::Queue,
+    pool: ash::vk::CommandPool,
+    command_buffer: ash::vk::CommandBuffer,
+    timeline: &Timeline,
+    value: u64,
+) -> Result<PendingSubmission, UrnError> {
+    let submitted = unsafe { base.logical_device.0.end_command_buffer(command_buffer) }
+        .and_then(|_| {
+            let signal_values = [value];
+            let mut timeline_submit_info = ash::vk::TimelineSemaphoreSubmitInfo::builder()
+                .signal_semaphore_values(&signal_values);
+            let command_buffers = [command_buffer];
+            let signal_semaphores = [timeline.0];
+            let submit_info = ash::vk::SubmitInfo::builder()
+                .command_buffers(&command_buffers)
+                .signal_semaphores(&signal_semaphores)
+                .push_next(&mut timeline_submit_info);
+            let submit_infos = [submit_info.build()];
+            unsafe {
+                base.logical_device
+                    .0
+                    .queue_submit(queue, &submit_infos, ash::vk::Fence::default())
+            }
+        })
+        .map_err(UrnError::from);
+    free_on_error(base, pool, command_buffer, submitted)?;
+
+    Ok(PendingSubmission::new(
+        pool,
+        command_buffer,
+        Completion::Timeline(timeline.0, value),
+    ))
+}
+
+/// Like `submit`, but waits on `wait_semaphore` at `wait_stage` before executing.
+pub fn submit_waiting(
+    base: &Base,
+    queue: ash::vk::Queue,
+    pool: ash::vk::CommandPool,
+    command_buffer: ash::vk::CommandBuffer,
+    wait_semaphore: ash::vk::Semaphore,
+    wait_stage: ash::vk::PipelineStageFlags,
+) -> Result<PendingSubmission, UrnError> {
+    let fence = free_on_error(
+        base,
+        pool,
+        command_buffer,
+        submit_fenced(
+            base,
+            queue,
+            command_buffer,
+            &[wait_semaphore],
+            &[wait_stage],
+        ),
+    )?;
+
+    Ok(PendingSubmission::new(
+        pool,
+        command_buffer,
+        Completion::Fence(fence),
+    ))
+}
+
+/// Ends and submits the command buffer with a new fence, destroys the fence if submitting fails.
+fn submit_fenced(
+    base: &Base,
+    queue: ash::vk::Queue,
+    command_buffer: ash::vk::CommandBuffer,
+    wait_semaphores: &[ash::vk::Semaphore],
+    wait_stages: &[ash::vk::PipelineStageFlags],
+) -> Result<Fence, UrnError> {
     unsafe { base.logical_device.0.end_command_buffer(command_buffer)? };
 
+    let fence = Fence::new(base, false, "SingleTimeFence".to_string())?;
+
     let command_buffers = [command_buffer];
-    let submit_info = ash::vk::SubmitInfo::builder().command_buffers(&command_buffers);
+    let submit_info = ash::vk::SubmitInfo::builder()
+        .wait_semaphores(wait_semaphores)
+        .wait_dst_stage_mask(wait_stages)
+        .command_buffers(&command_buffers);
     let submit_infos = [submit_info.build()];
-    unsafe {
+    let submitted = unsafe {
         base.logical_device
             .0
-            .queue_submit(queue, &submit_infos, ash::vk::Fence::default())?
+            .queue_submit(queue, &submit_infos, fence.0)
     };
+    if let Err(e) = submitted {
+        fence.destroy(base);
+        return Err(e.into());
+    }
 
-    unsafe { base.logical_device.0.queue_wait_idle(queue)? };
-    unsafe {
-        base.logical_device
-            .0
-            .free_command_buffers(pool, &command_buffers);
+    Ok(fence)
+}
+
+/// The command buffer was never submitted if `res` is an error, so it can be freed right away.
+fn free_on_error<T>(
+    base: &Base,
+    pool: ash::vk::CommandPool,
+    command_buffer: ash::vk::CommandBuffer,
+    res: Result<T, UrnError>,
+) -> Result<T, UrnError> {
+    if res.is_err() {
+        unsafe {
+            base.logical_device
+                .0
+                .free_command_buffers(pool, &[command_buffer])
+        };
+    }
+    res
+}
+
+enum Completion {
+    Fence(Fence),
+    Timeline(ash::vk::Semaphore, u64),
+}
+
+/// A single time submission still in flight. `finish` it to free the
+/// command buffer and the staging buffers it keeps alive, there is no `Drop`,
+/// a dropped submission leaks its fence, command buffers and staging buffers.
+pub struct PendingSubmission {
+    command_buffers: Vec<(ash::vk::CommandPool, ash::vk::CommandBuffer)>,
+    semaphores: Vec<Semaphore>,
+    completion: Completion,
+    staging: Vec<DeviceBuffer>,
+}
+
+impl PendingSubmission {
+    fn new(
+        pool: ash::vk::CommandPool,
+        command_buffer: ash::vk::CommandBuffer,
+        completion: Completion,
+    ) -> Self {
+        Self {
+            command_buffers: vec![(pool, command_buffer)],
+            semaphores: Vec::new(),
+            completion,
+            staging: Vec::new(),
+        }
     }
 
-    Ok(())
+    /// Earlier submissions this one waits on
+    pub(crate) fn keep_alive_command_buffer(
+        &mut self,
+        pool: ash::vk::CommandPool,
+        command_buffer: ash::vk::CommandBuffer,
+    ) {
+        self.command_buffers.push((pool, command_buffer));
+    }
+
+    pub(crate) fn keep_alive_semaphore(&mut self, semaphore: Semaphore) {
+        self.semaphores.push(semaphore);
+    }
+
+    /// Destroyed once the submission is finished.
+    pub fn keep_alive(&mut self, staging: DeviceBuffer) {
+        self.staging.push(staging);
+    }
+
+    /// Polls without blocking.
+    pub fn is_complete(&self, base: &Base) -> Result<bool, UrnError> {
+        match &self.completion {
+            Completion::Fence(fence) => fence.query(base),
+            Completion::Timeline(semaphore, value) => {
+                Ok(Timeline(*semaphore).query(base)? >= *value)
+            }
+        }
+    }
+
+    pub fn wait(&self, base: &Base) -> Result<(), UrnError> {
+        match &self.completion {
+            Completion::Fence(fence) => fence.wait(base),
+            Completion::Timeline(semaphore, value) => Timeline(*semaphore).wait(base, *value),
+        }
+    }
+
+    /// Waits if necessary, then frees everything. A failed wait almost always
+    /// means a lost device, whose work counts as complete, so everything is freed even then
+    /// and the error is returned afterwards.
+    pub fn finish(self, base: &Base) -> Result<(), UrnError> {
+        let waited = self.wait(base);
+
+        for (pool, command_buffer) in &self.command_buffers {
+            unsafe {
+                base.logical_device
+                    .0
+                    .free_command_buffers(*pool, &[*command_buffer]);
+            }
+        }
+        for semaphore in &self.semaphores {
+            semaphore.destroy(base);
+        }
+        if let Completion::Fence(fence) = &self.completion {
+            fence.destroy(base);
+        }
+        for staging in &self.staging {
+            staging.destroy(base);
+        }
+
+        waited
+    }
+}
+
+/// Finishes all submissions, returns the first error after trying every one.
+pub fn finish_all(base: &Base, pending: Vec<PendingSubmission>) -> Result<(), UrnError> {
+    let mut res = Ok(());
+    for submission in pending {
+        let finished = submission.finish(base);
+        if res.is_ok() {
+            res = finished;
+        }
+    }
+    res
 }
@@ -0,0 +1,110 @@
+use crate::Base;
+use crate::UrnError;
+
+use super::recorder::{CommandRecorder, Inheritance};
+use super::{CommandBuffer, Pool};
+
+pub struct ThreadPoolsSettings {
+    pub queue_family_idx: u32,
+    pub n_threads: usize,
+    pub pool_flags: ash::vk::CommandPoolCreateFlags,
+    pub name: String,
+}
+
+/// One command pool per recording thread, a pool and the buffers
+/// allocated from it must only be used by one thread at a time.
+pub struct ThreadPools {
+    pub pools: Vec<Pool>,
+    name: String,
+}
+
+impl ThreadPools {
+    pub fn new(base: &Base, settings: &ThreadPoolsSettings) -> Result<Self, UrnError> {
+        let pools = (0..settings.n_threads)
+            .map(|i| {
+                Pool::new_with_flags(
+                    base,
+                    settings.queue_family_idx,
+                    settings.pool_flags,
+                    format!("{}Pool_{}", settings.name, i),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            pools,
+            name: settings.name.clone(),
+        })
+    }
+
+    /// One secondary command buffer from each pool, in thread order.
+    pub fn alloc_secondaries(&self, base: &Base) -> Result<Vec<CommandBuffer>, UrnError> {
+        self.pools
+            .iter()
+            .enumerate()
+            .map(|(i, pool)| {
+                CommandBuffer::alloc_secondary(
+                    base,
+                    pool.0,
+                    format!("{}SecondaryCommandBuffer_{}", self.name, i),
+                )
+            })
+            .collect()
+    }
+
+    /// Records `secondaries[i]` on its own thread with `record(i, recorder)`,
+    /// the buffers have to come from `alloc_secondaries`.
+    /// Before re-recording the pools have to be reset,
+    /// unless they were created with `RESET_COMMAND_BUFFER`.
+    pub fn record_secondaries<F>(
+        &self,
+        base: &Base,
+        secondaries: &[CommandBuffer],
+        inheritance: Option<&Inheritance>,
+        flags: ash::vk::CommandBufferUsageFlags,
+        record: F,
+    ) -> Result<(), UrnError>
+    where
+        F: Fn(usize, &mut CommandRecorder) -> Result<(), UrnError> + Sync,
+    {
+        if secondaries.len() != self.pools.len() {
+            return Err(UrnError::GenericDynamic(format!(
+                "Got {} secondary command buffers for {} threads.",
+                secondaries.len(),
+                self.pools.len()
+            )));
+        }
+
+        let record = &record;
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = secondaries
+                .iter()
+                .enumerate()
+                .map(|(i, secondary)| {
+                    scope.spawn(move || -> Result<(), UrnError> {
+                        let mut recorder = CommandRecorder::begin_secondary(
+                            base,
+                            secondary.0,
+                            inheritance,
+                            flags,
+                        )?;
+                        record(i, &mut recorder)?;
+                        recorder.end()
+                    })
+                })
+                .collect();
+
+            handles.into_iter().try_for_each(|handle| {
+                handle
+                    .join()
+                    .map_err(|_| UrnError::Generic("Recording thread panicked."))?
+            })
+        })
+    }
+
+    pub fn destroy(&self, base: &Base) {
+        for pool in &self.pools {
+            pool.destroy(base);
+        }
+    }
+}
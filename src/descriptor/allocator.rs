@@ -0,0 +1,214 @@
+use crate::Base;
+use crate::UrnError;
+
+use super::descriptor_types::DESCRIPTOR_TYPES;
+use super::{Layout, Pool, Set, SetUsage, Setup};
+
+use std::collections::HashMap;
+
+/// Binding, type, count and stage of every binding, sorted by binding
+pub type Signature = Vec<(u32, ash::vk::DescriptorType, u32, ash::vk::ShaderStageFlags)>;
+
+pub struct AllocatorSettings {
+    /// Number of sets the first pool can hold, every further pool doubles this
+    pub sets_per_pool: u32,
+    /// Descriptors of each type reserved per set
+    pub pool_ratios: Vec<(ash::vk::DescriptorType, f32)>,
+    pub name: String,
+}
+
+/// Hands out descriptor sets from a growing list of pools.
+/// Layouts are shared between all sets with the same bindings.
+/// Keep one allocator per frame in flight and `reset` it once the frame is done.
+pub struct Allocator {
+    layouts: HashMap<Signature, Layout>,
+    current_pool: Option<Pool>,
+    used_pools: Vec<Pool>,
+    free_pools: Vec<Pool>,
+    next_pool_size: u32,
+    pool_ratios: Vec<(ash::vk::DescriptorType, f32)>,
+    name: String,
+}
+
+impl AllocatorSettings {
+    /// One descriptor of every type per set
+    pub fn uniform_ratios() -> Vec<(ash::vk::DescriptorType, f32)> {
+        DESCRIPTOR_TYPES.iter().map(|&ty| (ty, 1.0)).collect()
+    }
+}
+
+impl Allocator {
+    pub const MAX_POOL_SIZE: u32 = 4096;
+
+    pub fn new(settings: &AllocatorSettings) -> Self {
+        Self {
+            layouts: HashMap::new(),
+            current_pool: None,
+            used_pools: Vec::new(),
+            free_pools: Vec::new(),
+            next_pool_size: settings.sets_per_pool.max(1),
+            pool_ratios: settings.pool_ratios.clone(),
+            name: settings.name.clone(),
+        }
+    }
+
+    /// Cached layout for the bindings of the setup map
+    pub fn layout(
+        &mut self,
+        base: &Base,
+        setup_map: &HashMap<u32, Setup>,
+    ) -> Result<ash::vk::DescriptorSetLayout, UrnError> {
+        let signature = signature(setup_map);
+        if let Some(layout) = self.layouts.get(&signature) {
+            return Ok(layout.0);
+        }
+
+        let bindings: Vec<ash::vk::DescriptorSetLayoutBinding> = signature
+            .iter()
+            .map(|(binding, ty, count, stage)| {
+                ash::vk::DescriptorSetLayoutBinding::builder()
+                    .binding(*binding)
+                    .descriptor_type(*ty)
+                    .descriptor_count(*count)
+                    .stage_flags(*stage)
+                    .build()
+            })
+            .collect();
+        let layout = Layout::new(
+            base,
+            bindings.as_slice(),
+            format!("{}Layout_{}", self.name, self.layouts.len()),
+        )?;
+        let handle = layout.0;
+        self.layouts.insert(signature, layout);
+
+        Ok(handle)
+    }
+
+    /// Number of distinct layouts created so far
+    pub fn layout_count(&self) -> usize {
+        self.layouts.len()
+    }
+
+    /// Number of pools created so far, in use or free
+    pub fn pool_count(&self) -> usize {
+        self.current_pool.iter().count() + self.used_pools.len() + self.free_pools.len()
+    }
+
+    /// Sets the next newly created pool can hold
+    pub fn next_pool_size(&self) -> u32 {
+        self.next_pool_size
+    }
+
+    /// Allocates and writes a set in one go
+    pub fn allocate(
+        &mut self,
+        base: &Base,
+        setup_map: &HashMap<u32, Setup>,
+        set_usage: &SetUsage,
+    ) -> Result<Set, UrnError> {
+        let layout = self.layout(base, setup_map)?;
+        let set = self.allocate_with_layout(base, layout, set_usage.name.clone())?;
+        set.update(base, setup_map, set_usage)?;
+
+        Ok(set)
+    }
+
+    /// Allocates an unwritten set, switching to another pool if the current one is exhausted
+    pub fn allocate_with_layout(
+        &mut self,
+        base: &Base,
+        layout: ash::vk::DescriptorSetLayout,
+        name: String,
+    ) -> Result<Set, UrnError> {
+        let pool = match &self.current_pool {
+            Some(pool) => pool.0,
+            None => self.next_pool(base)?,
+        };
+
+        match Set::alloc(base, layout, pool, name.clone()) {
+            Err(UrnError::AshError(ash::vk::Result::ERROR_OUT_OF_POOL_MEMORY))
+            | Err(UrnError::AshError(ash::vk::Result::ERROR_FRAGMENTED_POOL)) => {
+                let pool = self.next_pool(base)?;
+                Set::alloc(base, layout, pool, name)
+            }
+            res => res,
+        }
+    }
+
+    /// All sets handed out so far become invalid, pools are kept for reuse
+    pub fn reset(&mut self, base: &Base) -> Result<(), UrnError> {
+        if let Some(pool) = self.current_pool.take() {
+            self.used_pools.push(pool);
+        }
+        for pool in self.used_pools.drain(..) {
+            pool.reset(base)?;
+            self.free_pools.push(pool);
+        }
+        Ok(())
+    }
+
+    pub fn destroy(&self, base: &Base) {
+        for pool in self
+            .current_pool
+            .iter()
+            .chain(self.used_pools.iter())
+            .chain(self.free_pools.iter())
+        {
+            pool.destroy(base);
+        }
+        for layout in self.layouts.values() {
+            layout.destroy(base);
+        }
+    }
+
+    fn next_pool(&mut self, base: &Base) -> Result<ash::vk::DescriptorPool, UrnError> {
+        if let Some(pool) = self.current_pool.take() {
+            self.used_pools.push(pool);
+        }
+
+        let pool = match self.free_pools.pop() {
+            Some(pool) => pool,
+            None => {
+                let max_sets = self.next_pool_size;
+                self.next_pool_size = (max_sets * 2).min(Self::MAX_POOL_SIZE);
+
+                let pool_sizes: Vec<ash::vk::DescriptorPoolSize> = self
+                    .pool_ratios
+                    .iter()
+                    .map(|(ty, ratio)| {
+                        ash::vk::DescriptorPoolSize::builder()
+                            .ty(*ty)
+                            .descriptor_count(((max_sets as f32 * ratio).ceil() as u32).max(1))
+                            .build()
+                    })
+                    .collect();
+                Pool::new(
+                    base,
+                    pool_sizes.as_slice(),
+                    max_sets,
+                    format!(
+                        "{}Pool_{}",
+                        self.name,
+                        self.used_pools.len() + self.free_pools.len()
+                    ),
+                )?
+            }
+        };
+
+        let handle = pool.0;
+        self.current_pool = Some(pool);
+
+        Ok(handle)
+    }
+}
+
+/// Key the layouts of an `Allocator` are cached under
+pub fn signature(setup_map: &HashMap<u32, Setup>) -> Signature {
+    let mut signature: Signature = setup_map
+        .iter()
+        .map(|(binding, setup)| (*binding, setup.ty, setup.count, setup.stage))
+        .collect();
+    signature.sort_by_key(|s| s.0);
+    signature
+}
@@ -0,0 +1,220 @@
+use crate::Base;
+use crate::DeviceBuffer;
+use crate::DeviceImage;
+use crate::UrnError;
+
+use super::{Layout, Pool, Set};
+
+use ash::version::DeviceV1_0;
+
+/// Index of a storage buffer in the bindless set
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BufferHandle(pub u32);
+
+/// Index of a sampled image in the bindless set
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ImageHandle(pub u32);
+
+/// Stable indices into a fixed size array, freed indices are reused first.
+pub struct HandleAllocator {
+    capacity: u32,
+    next: u32,
+    free: Vec<u32>,
+}
+
+impl HandleAllocator {
+    pub fn new(capacity: u32) -> Self {
+        Self {
+            capacity,
+            next: 0,
+            free: Vec::new(),
+        }
+    }
+
+    pub fn alloc(&mut self) -> Option<u32> {
+        if let Some(idx) = self.free.pop() {
+            return Some(idx);
+        }
+        if self.next == self.capacity {
+            return None;
+        }
+        self.next += 1;
+        Some(self.next - 1)
+    }
+
+    /// Errors on indices that were never handed out or are already free.
+    pub fn free(&mut self, idx: u32) -> Result<(), UrnError> {
+        if idx >= self.next {
+            return Err(UrnError::Generic(
+                "Freed a handle that was never allocated.",
+            ));
+        }
+        if self.free.contains(&idx) {
+            return Err(UrnError::Generic("Freed a handle twice."));
+        }
+        self.free.push(idx);
+        Ok(())
+    }
+}
+
+pub struct BindlessSettings {
+    pub max_buffers: u32,
+    pub max_images: u32,
+    pub stage: ash::vk::ShaderStageFlags,
+    pub name: String,
+}
+
+/// One large set with update-after-bind arrays, bound once and indexed in the shaders.
+/// Binding 0 holds the storage buffers, binding 1 the (variable count) sampled images.
+/// Needs `descriptor_indexing` enabled on the device.
+pub struct Bindless {
+    pub layout: Layout,
+    pub pool: Pool,
+    pub set: Set,
+    buffers: HandleAllocator,
+    images: HandleAllocator,
+}
+
+impl Bindless {
+    pub const BUFFER_BINDING: u32 = 0;
+    pub const IMAGE_BINDING: u32 = 1;
+
+    pub fn new(base: &Base, settings: &BindlessSettings) -> Result<Self, UrnError> {
+        if !base.logical_device.2.descriptor_indexing {
+            return Err(UrnError::Generic(
+                "Bindless needs descriptor_indexing enabled on the device.",
+            ));
+        }
+        let bindings = [
+            ash::vk::DescriptorSetLayoutBinding::builder()
+                .binding(Self::BUFFER_BINDING)
+                .descriptor_type(ash::vk::DescriptorType::STORAGE_BUFFER)
+                .descriptor_count(settings.max_buffers)
+                .stage_flags(settings.stage)
+                .build(),
+            ash::vk::DescriptorSetLayoutBinding::builder()
+                .binding(Self::IMAGE_BINDING)
+                .descriptor_type(ash::vk::DescriptorType::SAMPLED_IMAGE)
+                .descriptor_count(settings.max_images)
+                .stage_flags(settings.stage)
+                .build(),
+        ];
+        let flags = ash::vk::DescriptorBindingFlags::UPDATE_AFTER_BIND
+            | ash::vk::DescriptorBindingFlags::UPDATE_UNUSED_WHILE_PENDING
+            | ash::vk::DescriptorBindingFlags::PARTIALLY_BOUND;
+        let binding_flags = [
+            flags,
+            flags | ash::vk::DescriptorBindingFlags::VARIABLE_DESCRIPTOR_COUNT,
+        ];
+        let layout = Layout::new_with_flags(
+            base,
+            &bindings,
+            &binding_flags,
+            ash::vk::DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL,
+            format!("{}Layout", settings.name),
+        )?;
+
+        let pool_sizes = [
+            ash::vk::DescriptorPoolSize::builder()
+                .ty(ash::vk::DescriptorType::STORAGE_BUFFER)
+                .descriptor_count(settings.max_buffers)
+                .build(),
+            ash::vk::DescriptorPoolSize::builder()
+                .ty(ash::vk::DescriptorType::SAMPLED_IMAGE)
+                .descriptor_count(settings.max_images)
+                .build(),
+        ];
+        let pool = Pool::new_with_flags(
+            base,
+            &pool_sizes,
+            1,
+            ash::vk::DescriptorPoolCreateFlags::UPDATE_AFTER_BIND,
+            format!("{}Pool", settings.name),
+        )?;
+
+        let set = Set::alloc_variable(
+            base,
+            layout.0,
+            pool.0,
+            settings.max_images,
+            format!("{}Set", settings.name),
+        )?;
+
+        Ok(Self {
+            layout,
+            pool,
+            set,
+            buffers: HandleAllocator::new(settings.max_buffers),
+            images: HandleAllocator::new(settings.max_images),
+        })
+    }
+
+    pub fn add_buffer(
+        &mut self,
+        base: &Base,
+        buffer: &DeviceBuffer,
+    ) -> Result<BufferHandle, UrnError> {
+        let idx = self
+            .buffers
+            .alloc()
+            .ok_or(UrnError::Generic("Bindless set is out of buffer slots."))?;
+
+        let buffer_infos = [ash::vk::DescriptorBufferInfo::builder()
+            .buffer(buffer.buffer.0)
+            .offset(0)
+            .range(ash::vk::WHOLE_SIZE)
+            .build()];
+        let writes = [ash::vk::WriteDescriptorSet::builder()
+            .dst_set(self.set.0)
+            .dst_binding(Self::BUFFER_BINDING)
+            .dst_array_element(idx)
+            .descriptor_type(ash::vk::DescriptorType::STORAGE_BUFFER)
+            .buffer_info(&buffer_infos)
+            .build()];
+        unsafe { base.logical_device.0.update_descriptor_sets(&writes, &[]) };
+
+        Ok(BufferHandle(idx))
+    }
+
+    pub fn add_image(
+        &mut self,
+        base: &Base,
+        image: &DeviceImage,
+        layout: ash::vk::ImageLayout,
+    ) -> Result<ImageHandle, UrnError> {
+        let idx = self
+            .images
+            .alloc()
+            .ok_or(UrnError::Generic("Bindless set is out of image slots."))?;
+
+        let image_infos = [ash::vk::DescriptorImageInfo::builder()
+            .image_layout(layout)
+            .image_view(image.view.0)
+            .build()];
+        let writes = [ash::vk::WriteDescriptorSet::builder()
+            .dst_set(self.set.0)
+            .dst_binding(Self::IMAGE_BINDING)
+            .dst_array_element(idx)
+            .descriptor_type(ash::vk::DescriptorType::SAMPLED_IMAGE)
+            .image_info(&image_infos)
+            .build()];
+        unsafe { base.logical_device.0.update_descriptor_sets(&writes, &[]) };
+
+        Ok(ImageHandle(idx))
+    }
+
+    /// The slot may be handed out again, shaders must not access it anymore.
+    pub fn remove_buffer(&mut self, handle: BufferHandle) -> Result<(), UrnError> {
+        self.buffers.free(handle.0)
+    }
+
+    /// The slot may be handed out again, shaders must not access it anymore.
+    pub fn remove_image(&mut self, handle: ImageHandle) -> Result<(), UrnError> {
+        self.images.free(handle.0)
+    }
+
+    pub fn destroy(&self, base: &Base) {
+        self.pool.destroy(base);
+        self.layout.destroy(base);
+    }
+}
@@ -11,7 +11,55 @@ impl Layout {
         bindings: &[ash::vk::DescriptorSetLayoutBinding],
         name: String,
     ) -> Result<Self, UrnError> {
-        let layout_info = ash::vk::DescriptorSetLayoutCreateInfo::builder().bindings(&bindings);
+        Self::new_with_flags(
+            base,
+            bindings,
+            &[],
+            ash::vk::DescriptorSetLayoutCreateFlags::empty(),
+            name,
+        )
+    }
+
+    /// Layout for `VK_KHR_push_descriptor`, no sets can be allocated with it.
+    pub fn new_push(
+        base: &Base,
+        bindings: &[ash::vk::DescriptorSetLayoutBinding],
+        name: String,
+    ) -> Result<Self, UrnError> {
+        Self::new_with_flags(
+            base,
+            bindings,
+            &[],
+            ash::vk::DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR_KHR,
+            name,
+        )
+    }
+
+    /// Binding flags are either empty or one entry per binding,
+    /// requires descriptor indexing for anything but empty flags.
+    pub fn new_with_flags(
+        base: &Base,
+        bindings: &[ash::vk::DescriptorSetLayoutBinding],
+        binding_flags: &[ash::vk::DescriptorBindingFlags],
+        create_flags: ash::vk::DescriptorSetLayoutCreateFlags,
+        name: String,
+    ) -> Result<Self, UrnError> {
+        if !binding_flags.is_empty() && binding_flags.len() != bindings.len() {
+            return Err(UrnError::Generic(
+                "Binding flags have to match the number of bindings.",
+            ));
+        }
+
+        let mut binding_flags_info = ash::vk::DescriptorSetLayoutBindingFlagsCreateInfo::builder()
+            .binding_flags(binding_flags);
+        let layout_info = ash::vk::DescriptorSetLayoutCreateInfo::builder()
+            .flags(create_flags)
+            .bindings(&bindings);
+        let layout_info = if binding_flags.is_empty() {
+            layout_info
+        } else {
+            layout_info.push_next(&mut binding_flags_info)
+        };
         let layout = unsafe {
             base.logical_device
                 .0
@@ -21,4 +69,12 @@ impl Layout {
 
         Ok(Self(layout))
     }
+
+    pub fn destroy(&self, base: &Base) {
+        unsafe {
+            base.logical_device
+                .0
+                .destroy_descriptor_set_layout(self.0, None);
+        }
+    }
 }
@@ -3,32 +3,53 @@ use crate::UrnError;
 
 use std::collections::HashMap;
 
+pub mod allocator;
+pub mod bindless;
 mod descriptor_types;
 pub mod layout;
 pub mod pool;
+pub mod push;
 pub mod set;
 
+pub use allocator::{Allocator, AllocatorSettings};
+pub use bindless::{Bindless, BindlessSettings, BufferHandle, ImageHandle};
 use descriptor_types::DESCRIPTOR_TYPES;
 pub use layout::Layout;
 pub use pool::Pool;
+pub use push::{PushDescriptor, PushDescriptorSettings};
 pub use set::Set;
 
-use ash::version::DeviceV1_0;
-
 pub struct Descriptor {
     pub layout: Layout,
     pub pool: Pool,
     pub sets: Vec<Set>,
+    pub setup_map: HashMap<u32, Setup>,
 }
 
+#[derive(Debug, Copy, Clone)]
 pub struct Setup {
     pub ty: ash::vk::DescriptorType,
     pub stage: ash::vk::ShaderStageFlags,
+    /// Number of array elements, 1 for a plain binding
+    pub count: u32,
 }
 
+/// The array variants fill the binding starting at element 0.
+/// Dynamic uniform / storage buffers should use a range, the offset
+/// per draw is supplied when binding the set.
 pub enum Usage {
     Buffer(ash::vk::Buffer),
+    BufferRange(ash::vk::Buffer, ash::vk::DeviceSize, ash::vk::DeviceSize),
+    Buffers(Vec<(ash::vk::Buffer, ash::vk::DeviceSize, ash::vk::DeviceSize)>),
     ImageSampler(ash::vk::ImageLayout, ash::vk::ImageView, ash::vk::Sampler),
+    ImageSamplers(Vec<(ash::vk::ImageLayout, ash::vk::ImageView, ash::vk::Sampler)>),
+    /// Sampled image, storage image or input attachment
+    Image(ash::vk::ImageLayout, ash::vk::ImageView),
+    Images(Vec<(ash::vk::ImageLayout, ash::vk::ImageView)>),
+    Sampler(ash::vk::Sampler),
+    Samplers(Vec<ash::vk::Sampler>),
+    TexelBuffer(ash::vk::BufferView),
+    TexelBuffers(Vec<ash::vk::BufferView>),
 }
 
 pub struct SetUsage {
@@ -46,18 +67,7 @@ impl Descriptor {
     pub fn new(base: &Base, settings: &DescriptorSettings) -> Result<Self, UrnError> {
         let num_sets = settings.set_usages.len() as u32;
 
-        let bindings: Vec<ash::vk::DescriptorSetLayoutBinding> = settings
-            .setup_map
-            .iter()
-            .map(|(binding, setup)| {
-                ash::vk::DescriptorSetLayoutBinding::builder()
-                    .binding(*binding)
-                    .descriptor_type(setup.ty)
-                    .descriptor_count(1)
-                    .stage_flags(setup.stage)
-                    .build()
-            })
-            .collect();
+        let bindings = bindings(&settings.setup_map);
         let layout = Layout::new(
             base,
             bindings.as_slice(),
@@ -70,7 +80,7 @@ impl Descriptor {
                 let descriptor_count = settings
                     .setup_map
                     .iter()
-                    .map(|(_, s)| if s.ty == ty { num_sets } else { 0 })
+                    .map(|(_, s)| if s.ty == ty { s.count * num_sets } else { 0 })
                     .sum();
                 if descriptor_count > 0 {
                     Some(
@@ -102,17 +112,66 @@ impl Descriptor {
             )?);
         }
 
-        Ok(Self { layout, pool, sets })
+        Ok(Self {
+            layout,
+            pool,
+            sets,
+            setup_map: settings.setup_map.clone(),
+        })
+    }
+
+    /// Rebinds some of the bindings of one set, e.g. to swap a texture.
+    pub fn update(
+        &self,
+        base: &Base,
+        set_idx: usize,
+        set_usage: &SetUsage,
+    ) -> Result<(), UrnError> {
+        self.sets[set_idx].update(base, &self.setup_map, set_usage)
+    }
+
+    /// Makes set `dst_idx` a copy of set `src_idx`.
+    pub fn copy(&self, base: &Base, src_idx: usize, dst_idx: usize) -> Result<(), UrnError> {
+        self.sets[dst_idx].copy_from(base, &self.sets[src_idx], &self.setup_map)
     }
 
     pub fn destroy(&self, base: &Base) {
-        unsafe {
-            base.logical_device
-                .0
-                .destroy_descriptor_set_layout(self.layout.0, None);
-            base.logical_device
-                .0
-                .destroy_descriptor_pool(self.pool.0, None);
+        self.layout.destroy(base);
+        self.pool.destroy(base);
+    }
+}
+
+fn bindings(setup_map: &HashMap<u32, Setup>) -> Vec<ash::vk::DescriptorSetLayoutBinding> {
+    setup_map
+        .iter()
+        .map(|(binding, setup)| {
+            ash::vk::DescriptorSetLayoutBinding::builder()
+                .binding(*binding)
+                .descriptor_type(setup.ty)
+                .descriptor_count(setup.count)
+                .stage_flags(setup.stage)
+                .build()
+        })
+        .collect()
+}
+
+/// Size of one element in a dynamic uniform / storage buffer,
+/// respecting the minimal offset alignment of the device.
+pub fn dynamic_stride(
+    base: &Base,
+    ty: ash::vk::DescriptorType,
+    element_size: ash::vk::DeviceSize,
+) -> ash::vk::DeviceSize {
+    let limits = base.physical_device.limits(&base.instance.0);
+    let alignment = match ty {
+        ash::vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC => {
+            limits.min_uniform_buffer_offset_alignment
+        }
+        ash::vk::DescriptorType::STORAGE_BUFFER_DYNAMIC => {
+            limits.min_storage_buffer_offset_alignment
         }
+        _ => 1,
     }
+    .max(1);
+    element_size.div_ceil(alignment) * alignment
 }
@@ -11,8 +11,25 @@ impl Pool {
         pool_sizes: &[ash::vk::DescriptorPoolSize],
         max_sets: u32,
         name: String,
+    ) -> Result<Self, UrnError> {
+        Self::new_with_flags(
+            base,
+            pool_sizes,
+            max_sets,
+            ash::vk::DescriptorPoolCreateFlags::empty(),
+            name,
+        )
+    }
+
+    pub fn new_with_flags(
+        base: &Base,
+        pool_sizes: &[ash::vk::DescriptorPoolSize],
+        max_sets: u32,
+        flags: ash::vk::DescriptorPoolCreateFlags,
+        name: String,
     ) -> Result<Self, UrnError> {
         let pool_info = ash::vk::DescriptorPoolCreateInfo::builder()
+            .flags(flags)
             .pool_sizes(&pool_sizes)
             .max_sets(max_sets);
 
@@ -26,4 +43,20 @@ impl Pool {
 
         Ok(Self(pool))
     }
+
+    /// Returns all sets allocated from this pool at once.
+    pub fn reset(&self, base: &Base) -> Result<(), UrnError> {
+        unsafe {
+            base.logical_device
+                .0
+                .reset_descriptor_pool(self.0, ash::vk::DescriptorPoolResetFlags::default())?
+        };
+        Ok(())
+    }
+
+    pub fn destroy(&self, base: &Base) {
+        unsafe {
+            base.logical_device.0.destroy_descriptor_pool(self.0, None);
+        }
+    }
 }
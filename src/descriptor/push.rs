@@ -0,0 +1,137 @@
+use crate::Base;
+use crate::UrnError;
+
+use super::set::{infos, writes};
+use super::{Allocator, AllocatorSettings, Layout, SetUsage, Setup};
+
+use std::collections::HashMap;
+
+use ash::version::DeviceV1_0;
+
+enum Mode {
+    Push(ash::extensions::khr::PushDescriptor, Layout),
+    Fallback(Allocator),
+}
+
+pub struct PushDescriptorSettings {
+    pub setup_map: HashMap<u32, Setup>,
+    /// Sets per transient pool, only used without the extension
+    pub fallback_sets_per_pool: u32,
+    pub name: String,
+}
+
+/// Small per-dispatch bindings recorded straight into the command buffer.
+/// Uses `VK_KHR_push_descriptor` if the device has it enabled,
+/// otherwise the sets come from transient pools that are reset with `reset`.
+pub struct PushDescriptor {
+    /// Use this for the pipeline layout
+    pub layout: ash::vk::DescriptorSetLayout,
+    pub setup_map: HashMap<u32, Setup>,
+    mode: Mode,
+}
+
+impl PushDescriptor {
+    pub fn new(base: &Base, settings: &PushDescriptorSettings) -> Result<Self, UrnError> {
+        let supported = base
+            .logical_device
+            .has_extension(ash::extensions::khr::PushDescriptor::name());
+
+        let mode = if supported {
+            let layout = Layout::new_push(
+                base,
+                super::bindings(&settings.setup_map).as_slice(),
+                format!("{}PushLayout", settings.name),
+            )?;
+            let loader =
+                ash::extensions::khr::PushDescriptor::new(&base.instance.0, &base.logical_device.0);
+            Mode::Push(loader, layout)
+        } else {
+            let mut ratios: HashMap<ash::vk::DescriptorType, f32> = HashMap::new();
+            for setup in settings.setup_map.values() {
+                *ratios.entry(setup.ty).or_insert(0.0) += setup.count as f32;
+            }
+            Mode::Fallback(Allocator::new(&AllocatorSettings {
+                sets_per_pool: settings.fallback_sets_per_pool,
+                pool_ratios: ratios.into_iter().collect(),
+                name: format!("{}Fallback", settings.name),
+            }))
+        };
+
+        let mut res = Self {
+            layout: ash::vk::DescriptorSetLayout::null(),
+            setup_map: settings.setup_map.clone(),
+            mode,
+        };
+        res.layout = match &mut res.mode {
+            Mode::Push(_, layout) => layout.0,
+            Mode::Fallback(allocator) => allocator.layout(base, &res.setup_map)?,
+        };
+
+        Ok(res)
+    }
+
+    pub fn is_native(&self) -> bool {
+        matches!(self.mode, Mode::Push(..))
+    }
+
+    /// Records the bindings as set `set_idx` of the pipeline layout.
+    pub fn push(
+        &mut self,
+        base: &Base,
+        command_buffer: ash::vk::CommandBuffer,
+        bind_point: ash::vk::PipelineBindPoint,
+        pipeline_layout: ash::vk::PipelineLayout,
+        set_idx: u32,
+        set_usage: &SetUsage,
+    ) -> Result<(), UrnError> {
+        match &mut self.mode {
+            Mode::Push(loader, _) => {
+                let infos = infos(&self.setup_map, set_usage)?;
+                let writes = writes(
+                    ash::vk::DescriptorSet::null(),
+                    &self.setup_map,
+                    infos.as_slice(),
+                );
+                unsafe {
+                    loader.cmd_push_descriptor_set(
+                        command_buffer,
+                        bind_point,
+                        pipeline_layout,
+                        set_idx,
+                        writes.as_slice(),
+                    )
+                };
+            }
+            Mode::Fallback(allocator) => {
+                let set = allocator.allocate(base, &self.setup_map, set_usage)?;
+                unsafe {
+                    base.logical_device.0.cmd_bind_descriptor_sets(
+                        command_buffer,
+                        bind_point,
+                        pipeline_layout,
+                        set_idx,
+                        &[set.0],
+                        &[],
+                    )
+                };
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Frees the fallback sets, only once the command buffers using them are done.
+    pub fn reset(&mut self, base: &Base) -> Result<(), UrnError> {
+        match &mut self.mode {
+            Mode::Push(..) => Ok(()),
+            Mode::Fallback(allocator) => allocator.reset(base),
+        }
+    }
+
+    pub fn destroy(&self, base: &Base) {
+        match &self.mode {
+            Mode::Push(_, layout) => layout.destroy(base),
+            Mode::Fallback(allocator) => allocator.destroy(base),
+        }
+    }
+}
@@ -9,43 +9,207 @@ use std::collections::HashMap;
 
 use ash::version::DeviceV1_0;
 
-struct Info {
+pub(crate) struct Info {
     binding: u32,
     buffer_infos: Vec<ash::vk::DescriptorBufferInfo>,
     image_infos: Vec<ash::vk::DescriptorImageInfo>,
+    texel_buffer_views: Vec<ash::vk::BufferView>,
+}
+
+fn buffer_info(
+    buffer: ash::vk::Buffer,
+    offset: ash::vk::DeviceSize,
+    range: ash::vk::DeviceSize,
+) -> ash::vk::DescriptorBufferInfo {
+    ash::vk::DescriptorBufferInfo::builder()
+        .buffer(buffer)
+        .offset(offset)
+        .range(range)
+        .build()
+}
+
+fn image_info(
+    image_layout: ash::vk::ImageLayout,
+    image_view: ash::vk::ImageView,
+    sampler: ash::vk::Sampler,
+) -> ash::vk::DescriptorImageInfo {
+    ash::vk::DescriptorImageInfo::builder()
+        .image_layout(image_layout)
+        .image_view(image_view)
+        .sampler(sampler)
+        .build()
 }
 
 impl Info {
-    fn buffer_info(binding: u32, buffer: ash::vk::Buffer) -> Self {
-        Self {
+    fn new(binding: u32, setup: &Setup, usage: &Usage) -> Result<Self, UrnError> {
+        let mut info = Self {
             binding,
-            buffer_infos: vec![ash::vk::DescriptorBufferInfo::builder()
-                .buffer(buffer)
-                .offset(0)
-                .range(ash::vk::WHOLE_SIZE)
-                .build()],
+            buffer_infos: vec![],
             image_infos: vec![],
+            texel_buffer_views: vec![],
+        };
+
+        let ty = setup.ty;
+        let is_buffer = ty == ash::vk::DescriptorType::UNIFORM_BUFFER
+            || ty == ash::vk::DescriptorType::STORAGE_BUFFER
+            || ty == ash::vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC
+            || ty == ash::vk::DescriptorType::STORAGE_BUFFER_DYNAMIC;
+        let is_image = ty == ash::vk::DescriptorType::SAMPLED_IMAGE
+            || ty == ash::vk::DescriptorType::STORAGE_IMAGE
+            || ty == ash::vk::DescriptorType::INPUT_ATTACHMENT;
+        let is_texel_buffer = ty == ash::vk::DescriptorType::UNIFORM_TEXEL_BUFFER
+            || ty == ash::vk::DescriptorType::STORAGE_TEXEL_BUFFER;
+
+        let matches = match usage {
+            Usage::Buffer(buffer) => {
+                info.buffer_infos
+                    .push(buffer_info(*buffer, 0, ash::vk::WHOLE_SIZE));
+                is_buffer
+            }
+            Usage::BufferRange(buffer, offset, range) => {
+                info.buffer_infos
+                    .push(buffer_info(*buffer, *offset, *range));
+                is_buffer
+            }
+            Usage::Buffers(buffers) => {
+                info.buffer_infos = buffers
+                    .iter()
+                    .map(|(buffer, offset, range)| buffer_info(*buffer, *offset, *range))
+                    .collect();
+                is_buffer
+            }
+            Usage::ImageSampler(image_layout, image_view, sampler) => {
+                info.image_infos
+                    .push(image_info(*image_layout, *image_view, *sampler));
+                ty == ash::vk::DescriptorType::COMBINED_IMAGE_SAMPLER
+            }
+            Usage::ImageSamplers(image_samplers) => {
+                info.image_infos = image_samplers
+                    .iter()
+                    .map(|(image_layout, image_view, sampler)| {
+                        image_info(*image_layout, *image_view, *sampler)
+                    })
+                    .collect();
+                ty == ash::vk::DescriptorType::COMBINED_IMAGE_SAMPLER
+            }
+            Usage::Image(image_layout, image_view) => {
+                info.image_infos.push(image_info(
+                    *image_layout,
+                    *image_view,
+                    ash::vk::Sampler::null(),
+                ));
+                is_image
+            }
+            Usage::Images(images) => {
+                info.image_infos = images
+                    .iter()
+                    .map(|(image_layout, image_view)| {
+                        image_info(*image_layout, *image_view, ash::vk::Sampler::null())
+                    })
+                    .collect();
+                is_image
+            }
+            Usage::Sampler(sampler) => {
+                info.image_infos.push(image_info(
+                    ash::vk::ImageLayout::UNDEFINED,
+                    ash::vk::ImageView::null(),
+                    *sampler,
+                ));
+                ty == ash::vk::DescriptorType::SAMPLER
+            }
+            Usage::Samplers(samplers) => {
+                info.image_infos = samplers
+                    .iter()
+                    .map(|sampler| {
+                        image_info(
+                            ash::vk::ImageLayout::UNDEFINED,
+                            ash::vk::ImageView::null(),
+                            *sampler,
+                        )
+                    })
+                    .collect();
+                ty == ash::vk::DescriptorType::SAMPLER
+            }
+            Usage::TexelBuffer(view) => {
+                info.texel_buffer_views.push(*view);
+                is_texel_buffer
+            }
+            Usage::TexelBuffers(views) => {
+                info.texel_buffer_views = views.clone();
+                is_texel_buffer
+            }
+        };
+
+        if !matches {
+            return Err(UrnError::GenericDynamic(format!(
+                "Descriptor usage for binding {} does not match its type {:?}.",
+                binding, ty
+            )));
+        }
+        if info.len() == 0 || info.len() > setup.count {
+            return Err(UrnError::GenericDynamic(format!(
+                "Binding {} got {} descriptors, but has room for {}.",
+                binding,
+                info.len(),
+                setup.count
+            )));
         }
+
+        Ok(info)
     }
 
-    fn image_info(
-        binding: u32,
-        image_layout: ash::vk::ImageLayout,
-        image_view: ash::vk::ImageView,
-        sampler: ash::vk::Sampler,
-    ) -> Self {
-        Self {
-            binding,
-            buffer_infos: vec![],
-            image_infos: vec![ash::vk::DescriptorImageInfo::builder()
-                .image_layout(image_layout)
-                .image_view(image_view)
-                .sampler(sampler)
-                .build()],
-        }
+    fn len(&self) -> u32 {
+        (self.buffer_infos.len() + self.image_infos.len() + self.texel_buffer_views.len()) as u32
     }
 }
 
+/// Validated infos for every binding in the usage
+pub(crate) fn infos(
+    setup_map: &HashMap<u32, Setup>,
+    set_usage: &SetUsage,
+) -> Result<Vec<Info>, UrnError> {
+    set_usage
+        .usages
+        .iter()
+        .map(|(binding, usage)| {
+            Info::new(
+                *binding,
+                setup_map
+                    .get(binding)
+                    .expect("Invalid binding for descriptor."),
+                usage,
+            )
+        })
+        .collect()
+}
+
+/// Writes pointing into the infos, `dst_set` is ignored when pushing
+pub(crate) fn writes(
+    dst_set: ash::vk::DescriptorSet,
+    setup_map: &HashMap<u32, Setup>,
+    infos: &[Info],
+) -> Vec<ash::vk::WriteDescriptorSet> {
+    infos
+        .iter()
+        .map(|info| {
+            let incomplete = ash::vk::WriteDescriptorSet::builder()
+                .dst_set(dst_set)
+                .dst_binding(info.binding)
+                .dst_array_element(0)
+                .descriptor_type(setup_map.get(&info.binding).unwrap().ty);
+            if !info.buffer_infos.is_empty() {
+                return incomplete.buffer_info(info.buffer_infos.as_slice()).build();
+            }
+            if !info.image_infos.is_empty() {
+                return incomplete.image_info(info.image_infos.as_slice()).build();
+            }
+            incomplete
+                .texel_buffer_view(info.texel_buffer_views.as_slice())
+                .build()
+        })
+        .collect()
+}
+
 pub struct Set(pub ash::vk::DescriptorSet);
 
 impl Set {
@@ -56,7 +220,19 @@ impl Set {
         setup_map: &HashMap<u32, Setup>,
         set_usage: &SetUsage,
     ) -> Result<Self, UrnError> {
-        // allocation
+        let set = Self::alloc(base, layout, pool, set_usage.name.clone())?;
+        set.update(base, setup_map, set_usage)?;
+
+        Ok(set)
+    }
+
+    /// Allocation only, the pool errors are passed on as they are.
+    pub fn alloc(
+        base: &Base,
+        layout: ash::vk::DescriptorSetLayout,
+        pool: ash::vk::DescriptorPool,
+        name: String,
+    ) -> Result<Self, UrnError> {
         let layouts = [layout];
         let alloc_info = ash::vk::DescriptorSetAllocateInfo::builder()
             .descriptor_pool(pool)
@@ -66,54 +242,90 @@ impl Set {
                 .0
                 .allocate_descriptor_sets(&alloc_info)?
         }[0];
-        base.name_object(set, set_usage.name.clone())?;
+        base.name_object(set, name)?;
 
-        // prepare infos
-        let infos: Vec<Info> = set_usage
-            .usages
-            .iter()
-            .map(|(binding, usage)| match usage {
-                Usage::Buffer(buffer) => Info::buffer_info(*binding, *buffer),
-                Usage::ImageSampler(image_layout, image_view, sampler) => {
-                    Info::image_info(*binding, *image_layout, *image_view, *sampler)
-                }
-            })
-            .collect();
+        Ok(Self(set))
+    }
+
+    /// Allocation for layouts whose last binding has a variable descriptor count.
+    pub fn alloc_variable(
+        base: &Base,
+        layout: ash::vk::DescriptorSetLayout,
+        pool: ash::vk::DescriptorPool,
+        variable_count: u32,
+        name: String,
+    ) -> Result<Self, UrnError> {
+        let layouts = [layout];
+        let counts = [variable_count];
+        let mut variable_info =
+            ash::vk::DescriptorSetVariableDescriptorCountAllocateInfo::builder()
+                .descriptor_counts(&counts);
+        let alloc_info = ash::vk::DescriptorSetAllocateInfo::builder()
+            .descriptor_pool(pool)
+            .set_layouts(&layouts)
+            .push_next(&mut variable_info);
+        let set = unsafe {
+            base.logical_device
+                .0
+                .allocate_descriptor_sets(&alloc_info)?
+        }[0];
+        base.name_object(set, name)?;
+
+        Ok(Self(set))
+    }
+
+    /// Rewrites only the bindings present in the usage, the rest stays as is.
+    /// The set must not be in use by a pending command buffer.
+    pub fn update(
+        &self,
+        base: &Base,
+        setup_map: &HashMap<u32, Setup>,
+        set_usage: &SetUsage,
+    ) -> Result<(), UrnError> {
+        let infos = infos(setup_map, set_usage)?;
+        let writes = writes(self.0, setup_map, infos.as_slice());
 
-        // create writes from the infos
-        let writes: Vec<ash::vk::WriteDescriptorSet> = infos
+        let copies = [];
+
+        unsafe {
+            base.logical_device
+                .0
+                .update_descriptor_sets(writes.as_slice(), &copies)
+        };
+
+        Ok(())
+    }
+
+    /// Copies every binding of the setup map from another set with the same layout.
+    pub fn copy_from(
+        &self,
+        base: &Base,
+        src: &Set,
+        setup_map: &HashMap<u32, Setup>,
+    ) -> Result<(), UrnError> {
+        let writes = [];
+
+        let copies: Vec<ash::vk::CopyDescriptorSet> = setup_map
             .iter()
-            .map(|info| {
-                let incomplete = ash::vk::WriteDescriptorSet::builder()
-                    .dst_set(set)
-                    .dst_binding(info.binding)
+            .map(|(binding, setup)| {
+                ash::vk::CopyDescriptorSet::builder()
+                    .src_set(src.0)
+                    .src_binding(*binding)
+                    .src_array_element(0)
+                    .dst_set(self.0)
+                    .dst_binding(*binding)
                     .dst_array_element(0)
-                    .descriptor_type(
-                        setup_map
-                            .get(&info.binding)
-                            .expect("Invalid binding for descriptor.")
-                            .ty,
-                    );
-                if !info.buffer_infos.is_empty() {
-                    return incomplete.buffer_info(info.buffer_infos.as_slice()).build();
-                }
-                if !info.image_infos.is_empty() {
-                    return incomplete.image_info(info.image_infos.as_slice()).build();
-                }
-                panic!("No buffer infos and no image infos");
+                    .descriptor_count(setup.count)
+                    .build()
             })
             .collect();
 
-        // no copying supported
-        let copies = [];
-
-        // update
         unsafe {
             base.logical_device
                 .0
-                .update_descriptor_sets(writes.as_slice(), &copies)
+                .update_descriptor_sets(&writes, copies.as_slice())
         };
 
-        Ok(Self(set))
+        Ok(())
     }
 }
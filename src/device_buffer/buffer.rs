@@ -20,7 +20,9 @@ impl Buffer {
             base.queue_map.get(&DEDICATED_TRANSFER).unwrap().idx,
         ];
 
-        let buffer_info = if settings.shared {
+        // one family for both, e.g. on a headless base
+        let shared = settings.shared && queue_family_indices[0] != queue_family_indices[1];
+        let buffer_info = if shared {
             ash::vk::BufferCreateInfo::builder()
                 .size(settings.size)
                 .usage(settings.usage)
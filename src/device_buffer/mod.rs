@@ -5,9 +5,13 @@ use crate::command::single_time;
 
 mod buffer;
 mod memory;
+mod typed;
+mod view;
 
 pub use buffer::Buffer;
 pub use memory::Memory;
+pub use typed::{TypedBuffer, TypedBufferSettings};
+pub use view::{View, ViewSettings};
 
 use buffer::BufferSettings;
 use memory::MemorySettings;
@@ -98,7 +102,25 @@ impl DeviceBuffer {
         }
     }
 
+    /// Fails if `size` bytes at `offset` don't fit into `buffer_size` bytes,
+    /// `access` names the operation in the error.
+    pub fn check_range(
+        buffer_size: ash::vk::DeviceSize,
+        offset: ash::vk::DeviceSize,
+        size: ash::vk::DeviceSize,
+        access: &str,
+    ) -> Result<(), UrnError> {
+        match offset.checked_add(size) {
+            Some(end) if end <= buffer_size => Ok(()),
+            _ => Err(UrnError::GenericDynamic(format!(
+                "{} {} bytes at {} overflows a buffer of {} bytes.",
+                access, size, offset, buffer_size
+            ))),
+        }
+    }
+
     pub fn write<T>(&self, base: &Base, to_write: &T) -> Result<(), UrnError> {
+        Self::check_range(self.size, 0, std::mem::size_of::<T>() as u64, "Writing")?;
         if self.data_ptr.is_null() {
             let data_ptr = unsafe {
                 base.logical_device.0.map_memory(
@@ -123,6 +145,12 @@ impl DeviceBuffer {
     }
 
     pub fn write_slice<T>(&self, base: &Base, to_write: &[T]) -> Result<(), UrnError> {
+        Self::check_range(
+            self.size,
+            0,
+            std::mem::size_of_val(to_write) as u64,
+            "Writing",
+        )?;
         if self.data_ptr.is_null() {
             let data_ptr = unsafe {
                 base.logical_device.0.map_memory(
@@ -147,7 +175,47 @@ impl DeviceBuffer {
         Ok(())
     }
 
+    /// Writes to the byte `offset`, fails if the data doesn't fit.
+    pub fn write_slice_at<T>(
+        &self,
+        base: &Base,
+        offset: ash::vk::DeviceSize,
+        to_write: &[T],
+    ) -> Result<(), UrnError> {
+        let size = std::mem::size_of_val(to_write) as ash::vk::DeviceSize;
+        Self::check_range(self.size, offset, size, "Writing")?;
+        if size == 0 {
+            return Ok(());
+        }
+
+        let src = to_write.as_ptr() as *const u8;
+        if self.data_ptr.is_null() {
+            let data_ptr = unsafe {
+                base.logical_device.0.map_memory(
+                    self.memory.0,
+                    offset,
+                    size,
+                    ash::vk::MemoryMapFlags::default(),
+                )?
+            } as *mut u8;
+
+            unsafe {
+                data_ptr.copy_from_nonoverlapping(src, size as usize);
+                base.logical_device.0.unmap_memory(self.memory.0)
+            };
+        } else {
+            unsafe {
+                (self.data_ptr as *mut u8)
+                    .add(offset as usize)
+                    .copy_from_nonoverlapping(src, size as usize);
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn read<T>(&self, base: &Base, to_read: &mut T) -> Result<(), UrnError> {
+        Self::check_range(self.size, 0, std::mem::size_of::<T>() as u64, "Reading")?;
         if self.data_ptr.is_null() {
             let data_ptr = unsafe {
                 base.logical_device.0.map_memory(
@@ -172,6 +240,12 @@ impl DeviceBuffer {
     }
 
     pub fn read_slice<T>(&self, base: &Base, to_read: &mut [T]) -> Result<(), UrnError> {
+        Self::check_range(
+            self.size,
+            0,
+            std::mem::size_of_val(to_read) as u64,
+            "Reading",
+        )?;
         if self.data_ptr.is_null() {
             let data_ptr = unsafe {
                 base.logical_device.0.map_memory(
@@ -196,6 +270,46 @@ impl DeviceBuffer {
         Ok(())
     }
 
+    /// Reads from the byte `offset`, fails if the range doesn't fit.
+    pub fn read_slice_at<T>(
+        &self,
+        base: &Base,
+        offset: ash::vk::DeviceSize,
+        to_read: &mut [T],
+    ) -> Result<(), UrnError> {
+        let size = std::mem::size_of_val(to_read) as ash::vk::DeviceSize;
+        Self::check_range(self.size, offset, size, "Reading")?;
+        if size == 0 {
+            return Ok(());
+        }
+
+        let dst = to_read.as_mut_ptr() as *mut u8;
+        if self.data_ptr.is_null() {
+            let data_ptr = unsafe {
+                base.logical_device.0.map_memory(
+                    self.memory.0,
+                    offset,
+                    size,
+                    ash::vk::MemoryMapFlags::default(),
+                )?
+            } as *const u8;
+
+            unsafe {
+                dst.copy_from_nonoverlapping(data_ptr, size as usize);
+                base.logical_device.0.unmap_memory(self.memory.0)
+            };
+        } else {
+            unsafe {
+                dst.copy_from_nonoverlapping(
+                    (self.data_ptr as *const u8).add(offset as usize),
+                    size as usize,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn set_zero<T>(
         &self,
         base: &Base,
@@ -0,0 +1,150 @@
+use super::{DeviceBuffer, DeviceBufferSettings};
+use crate::memory_alignment::Pod;
+use crate::Base;
+use crate::UrnError;
+
+use std::marker::PhantomData;
+
+pub struct TypedBufferSettings {
+    /// Number of elements
+    pub len: usize,
+    pub usage: ash::vk::BufferUsageFlags,
+    pub properties: ash::vk::MemoryPropertyFlags,
+    pub map: bool,
+    pub shared: bool,
+    pub name: String,
+}
+
+/// `DeviceBuffer` holding `len` elements of `T`, every access is bounds checked.
+pub struct TypedBuffer<T: Pod> {
+    pub device_buffer: DeviceBuffer,
+    len: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Pod> TypedBuffer<T> {
+    pub fn new(base: &Base, settings: &TypedBufferSettings) -> Result<Self, UrnError> {
+        let size = Self::byte_size(settings.len)?;
+        let device_buffer = DeviceBuffer::new(
+            base,
+            &DeviceBufferSettings {
+                size,
+                usage: settings.usage,
+                properties: settings.properties,
+                map: settings.map,
+                shared: settings.shared,
+                name: settings.name.clone(),
+            },
+        )?;
+        Ok(Self {
+            device_buffer,
+            len: settings.len,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Takes over an untyped buffer, its size has to be a multiple of `T`.
+    pub fn from_device_buffer(device_buffer: DeviceBuffer) -> Result<Self, UrnError> {
+        let stride = Self::stride();
+        if stride == 0 || device_buffer.size % stride != 0 {
+            return Err(UrnError::GenericDynamic(format!(
+                "Buffer of {} bytes does not hold whole elements of {} bytes.",
+                device_buffer.size, stride
+            )));
+        }
+        Ok(Self {
+            len: (device_buffer.size / stride) as usize,
+            device_buffer,
+            _marker: PhantomData,
+        })
+    }
+
+    fn zeroed() -> T {
+        // every bit pattern is a valid `Pod`
+        unsafe { std::mem::zeroed() }
+    }
+
+    fn stride() -> ash::vk::DeviceSize {
+        std::mem::size_of::<T>() as ash::vk::DeviceSize
+    }
+
+    fn byte_size(len: usize) -> Result<ash::vk::DeviceSize, UrnError> {
+        match (len as ash::vk::DeviceSize).checked_mul(Self::stride()) {
+            Some(size) if size > 0 => Ok(size),
+            _ => Err(UrnError::GenericDynamic(format!(
+                "Can not create a buffer of {} elements of {} bytes.",
+                len,
+                Self::stride()
+            ))),
+        }
+    }
+
+    /// Fails if `count` elements from `first` on don't fit into `len` elements.
+    pub fn check(len: usize, first: usize, count: usize) -> Result<(), UrnError> {
+        match first.checked_add(count) {
+            Some(end) if end <= len => Ok(()),
+            _ => Err(UrnError::GenericDynamic(format!(
+                "Elements {}..{} are out of bounds of a buffer of {} elements.",
+                first,
+                first.saturating_add(count),
+                len
+            ))),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn buffer(&self) -> ash::vk::Buffer {
+        self.device_buffer.buffer.0
+    }
+
+    /// Byte offset of element `idx`, e.g. for copies and descriptor ranges
+    pub fn offset(&self, idx: usize) -> ash::vk::DeviceSize {
+        idx as ash::vk::DeviceSize * Self::stride()
+    }
+
+    pub fn write(&self, base: &Base, idx: usize, element: &T) -> Result<(), UrnError> {
+        self.write_slice(base, idx, std::slice::from_ref(element))
+    }
+
+    /// Writes `elements` starting at element `first`.
+    pub fn write_slice(&self, base: &Base, first: usize, elements: &[T]) -> Result<(), UrnError> {
+        Self::check(self.len, first, elements.len())?;
+        self.device_buffer
+            .write_slice_at(base, self.offset(first), elements)
+    }
+
+    pub fn read(&self, base: &Base, idx: usize) -> Result<T, UrnError> {
+        let mut element = [Self::zeroed()];
+        self.read_slice(base, idx, &mut element)?;
+        Ok(element[0])
+    }
+
+    /// Reads `elements.len()` elements starting at element `first`.
+    pub fn read_slice(
+        &self,
+        base: &Base,
+        first: usize,
+        elements: &mut [T],
+    ) -> Result<(), UrnError> {
+        Self::check(self.len, first, elements.len())?;
+        self.device_buffer
+            .read_slice_at(base, self.offset(first), elements)
+    }
+
+    pub fn read_all(&self, base: &Base) -> Result<Vec<T>, UrnError> {
+        let mut elements = vec![Self::zeroed(); self.len];
+        self.read_slice(base, 0, &mut elements)?;
+        Ok(elements)
+    }
+
+    pub fn destroy(&self, base: &Base) {
+        self.device_buffer.destroy(base);
+    }
+}
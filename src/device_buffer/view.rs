@@ -0,0 +1,35 @@
+use crate::Base;
+use crate::UrnError;
+
+use ash::version::DeviceV1_0;
+
+/// View for uniform / storage texel buffers
+pub struct View(pub ash::vk::BufferView);
+
+pub struct ViewSettings {
+    pub buffer: ash::vk::Buffer,
+    pub format: ash::vk::Format,
+    pub offset: ash::vk::DeviceSize,
+    pub range: ash::vk::DeviceSize,
+    pub name: String,
+}
+
+impl View {
+    pub fn new(base: &Base, settings: &ViewSettings) -> Result<Self, UrnError> {
+        let view_info = ash::vk::BufferViewCreateInfo::builder()
+            .buffer(settings.buffer)
+            .format(settings.format)
+            .offset(settings.offset)
+            .range(settings.range);
+        let buffer_view = unsafe { base.logical_device.0.create_buffer_view(&view_info, None)? };
+        base.name_object(buffer_view, settings.name.clone())?;
+
+        Ok(Self(buffer_view))
+    }
+
+    pub fn destroy(&self, base: &Base) {
+        unsafe {
+            base.logical_device.0.destroy_buffer_view(self.0, None);
+        }
+    }
+}
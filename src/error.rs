@@ -3,12 +3,18 @@
 pub enum UrnError {
     GenericDynamic(String),
     Generic(&'static str),
+    /// No physical device fits the requested settings
+    NoDevice,
     AshLoadingError(ash::LoadingError),
     AshError(ash::vk::Result),
     AshInstanceError(ash::InstanceError),
     IO(std::io::Error),
     NulError(std::ffi::NulError),
     ImageError(image::error::ImageError),
+    #[cfg(feature = "gltf")]
+    GltfError(gltf::Error),
+    #[cfg(feature = "exr")]
+    ExrError(exr::error::Error),
 }
 
 impl From<std::ffi::NulError> for UrnError {
@@ -46,3 +52,17 @@ impl From<image::error::ImageError> for UrnError {
         UrnError::ImageError(e)
     }
 }
+
+#[cfg(feature = "gltf")]
+impl From<gltf::Error> for UrnError {
+    fn from(e: gltf::Error) -> UrnError {
+        UrnError::GltfError(e)
+    }
+}
+
+#[cfg(feature = "exr")]
+impl From<exr::error::Error> for UrnError {
+    fn from(e: exr::error::Error) -> UrnError {
+        UrnError::ExrError(e)
+    }
+}
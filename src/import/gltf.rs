@@ -0,0 +1,252 @@
+use crate::urn_mesh::vector::mul;
+use crate::UrnError;
+use crate::{UrnMesh, UrnVertex};
+
+use super::RgbaImage;
+
+pub struct Primitive {
+    pub mesh: UrnMesh,
+    /// Index into `Scene::materials`
+    pub material: Option<usize>,
+}
+
+pub struct Mesh {
+    pub name: Option<String>,
+    pub primitives: Vec<Primitive>,
+}
+
+/// Same index as in the file, so `parent` and `children` refer into `Scene::nodes`.
+pub struct Node {
+    pub name: Option<String>,
+    /// Index into `Scene::meshes`
+    pub mesh: Option<usize>,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    /// Column major, relative to the parent
+    pub local_transform: [[f32; 4]; 4],
+    /// Column major, relative to the root
+    pub world_transform: [[f32; 4]; 4],
+}
+
+/// Texture references are indices into `Scene::images`.
+pub struct Material {
+    pub name: Option<String>,
+    pub base_color_factor: [f32; 4],
+    pub base_color_texture: Option<usize>,
+    pub metallic_factor: f32,
+    pub roughness_factor: f32,
+    pub metallic_roughness_texture: Option<usize>,
+    pub normal_texture: Option<usize>,
+}
+
+pub struct Scene {
+    pub meshes: Vec<Mesh>,
+    pub nodes: Vec<Node>,
+    pub materials: Vec<Material>,
+    pub images: Vec<RgbaImage>,
+}
+
+/// Loads a `.gltf` or `.glb` file, external buffers and images are resolved relative to it.
+pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<Scene, UrnError> {
+    let (document, buffers, images) = ::gltf::import(path)?;
+    scene(&document, &buffers, &images)
+}
+
+/// Loads a self-contained `.glb` or `.gltf` from memory.
+pub fn load_slice(bytes: &[u8]) -> Result<Scene, UrnError> {
+    let (document, buffers, images) = ::gltf::import_slice(bytes)?;
+    scene(&document, &buffers, &images)
+}
+
+fn scene(
+    document: &::gltf::Document,
+    buffers: &[::gltf::buffer::Data],
+    images: &[::gltf::image::Data],
+) -> Result<Scene, UrnError> {
+    let meshes = document
+        .meshes()
+        .map(|mesh| {
+            Ok(Mesh {
+                name: mesh.name().map(str::to_string),
+                primitives: mesh
+                    .primitives()
+                    .map(|primitive| {
+                        Ok(Primitive {
+                            mesh: primitive_mesh(&primitive, buffers)?,
+                            material: primitive.material().index(),
+                        })
+                    })
+                    .collect::<Result<_, UrnError>>()?,
+            })
+        })
+        .collect::<Result<_, UrnError>>()?;
+
+    let image_idx = |texture: ::gltf::Texture| texture.source().index();
+    let materials = document
+        .materials()
+        .map(|material| {
+            let pbr = material.pbr_metallic_roughness();
+            Material {
+                name: material.name().map(str::to_string),
+                base_color_factor: pbr.base_color_factor(),
+                base_color_texture: pbr.base_color_texture().map(|i| image_idx(i.texture())),
+                metallic_factor: pbr.metallic_factor(),
+                roughness_factor: pbr.roughness_factor(),
+                metallic_roughness_texture: pbr
+                    .metallic_roughness_texture()
+                    .map(|i| image_idx(i.texture())),
+                normal_texture: material.normal_texture().map(|i| image_idx(i.texture())),
+            }
+        })
+        .collect();
+
+    Ok(Scene {
+        meshes,
+        nodes: nodes(document),
+        materials,
+        images: images.iter().map(rgba_image).collect(),
+    })
+}
+
+fn primitive_mesh(
+    primitive: &::gltf::Primitive,
+    buffers: &[::gltf::buffer::Data],
+) -> Result<UrnMesh, UrnError> {
+    if primitive.mode() != ::gltf::mesh::Mode::Triangles {
+        return Err(UrnError::GenericDynamic(format!(
+            "Primitive #{} uses {:?}, only triangles are supported.",
+            primitive.index(),
+            primitive.mode()
+        )));
+    }
+
+    let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(|data| &data.0[..]));
+
+    let positions: Vec<[f32; 3]> = reader
+        .read_positions()
+        .ok_or(UrnError::Generic("Primitive without positions."))?
+        .collect();
+    let n = positions.len();
+
+    let normals: Vec<[f32; 3]> = match reader.read_normals() {
+        Some(normals) => normals.collect(),
+        None => vec![[0.0, 0.0, 0.0]; n],
+    };
+    let colors: Vec<[f32; 4]> = match reader.read_colors(0) {
+        Some(colors) => colors.into_rgba_f32().collect(),
+        None => vec![[1.0, 1.0, 1.0, 1.0]; n],
+    };
+    let tex_coords: Vec<[f32; 2]> = match reader.read_tex_coords(0) {
+        Some(tex_coords) => tex_coords.into_f32().collect(),
+        None => vec![[0.0, 0.0]; n],
+    };
+    if normals.len() != n || colors.len() != n || tex_coords.len() != n {
+        return Err(UrnError::Generic("Primitive attributes differ in length."));
+    }
+
+    let indices: Vec<u32> = match reader.read_indices() {
+        Some(indices) => indices.into_u32().collect(),
+        None => (0..n as u32).collect(),
+    };
+    if let Some(i) = indices.iter().find(|i| **i as usize >= n) {
+        return Err(UrnError::GenericDynamic(format!(
+            "Index {} out of range for {} vertices.",
+            i, n
+        )));
+    }
+
+    Ok(UrnMesh {
+        vertices: (0..n)
+            .map(|i| UrnVertex {
+                pos: positions[i].into(),
+                nor: normals[i].into(),
+                col: colors[i].into(),
+                tex: tex_coords[i].into(),
+            })
+            .collect(),
+        indices,
+    })
+}
+
+fn nodes(document: &::gltf::Document) -> Vec<Node> {
+    let mut nodes: Vec<Node> = document
+        .nodes()
+        .map(|node| {
+            let local_transform = node.transform().matrix();
+            Node {
+                name: node.name().map(str::to_string),
+                mesh: node.mesh().map(|mesh| mesh.index()),
+                parent: None,
+                children: node.children().map(|child| child.index()).collect(),
+                local_transform,
+                world_transform: local_transform,
+            }
+        })
+        .collect();
+
+    for idx in 0..nodes.len() {
+        for child in nodes[idx].children.clone() {
+            nodes[child].parent = Some(idx);
+        }
+    }
+
+    // parents first, starting at the roots
+    let mut stack: Vec<usize> = (0..nodes.len())
+        .filter(|idx| nodes[*idx].parent.is_none())
+        .collect();
+    while let Some(idx) = stack.pop() {
+        for child in nodes[idx].children.clone() {
+            nodes[child].world_transform =
+                mul(&nodes[idx].world_transform, &nodes[child].local_transform);
+            stack.push(child);
+        }
+    }
+
+    nodes
+}
+
+fn rgba_image(data: &::gltf::image::Data) -> RgbaImage {
+    use ::gltf::image::Format;
+
+    // 16 bit channels are native endian, only the high byte is kept.
+    // Two channels are luminance and alpha.
+    let (channels, bytes) = match data.format {
+        Format::R8 => (1, 1),
+        Format::R8G8 => (2, 1),
+        Format::R8G8B8 | Format::B8G8R8 => (3, 1),
+        Format::R8G8B8A8 | Format::B8G8R8A8 => (4, 1),
+        Format::R16 => (1, 2),
+        Format::R16G16 => (2, 2),
+        Format::R16G16B16 => (3, 2),
+        Format::R16G16B16A16 => (4, 2),
+    };
+    let bgr = matches!(data.format, Format::B8G8R8 | Format::B8G8R8A8);
+
+    let pixels = data
+        .pixels
+        .chunks_exact(channels * bytes)
+        .flat_map(|pixel| {
+            let channel = |c: usize| match bytes {
+                1 => pixel[c],
+                _ => (u16::from_ne_bytes([pixel[2 * c], pixel[2 * c + 1]]) >> 8) as u8,
+            };
+            let rgba = match channels {
+                1 => [channel(0), channel(0), channel(0), 255],
+                2 => [channel(0), channel(0), channel(0), channel(1)],
+                3 => [channel(0), channel(1), channel(2), 255],
+                _ => [channel(0), channel(1), channel(2), channel(3)],
+            };
+            if bgr {
+                [rgba[2], rgba[1], rgba[0], rgba[3]]
+            } else {
+                rgba
+            }
+        })
+        .collect();
+
+    RgbaImage {
+        width: data.width,
+        height: data.height,
+        pixels,
+    }
+}
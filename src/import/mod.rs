@@ -0,0 +1,13 @@
+#[cfg(feature = "gltf")]
+pub mod gltf;
+pub mod obj;
+pub mod ply;
+
+/// Decoded image, 4 bytes per pixel,
+/// ready for `transfer::create_texture_device_image_from_rgba`.
+#[derive(Debug, Clone)]
+pub struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
@@ -0,0 +1,144 @@
+use crate::UrnError;
+use crate::{UrnMesh, UrnVertex};
+
+use std::collections::HashMap;
+
+/// Loads a Wavefront OBJ file, see `parse`.
+pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<UrnMesh, UrnError> {
+    parse(&std::fs::read_to_string(path)?)
+}
+
+/// Positions, optional vertex colors (`v x y z r g b`), texture coordinates and normals.
+/// Polygons are triangulated as fans, corners with the same `v/vt/vn` share a vertex.
+/// Texture coordinates are flipped to a top left origin, like in glTF.
+/// Groups, materials, lines and points are ignored.
+pub fn parse(source: &str) -> Result<UrnMesh, UrnError> {
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut colors: Vec<[f32; 4]> = Vec::new();
+    let mut tex_coords: Vec<[f32; 2]> = Vec::new();
+    let mut normals: Vec<[f32; 3]> = Vec::new();
+
+    let mut mesh = UrnMesh::new();
+    let mut corners: HashMap<(usize, Option<usize>, Option<usize>), u32> = HashMap::new();
+
+    for (line_idx, line) in source.lines().enumerate() {
+        let line_nr = line_idx + 1;
+        let error = |message: String| {
+            UrnError::GenericDynamic(format!("OBJ line {}: {}", line_nr, message))
+        };
+
+        let line = line.split('#').next().unwrap().trim();
+        let mut tokens = line.split_whitespace();
+        let keyword = match tokens.next() {
+            Some(keyword) => keyword,
+            None => continue,
+        };
+        let values: Vec<&str> = tokens.collect();
+
+        let floats = |min: usize, max: usize| -> Result<Vec<f32>, UrnError> {
+            if values.len() < min || values.len() > max {
+                return Err(error(format!(
+                    "`{}` expects {} to {} numbers, got {}",
+                    keyword,
+                    min,
+                    max,
+                    values.len()
+                )));
+            }
+            values
+                .iter()
+                .map(|v| {
+                    v.parse::<f32>()
+                        .map_err(|_| error(format!("`{}` is not a number", v)))
+                })
+                .collect()
+        };
+
+        match keyword {
+            "v" => {
+                let v = floats(3, 7)?;
+                positions.push([v[0], v[1], v[2]]);
+                colors.push(match v.len() {
+                    3 | 4 => [1.0, 1.0, 1.0, 1.0],
+                    6 => [v[3], v[4], v[5], 1.0],
+                    7 => [v[3], v[4], v[5], v[6]],
+                    n => {
+                        return Err(error(format!(
+                            "`v` expects 3, 4, 6 or 7 numbers, got {}",
+                            n
+                        )))
+                    }
+                });
+            }
+            "vt" => {
+                let v = floats(1, 3)?;
+                tex_coords.push([v[0], 1.0 - v.get(1).copied().unwrap_or(0.0)]);
+            }
+            "vn" => {
+                let v = floats(3, 3)?;
+                normals.push([v[0], v[1], v[2]]);
+            }
+            "f" => {
+                if values.len() < 3 {
+                    return Err(error(format!(
+                        "a face needs at least 3 corners, got {}",
+                        values.len()
+                    )));
+                }
+
+                let resolve = |token: &str, len: usize, what: &str| -> Result<usize, UrnError> {
+                    let idx = token
+                        .parse::<i64>()
+                        .map_err(|_| error(format!("`{}` is not a {} index", token, what)))?;
+                    // 1 based, negative counts back from the last one
+                    let resolved = if idx < 0 { len as i64 + idx } else { idx - 1 };
+                    if idx == 0 || resolved < 0 || resolved >= len as i64 {
+                        return Err(error(format!(
+                            "{} index {} is out of range, {} defined so far",
+                            what, idx, len
+                        )));
+                    }
+                    Ok(resolved as usize)
+                };
+
+                let mut face = Vec::with_capacity(values.len());
+                for corner in &values {
+                    let parts: Vec<&str> = corner.split('/').collect();
+                    if parts.len() > 3 {
+                        return Err(error(format!("malformed face corner `{}`", corner)));
+                    }
+                    let v = resolve(parts[0], positions.len(), "position")?;
+                    let vt = match parts.get(1) {
+                        None | Some(&"") => None,
+                        Some(t) => Some(resolve(t, tex_coords.len(), "texture coordinate")?),
+                    };
+                    let vn = match parts.get(2) {
+                        None | Some(&"") => None,
+                        Some(n) => Some(resolve(n, normals.len(), "normal")?),
+                    };
+
+                    let vertices = &mut mesh.vertices;
+                    let idx = *corners.entry((v, vt, vn)).or_insert_with(|| {
+                        vertices.push(UrnVertex {
+                            pos: positions[v].into(),
+                            nor: vn.map_or([0.0, 0.0, 0.0], |n| normals[n]).into(),
+                            col: colors[v].into(),
+                            tex: vt.map_or([0.0, 0.0], |t| tex_coords[t]).into(),
+                        });
+                        vertices.len() as u32 - 1
+                    });
+                    face.push(idx);
+                }
+
+                for i in 1..face.len() - 1 {
+                    mesh.indices
+                        .extend_from_slice(&[face[0], face[i], face[i + 1]]);
+                }
+            }
+            "o" | "g" | "s" | "mtllib" | "usemtl" | "l" | "p" | "vp" => {}
+            _ => return Err(error(format!("unknown keyword `{}`", keyword))),
+        }
+    }
+
+    Ok(mesh)
+}
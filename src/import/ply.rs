@@ -0,0 +1,349 @@
+use crate::UrnError;
+use crate::{UrnMesh, UrnVertex};
+
+/// Loads a PLY file, see `parse`.
+pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<UrnMesh, UrnError> {
+    parse(&std::fs::read(path)?)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Encoding {
+    Ascii,
+    LittleEndian,
+    BigEndian,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Scalar {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+enum Property {
+    Scalar(String, Scalar),
+    List(String, Scalar, Scalar),
+}
+
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+impl Scalar {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "char" | "int8" => Scalar::I8,
+            "uchar" | "uint8" => Scalar::U8,
+            "short" | "int16" => Scalar::I16,
+            "ushort" | "uint16" => Scalar::U16,
+            "int" | "int32" => Scalar::I32,
+            "uint" | "uint32" => Scalar::U32,
+            "float" | "float32" => Scalar::F32,
+            "double" | "float64" => Scalar::F64,
+            _ => return None,
+        })
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Scalar::I8 | Scalar::U8 => 1,
+            Scalar::I16 | Scalar::U16 => 2,
+            Scalar::I32 | Scalar::U32 | Scalar::F32 => 4,
+            Scalar::F64 => 8,
+        }
+    }
+
+    /// Factor mapping integer colors to [0, 1]
+    fn color_scale(self) -> f64 {
+        match self {
+            Scalar::U8 | Scalar::I8 => 1.0 / 255.0,
+            Scalar::U16 | Scalar::I16 => 1.0 / 65535.0,
+            Scalar::U32 | Scalar::I32 => 1.0 / 4_294_967_295.0,
+            Scalar::F32 | Scalar::F64 => 1.0,
+        }
+    }
+}
+
+/// Reads the body value by value, whatever the encoding.
+struct Body<'a> {
+    encoding: Encoding,
+    bytes: &'a [u8],
+    pos: usize,
+    tokens: std::str::SplitAsciiWhitespace<'a>,
+}
+
+impl<'a> Body<'a> {
+    fn ends(&self, what: &str) -> UrnError {
+        if self.encoding == Encoding::Ascii {
+            UrnError::GenericDynamic(format!("PLY: body ends while reading {}", what))
+        } else {
+            UrnError::GenericDynamic(format!(
+                "PLY: body ends at byte {} while reading {}",
+                self.bytes.len(),
+                what
+            ))
+        }
+    }
+
+    /// Errors unless `count` values of `ty` are left, for counts read from the file
+    /// that would be allocated for otherwise.
+    fn check(&self, count: usize, ty: Scalar, what: &str) -> Result<(), UrnError> {
+        let fits = if self.encoding == Encoding::Ascii {
+            self.tokens.clone().take(count).count() == count
+        } else {
+            count
+                .checked_mul(ty.size())
+                .is_some_and(|size| size <= self.bytes.len() - self.pos)
+        };
+        if fits {
+            Ok(())
+        } else {
+            Err(self.ends(what))
+        }
+    }
+
+    fn read(&mut self, ty: Scalar, what: &str) -> Result<f64, UrnError> {
+        if self.encoding == Encoding::Ascii {
+            let token = self.tokens.next().ok_or_else(|| self.ends(what))?;
+            return token.parse::<f64>().map_err(|_| {
+                UrnError::GenericDynamic(format!("PLY: `{}` is not a valid {}", token, what))
+            });
+        }
+
+        let size = ty.size();
+        if self.pos + size > self.bytes.len() {
+            return Err(self.ends(what));
+        }
+        let mut raw = [0u8; 8];
+        raw[..size].copy_from_slice(&self.bytes[self.pos..self.pos + size]);
+        if self.encoding == Encoding::BigEndian {
+            raw[..size].reverse();
+        }
+        self.pos += size;
+
+        Ok(match ty {
+            Scalar::I8 => raw[0] as i8 as f64,
+            Scalar::U8 => raw[0] as f64,
+            Scalar::I16 => i16::from_le_bytes([raw[0], raw[1]]) as f64,
+            Scalar::U16 => u16::from_le_bytes([raw[0], raw[1]]) as f64,
+            Scalar::I32 => i32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]) as f64,
+            Scalar::U32 => u32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]) as f64,
+            Scalar::F32 => f32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]) as f64,
+            Scalar::F64 => f64::from_le_bytes(raw),
+        })
+    }
+}
+
+/// Encoding, elements and where the body starts
+fn header(bytes: &[u8]) -> Result<(Encoding, Vec<Element>, usize), UrnError> {
+    let mut encoding = None;
+    let mut elements: Vec<Element> = Vec::new();
+
+    let mut pos = 0;
+    let mut line_nr = 0;
+    loop {
+        let end = bytes[pos..]
+            .iter()
+            .position(|b| *b == b'\n')
+            .map(|e| pos + e)
+            .ok_or(UrnError::Generic("PLY: header without `end_header`."))?;
+        let line = std::str::from_utf8(&bytes[pos..end])
+            .map_err(|_| UrnError::Generic("PLY: header is not valid text."))?
+            .trim();
+        pos = end + 1;
+        line_nr += 1;
+
+        let error = |message: String| {
+            UrnError::GenericDynamic(format!("PLY header line {}: {}", line_nr, message))
+        };
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        if line_nr == 1 {
+            if line != "ply" {
+                return Err(error("not a PLY file".to_string()));
+            }
+            continue;
+        }
+
+        match tokens.as_slice() {
+            [] | ["comment", ..] | ["obj_info", ..] => {}
+            ["format", format, "1.0"] => {
+                encoding = Some(match *format {
+                    "ascii" => Encoding::Ascii,
+                    "binary_little_endian" => Encoding::LittleEndian,
+                    "binary_big_endian" => Encoding::BigEndian,
+                    _ => return Err(error(format!("unknown format `{}`", format))),
+                });
+            }
+            ["element", name, count] => elements.push(Element {
+                name: name.to_string(),
+                count: count
+                    .parse()
+                    .map_err(|_| error(format!("`{}` is not an element count", count)))?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count_ty, item_ty, name] => {
+                let scalar = |ty: &str| {
+                    Scalar::parse(ty).ok_or_else(|| error(format!("unknown type `{}`", ty)))
+                };
+                let property =
+                    Property::List(name.to_string(), scalar(count_ty)?, scalar(item_ty)?);
+                elements
+                    .last_mut()
+                    .ok_or_else(|| error("property before any element".to_string()))?
+                    .properties
+                    .push(property);
+            }
+            ["property", ty, name] => {
+                let ty =
+                    Scalar::parse(ty).ok_or_else(|| error(format!("unknown type `{}`", ty)))?;
+                elements
+                    .last_mut()
+                    .ok_or_else(|| error("property before any element".to_string()))?
+                    .properties
+                    .push(Property::Scalar(name.to_string(), ty));
+            }
+            ["end_header"] => break,
+            _ => return Err(error(format!("can not parse `{}`", line))),
+        }
+    }
+
+    let encoding = encoding.ok_or(UrnError::Generic("PLY: header without format."))?;
+    Ok((encoding, elements, pos))
+}
+
+/// ASCII and binary PLY with `vertex` and `face` elements. Vertex properties used are
+/// `x y z`, `nx ny nz`, `red green blue alpha` and `u v` (or `s t`), everything else
+/// is skipped. Faces are triangulated as fans.
+/// Texture coordinates are flipped to a top left origin, like in glTF and OBJ.
+pub fn parse(bytes: &[u8]) -> Result<UrnMesh, UrnError> {
+    let (encoding, elements, body_start) = header(bytes)?;
+
+    let body_bytes = &bytes[body_start..];
+    let text = if encoding == Encoding::Ascii {
+        std::str::from_utf8(body_bytes)
+            .map_err(|_| UrnError::Generic("PLY: body is not valid text."))?
+    } else {
+        ""
+    };
+    let mut body = Body {
+        encoding,
+        bytes: body_bytes,
+        pos: 0,
+        tokens: text.split_ascii_whitespace(),
+    };
+
+    let mut mesh = UrnMesh::new();
+
+    for element in &elements {
+        for item in 0..element.count {
+            let mut vertex = UrnVertex {
+                col: [1.0, 1.0, 1.0, 1.0].into(),
+                ..Default::default()
+            };
+            let mut found = 0;
+
+            for property in &element.properties {
+                match property {
+                    Property::Scalar(name, ty) => {
+                        let what = format!("{} {} `{}`", element.name, item, name);
+                        let value = body.read(*ty, &what)?;
+                        if element.name != "vertex" {
+                            continue;
+                        }
+                        let value32 = value as f32;
+                        let color = (value * ty.color_scale()) as f32;
+                        match name.as_str() {
+                            "x" => {
+                                vertex.pos.0[0] = value32;
+                                found += 1;
+                            }
+                            "y" => {
+                                vertex.pos.0[1] = value32;
+                                found += 1;
+                            }
+                            "z" => {
+                                vertex.pos.0[2] = value32;
+                                found += 1;
+                            }
+                            "nx" => vertex.nor.0[0] = value32,
+                            "ny" => vertex.nor.0[1] = value32,
+                            "nz" => vertex.nor.0[2] = value32,
+                            "red" | "r" => vertex.col.0[0] = color,
+                            "green" | "g" => vertex.col.0[1] = color,
+                            "blue" | "b" => vertex.col.0[2] = color,
+                            "alpha" | "a" => vertex.col.0[3] = color,
+                            "u" | "s" | "texture_u" => vertex.tex.0[0] = value32,
+                            "v" | "t" | "texture_v" => vertex.tex.0[1] = 1.0 - value32,
+                            _ => {}
+                        }
+                    }
+                    Property::List(name, count_ty, item_ty) => {
+                        let what = format!("{} {} `{}`", element.name, item, name);
+                        let count = body.read(*count_ty, &what)? as usize;
+                        body.check(count, *item_ty, &what)?;
+                        let mut values = Vec::with_capacity(count);
+                        for _ in 0..count {
+                            values.push(body.read(*item_ty, &what)?);
+                        }
+
+                        let is_face_indices = element.name == "face"
+                            && (name == "vertex_indices" || name == "vertex_index");
+                        if !is_face_indices {
+                            continue;
+                        }
+                        if count < 3 {
+                            return Err(UrnError::GenericDynamic(format!(
+                                "PLY: face {} has only {} corners.",
+                                item, count
+                            )));
+                        }
+                        let n_vertices = elements
+                            .iter()
+                            .find(|e| e.name == "vertex")
+                            .map_or(0, |e| e.count);
+                        let face: Vec<u32> = values.iter().map(|v| *v as u32).collect();
+                        if let Some(bad) = values
+                            .iter()
+                            .find(|v| **v < 0.0 || **v as usize >= n_vertices)
+                        {
+                            return Err(UrnError::GenericDynamic(format!(
+                                "PLY: face {} uses vertex {}, but there are only {}.",
+                                item, bad, n_vertices
+                            )));
+                        }
+                        for i in 1..face.len() - 1 {
+                            mesh.indices
+                                .extend_from_slice(&[face[0], face[i], face[i + 1]]);
+                        }
+                    }
+                }
+            }
+
+            if element.name == "vertex" {
+                if found != 3 {
+                    return Err(UrnError::Generic(
+                        "PLY: vertices need x, y and z properties.",
+                    ));
+                }
+                mesh.vertices.push(vertex);
+            }
+        }
+    }
+
+    if encoding != Encoding::Ascii && body.pos != body_bytes.len() {
+        return Err(UrnError::GenericDynamic(format!(
+            "PLY: {} bytes left after the last element.",
+            body_bytes.len() - body.pos
+        )));
+    }
+
+    Ok(mesh)
+}
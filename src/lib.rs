@@ -1,9 +1,17 @@
+// lets the derive macros refer to `::ash_urn` inside this crate as well
+extern crate self as ash_urn;
+
+#[doc(hidden)]
+pub use ash;
+
 pub mod base;
+pub mod capture;
 pub mod command;
 pub mod descriptor;
 pub mod device_buffer;
 pub mod device_image;
 pub mod error;
+pub mod import;
 pub mod memory_alignment;
 pub mod pipeline;
 pub mod queries;
@@ -15,9 +23,10 @@ pub mod urn_mesh;
 pub mod util;
 
 pub use base::Base;
+pub use capture::Capture;
 pub use command::{Command, CommandBuffer, CommandSettings};
 pub use descriptor::{Descriptor, DescriptorSettings};
-pub use device_buffer::{DeviceBuffer, DeviceBufferSettings};
+pub use device_buffer::{DeviceBuffer, DeviceBufferSettings, TypedBuffer, TypedBufferSettings};
 pub use device_image::{DeviceImage, DeviceImageSettings, Sampler};
 pub use error::UrnError;
 pub use pipeline::{
@@ -15,3 +15,148 @@ impl<T: Copy> From<T> for Align16<T> {
         Self(t)
     }
 }
+
+pub use ash_urn_derive::{Std140, Std430};
+
+/// Alignment and size of a type as member of a std140 block.
+///
+/// # Safety
+/// The values have to follow the std140 rules for the memory of the type.
+pub unsafe trait Std140Member {
+    const ALIGN: usize;
+    const SIZE: usize;
+}
+
+/// Alignment and size of a type as member of a std430 block.
+///
+/// # Safety
+/// The values have to follow the std430 rules for the memory of the type.
+pub unsafe trait Std430Member {
+    const ALIGN: usize;
+    const SIZE: usize;
+}
+
+/// Implemented by `#[derive(Std140)]`, which fails to compile if a field is not where
+/// std140 expects it. Use `Align16` or reorder fields to fix it.
+pub trait Std140: Std140Member {
+    /// Byte offset of every field, in declaration order
+    const OFFSETS: &'static [(&'static str, usize)];
+}
+
+/// Implemented by `#[derive(Std430)]`, see `Std140`.
+pub trait Std430: Std430Member {
+    /// Byte offset of every field, in declaration order
+    const OFFSETS: &'static [(&'static str, usize)];
+}
+
+#[doc(hidden)]
+pub const fn round_up(value: usize, align: usize) -> usize {
+    value.div_ceil(align) * align
+}
+
+#[doc(hidden)]
+pub const fn max(a: usize, b: usize) -> usize {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+macro_rules! impl_member {
+    ($($ty:ty: $align:expr, $size:expr;)*) => {
+        $(
+            unsafe impl Std140Member for $ty {
+                const ALIGN: usize = $align;
+                const SIZE: usize = $size;
+            }
+            unsafe impl Std430Member for $ty {
+                const ALIGN: usize = $align;
+                const SIZE: usize = $size;
+            }
+        )*
+    };
+}
+
+// scalars, vectors and column major matrices with vec4 columns
+impl_member! {
+    f32: 4, 4;
+    i32: 4, 4;
+    u32: 4, 4;
+    [f32; 2]: 8, 8;
+    [i32; 2]: 8, 8;
+    [u32; 2]: 8, 8;
+    [f32; 3]: 16, 12;
+    [i32; 3]: 16, 12;
+    [u32; 3]: 16, 12;
+    [f32; 4]: 16, 16;
+    [i32; 4]: 16, 16;
+    [u32; 4]: 16, 16;
+    [[f32; 4]; 2]: 16, 32;
+    [[f32; 4]; 3]: 16, 48;
+    [[f32; 4]; 4]: 16, 64;
+}
+
+#[cfg(feature = "cgmath")]
+impl_member! {
+    cgmath::Vector2<f32>: 8, 8;
+    cgmath::Vector3<f32>: 16, 12;
+    cgmath::Vector4<f32>: 16, 16;
+    cgmath::Matrix4<f32>: 16, 64;
+}
+
+// the wrappers only move the member in rust, std140 does not see the padding
+unsafe impl<T: Copy + Std140Member> Std140Member for Align8<T> {
+    const ALIGN: usize = T::ALIGN;
+    const SIZE: usize = T::SIZE;
+}
+unsafe impl<T: Copy + Std430Member> Std430Member for Align8<T> {
+    const ALIGN: usize = T::ALIGN;
+    const SIZE: usize = T::SIZE;
+}
+unsafe impl<T: Copy + Std140Member> Std140Member for Align16<T> {
+    const ALIGN: usize = T::ALIGN;
+    const SIZE: usize = T::SIZE;
+}
+unsafe impl<T: Copy + Std430Member> Std430Member for Align16<T> {
+    const ALIGN: usize = T::ALIGN;
+    const SIZE: usize = T::SIZE;
+}
+
+/// Plain old data that is copied to and from buffer memory byte by byte.
+/// Padding is allowed, the crate only copies it through raw pointers
+/// and never reads it as bytes, e.g. through a `&[u8]`.
+///
+/// # Safety
+/// Every bit pattern has to be a valid value, so no `bool`, enums, references or pointers.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($ty:ty),*) => {
+        $(unsafe impl Pod for $ty {})*
+    };
+}
+
+impl_pod!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+unsafe impl<T: Pod> Pod for Align8<T> {}
+unsafe impl<T: Pod> Pod for Align16<T> {}
+
+#[cfg(feature = "cgmath")]
+impl_pod!(
+    cgmath::Vector2<f32>,
+    cgmath::Vector3<f32>,
+    cgmath::Vector4<f32>,
+    cgmath::Matrix4<f32>
+);
+
+/// Arrays of derived structs, the rust stride has to match the one of the layout
+unsafe impl<T: Std140, const N: usize> Std140Member for [T; N] {
+    const ALIGN: usize = T::ALIGN;
+    const SIZE: usize = T::SIZE * N;
+}
+unsafe impl<T: Std430, const N: usize> Std430Member for [T; N] {
+    const ALIGN: usize = T::ALIGN;
+    const SIZE: usize = T::SIZE * N;
+}
@@ -1,6 +1,8 @@
 use crate::Base;
 use crate::UrnError;
 
+use super::push_constant::{bytes, PushConstant};
+
 use ash::version::DeviceV1_0;
 
 pub struct PipelineLayout(pub ash::vk::PipelineLayout);
@@ -27,6 +29,24 @@ impl PipelineLayout {
         Ok(Self(layout))
     }
 
+    /// Records `value` into the range of `P`, which has to be part of this layout.
+    pub fn push_constants<P: PushConstant>(
+        &self,
+        base: &Base,
+        command_buffer: ash::vk::CommandBuffer,
+        value: &P,
+    ) {
+        unsafe {
+            base.logical_device.0.cmd_push_constants(
+                command_buffer,
+                self.0,
+                P::STAGES,
+                P::OFFSET,
+                bytes(value),
+            )
+        };
+    }
+
     pub fn destroy(&self, base: &Base) {
         unsafe {
             base.logical_device.0.destroy_pipeline_layout(self.0, None);
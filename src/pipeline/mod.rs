@@ -1,9 +1,11 @@
 pub mod compute;
 pub mod graphics;
 pub mod layout;
+pub mod push_constant;
 pub mod shader_module;
 
 pub use compute::{ComputePipeline, ComputePipelineSettings};
 pub use graphics::{GraphicsPipeline, GraphicsPipelineSettings};
 pub use layout::{PipelineLayout, PipelineLayoutSettings};
+pub use push_constant::{push_constant_range, PushConstant};
 pub use shader_module::{ShaderModule, ShaderModuleSettings};
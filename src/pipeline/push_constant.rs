@@ -0,0 +1,49 @@
+use crate::Base;
+use crate::UrnError;
+
+/// Declares a struct as push constant block for the given stages.
+///
+/// # Safety
+/// The type has to be `#[repr(C)]` without padding bytes,
+/// it is pushed as the raw bytes of its memory.
+pub unsafe trait PushConstant: Copy {
+    const STAGES: ash::vk::ShaderStageFlags;
+    /// Byte offset in the push constant block, must be a multiple of 4
+    const OFFSET: u32 = 0;
+}
+
+/// Range of `P`, checked against the limits of the device.
+pub fn push_constant_range<P: PushConstant>(
+    base: &Base,
+) -> Result<ash::vk::PushConstantRange, UrnError> {
+    let size = std::mem::size_of::<P>() as u32;
+    if size == 0 || size % 4 != 0 || P::OFFSET % 4 != 0 {
+        return Err(UrnError::GenericDynamic(format!(
+            "Push constants need a size and offset that are multiples of 4, got {} and {}.",
+            size,
+            P::OFFSET
+        )));
+    }
+
+    let max_size = base
+        .physical_device
+        .limits(&base.instance.0)
+        .max_push_constants_size;
+    if P::OFFSET + size > max_size {
+        return Err(UrnError::GenericDynamic(format!(
+            "Push constants end at {} bytes, the device only supports {}.",
+            P::OFFSET + size,
+            max_size
+        )));
+    }
+
+    Ok(ash::vk::PushConstantRange::builder()
+        .stage_flags(P::STAGES)
+        .offset(P::OFFSET)
+        .size(size)
+        .build())
+}
+
+pub(crate) fn bytes<P: PushConstant>(value: &P) -> &[u8] {
+    unsafe { std::slice::from_raw_parts(value as *const P as *const u8, std::mem::size_of::<P>()) }
+}
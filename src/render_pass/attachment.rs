@@ -3,6 +3,7 @@ use crate::UrnError;
 
 pub fn color_description(
     swapchain_format: ash::vk::Format,
+    final_layout: ash::vk::ImageLayout,
 ) -> ash::vk::AttachmentDescriptionBuilder<'static> {
     ash::vk::AttachmentDescription::builder()
         .format(swapchain_format)
@@ -12,7 +13,7 @@ pub fn color_description(
         .stencil_load_op(ash::vk::AttachmentLoadOp::DONT_CARE)
         .stencil_store_op(ash::vk::AttachmentStoreOp::DONT_CARE)
         .initial_layout(ash::vk::ImageLayout::UNDEFINED)
-        .final_layout(ash::vk::ImageLayout::PRESENT_SRC_KHR)
+        .final_layout(final_layout)
 }
 
 pub fn depth_description(
@@ -10,13 +10,16 @@ pub struct RenderPass(pub ash::vk::RenderPass);
 
 pub struct RenderPassSettings {
     pub swap_chain_format: ash::vk::Format,
+    /// `PRESENT_SRC_KHR` for swapchain images, e.g. `TRANSFER_SRC_OPTIMAL` for offscreen targets
+    pub final_layout: ash::vk::ImageLayout,
     pub name: String,
 }
 
 impl RenderPass {
     pub fn new(base: &Base, settings: &RenderPassSettings) -> Result<Self, UrnError> {
         let attachment_descriptions = [
-            attachment::color_description(settings.swap_chain_format).build(),
+            attachment::color_description(settings.swap_chain_format, settings.final_layout)
+                .build(),
             attachment::depth_description(base)?.build(),
         ];
 
@@ -38,6 +38,8 @@ pub struct SwapChainSettings {
     pub support: SwapChainSupportDetail,
     pub surface: ash::vk::SurfaceKHR,
     pub image_count: u32,
+    /// Adds `TRANSFER_SRC` usage so the images can be captured
+    pub capture: bool,
     pub name: String,
 }
 
@@ -56,13 +58,28 @@ impl SwapChain {
             settings.image_count
         };
 
+        let mut image_usage = ash::vk::ImageUsageFlags::COLOR_ATTACHMENT;
+        if settings.capture {
+            if !settings
+                .support
+                .capabilities
+                .supported_usage_flags
+                .contains(ash::vk::ImageUsageFlags::TRANSFER_SRC)
+            {
+                return Err(UrnError::Generic(
+                    "Swapchain images can not be used as transfer source.",
+                ));
+            }
+            image_usage |= ash::vk::ImageUsageFlags::TRANSFER_SRC;
+        }
+
         let swap_chain_create_info = ash::vk::SwapchainCreateInfoKHR::builder()
             .surface(settings.surface)
             .min_image_count(image_count)
             .image_color_space(surface_format.0.color_space)
             .image_format(surface_format.0.format)
             .image_extent(extent.0)
-            .image_usage(ash::vk::ImageUsageFlags::COLOR_ATTACHMENT)
+            .image_usage(image_usage)
             .image_sharing_mode(ash::vk::SharingMode::EXCLUSIVE)
             .pre_transform(settings.support.capabilities.current_transform)
             .composite_alpha(ash::vk::CompositeAlphaFlagsKHR::OPAQUE)
@@ -2,6 +2,7 @@ use crate::Base;
 use crate::UrnError;
 
 use crate::command::single_time;
+use crate::command::PendingSubmission;
 
 use ash::version::DeviceV1_0;
 
@@ -14,6 +15,19 @@ pub fn copy_buffer_to_image(
     width: u32,
     height: u32,
 ) -> Result<(), UrnError> {
+    copy_buffer_to_image_pending(base, queue, pool, buffer, image, width, height)?.finish(base)
+}
+
+/// Doesn't wait for the copy, see `PendingSubmission`.
+pub fn copy_buffer_to_image_pending(
+    base: &Base,
+    queue: ash::vk::Queue,
+    pool: ash::vk::CommandPool,
+    buffer: ash::vk::Buffer,
+    image: ash::vk::Image,
+    width: u32,
+    height: u32,
+) -> Result<PendingSubmission, UrnError> {
     let command_buffer = single_time::begin(base, pool, "CopyBufferToImage".to_string())?;
 
     let region = ash::vk::BufferImageCopy::builder()
@@ -46,7 +60,7 @@ pub fn copy_buffer_to_image(
         )
     };
 
-    single_time::end(base, queue, pool, command_buffer)
+    single_time::submit(base, queue, pool, command_buffer)
 }
 
 pub fn copy_buffer_to_buffer(
@@ -57,6 +71,18 @@ pub fn copy_buffer_to_buffer(
     dst_buffer: ash::vk::Buffer,
     size: ash::vk::DeviceSize,
 ) -> Result<(), UrnError> {
+    copy_buffer_to_buffer_pending(base, queue, pool, src_buffer, dst_buffer, size)?.finish(base)
+}
+
+/// Doesn't wait for the copy, see `PendingSubmission`.
+pub fn copy_buffer_to_buffer_pending(
+    base: &Base,
+    queue: ash::vk::Queue,
+    pool: ash::vk::CommandPool,
+    src_buffer: ash::vk::Buffer,
+    dst_buffer: ash::vk::Buffer,
+    size: ash::vk::DeviceSize,
+) -> Result<PendingSubmission, UrnError> {
     let command_buffer = single_time::begin(base, pool, "CopyBufferToBuffer".to_string())?;
 
     let copy_region = ash::vk::BufferCopy::builder()
@@ -70,5 +96,5 @@ pub fn copy_buffer_to_buffer(
             .cmd_copy_buffer(command_buffer, src_buffer, dst_buffer, &regions)
     };
 
-    single_time::end(base, queue, pool, command_buffer)
+    single_time::submit(base, queue, pool, command_buffer)
 }
@@ -1,19 +1,21 @@
 use crate::Base;
 use crate::UrnError;
 
+use crate::urn_mesh::{Index, Indices};
 use crate::{DeviceBuffer, DeviceBufferSettings};
 
 use super::copy_buffer_to_buffer;
 use super::create_staging_device_buffer;
 
-pub fn create_index_device_buffer(
+/// Bind with `I::TYPE` as index type.
+pub fn create_index_device_buffer<I: Index>(
     base: &Base,
-    indices: &[u32],
+    indices: &[I],
     queue: ash::vk::Queue,
     pool: ash::vk::CommandPool,
     name: String,
 ) -> Result<DeviceBuffer, UrnError> {
-    let size = (indices.len() * std::mem::size_of::<u32>()) as ash::vk::DeviceSize;
+    let size = (indices.len() * std::mem::size_of::<I>()) as ash::vk::DeviceSize;
 
     let staging = create_staging_device_buffer(base, size, format!("{}Staging", name.clone()))?;
 
@@ -38,3 +40,18 @@ pub fn create_index_device_buffer(
 
     Ok(index)
 }
+
+/// Bind with `indices.index_type()` as index type.
+pub fn create_indices_device_buffer(
+    base: &Base,
+    indices: &Indices,
+    queue: ash::vk::Queue,
+    pool: ash::vk::CommandPool,
+    name: String,
+) -> Result<DeviceBuffer, UrnError> {
+    match indices {
+        Indices::U8(indices) => create_index_device_buffer(base, indices, queue, pool, name),
+        Indices::U16(indices) => create_index_device_buffer(base, indices, queue, pool, name),
+        Indices::U32(indices) => create_index_device_buffer(base, indices, queue, pool, name),
+    }
+}
@@ -0,0 +1,86 @@
+use crate::Base;
+use crate::UrnError;
+
+use crate::command::IndirectCommand;
+use crate::{DeviceBuffer, DeviceBufferSettings};
+
+use super::copy_buffer_to_buffer;
+use super::create_staging_device_buffer;
+
+fn create_indirect(
+    base: &Base,
+    size: ash::vk::DeviceSize,
+    name: String,
+) -> Result<DeviceBuffer, UrnError> {
+    DeviceBuffer::new(
+        base,
+        &DeviceBufferSettings {
+            size,
+            usage: ash::vk::BufferUsageFlags::INDIRECT_BUFFER
+                | ash::vk::BufferUsageFlags::STORAGE_BUFFER
+                | ash::vk::BufferUsageFlags::TRANSFER_SRC
+                | ash::vk::BufferUsageFlags::TRANSFER_DST,
+            properties: ash::vk::MemoryPropertyFlags::DEVICE_LOCAL,
+            map: false,
+            shared: false,
+            name,
+        },
+    )
+}
+
+/// Bytes for `len` elements of `stride` bytes, fails for empty or overflowing buffers.
+pub fn indirect_size(len: usize, stride: usize) -> Result<ash::vk::DeviceSize, UrnError> {
+    match len.checked_mul(stride) {
+        Some(size) if size > 0 => Ok(size as ash::vk::DeviceSize),
+        _ => Err(UrnError::GenericDynamic(format!(
+            "Can not create an indirect buffer of {} elements of {} bytes.",
+            len, stride
+        ))),
+    }
+}
+
+/// Commands known up front, read with stride `C::STRIDE`.
+pub fn create_indirect_device_buffer<C: IndirectCommand>(
+    base: &Base,
+    commands: &[C],
+    queue: ash::vk::Queue,
+    pool: ash::vk::CommandPool,
+    name: String,
+) -> Result<DeviceBuffer, UrnError> {
+    let size = indirect_size(commands.len(), std::mem::size_of::<C>())?;
+
+    let staging = create_staging_device_buffer(base, size, format!("{}Staging", name.clone()))?;
+    let indirect = staging
+        .write_slice(base, commands)
+        .and_then(|_| create_indirect(base, size, name));
+    let copied = indirect.and_then(|indirect| {
+        match copy_buffer_to_buffer(base, queue, pool, staging.buffer.0, indirect.buffer.0, size) {
+            Ok(()) => Ok(indirect),
+            Err(e) => {
+                indirect.destroy(base);
+                Err(e)
+            }
+        }
+    });
+    staging.destroy(base);
+
+    copied
+}
+
+/// Room for `len` commands, e.g. written by a compute shader.
+pub fn create_indirect_device_buffer_uninitialized<C: IndirectCommand>(
+    base: &Base,
+    len: usize,
+    name: String,
+) -> Result<DeviceBuffer, UrnError> {
+    create_indirect(base, indirect_size(len, std::mem::size_of::<C>())?, name)
+}
+
+/// `len` draw counts as `u32`, for the `*_indirect_count` draws.
+pub fn create_indirect_count_device_buffer(
+    base: &Base,
+    len: usize,
+    name: String,
+) -> Result<DeviceBuffer, UrnError> {
+    create_indirect(base, indirect_size(len, std::mem::size_of::<u32>())?, name)
+}
@@ -0,0 +1,26 @@
+use crate::Base;
+use crate::UrnError;
+
+use crate::DeviceBuffer;
+use crate::Vertex;
+
+use super::create_vertex_storage_device_buffer;
+
+/// Per-instance data that compute shaders can also write to, e.g. particle positions.
+/// `I` has to describe a single binding with instance input rate.
+pub fn create_instance_storage_device_buffer<I: Vertex>(
+    base: &Base,
+    instances: &[I],
+    queue: ash::vk::Queue,
+    pool: ash::vk::CommandPool,
+    name: String,
+) -> Result<DeviceBuffer, UrnError> {
+    let bindings = I::get_binding_description();
+    if bindings.len() != 1 || bindings[0].input_rate != ash::vk::VertexInputRate::INSTANCE {
+        return Err(UrnError::Generic(
+            "Instance data needs a single binding with instance input rate.",
+        ));
+    }
+
+    create_vertex_storage_device_buffer(base, instances, queue, pool, name)
+}
@@ -1,16 +1,35 @@
 pub mod copy;
 pub mod index;
+pub mod indirect;
+pub mod instance;
 pub mod ownership;
+pub mod readback;
 pub mod staging;
+pub mod staging_ring;
 pub mod storage;
 pub mod texture;
+pub mod upload_batch;
 pub mod vertex;
 
-pub use copy::{copy_buffer_to_buffer, copy_buffer_to_image};
-pub use index::create_index_device_buffer;
-pub use staging::create_staging_device_buffer;
+pub use copy::{
+    copy_buffer_to_buffer, copy_buffer_to_buffer_pending, copy_buffer_to_image,
+    copy_buffer_to_image_pending,
+};
+pub use index::{create_index_device_buffer, create_indices_device_buffer};
+pub use indirect::{
+    create_indirect_count_device_buffer, create_indirect_device_buffer,
+    create_indirect_device_buffer_uninitialized, indirect_size,
+};
+pub use instance::create_instance_storage_device_buffer;
+pub use readback::{
+    readback_buffer, readback_image, readback_image_bytes, texel_size, to_rgb_f32, to_rgba,
+    ReadbackImageSettings,
+};
+pub use staging::{create_staging_device_buffer, upload_to_buffer};
+pub use staging_ring::{RingAllocator, StagingRing, StagingRingSettings};
 pub use storage::create_storage_device_buffer;
 pub use storage::create_storage_device_buffer_uninitialized;
-pub use texture::create_texture_device_image;
+pub use texture::{create_texture_device_image, create_texture_device_image_from_rgba};
+pub use upload_batch::{UploadBatch, Uploads};
 pub use vertex::create_vertex_device_buffer;
 pub use vertex::create_vertex_storage_device_buffer;
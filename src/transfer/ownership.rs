@@ -1,4 +1,5 @@
 use crate::command::single_time;
+use crate::command::PendingSubmission;
 use crate::Base;
 use crate::Command;
 use crate::DeviceBuffer;
@@ -14,42 +15,62 @@ pub fn transfer_release(
     transfer_command: &Command,
     combined_command: &Command,
 ) -> Result<(), UrnError> {
+    transfer_release_pending(
+        base,
+        device_buffers,
+        device_images,
+        transfer_command,
+        combined_command,
+    )?
+    .finish(base)
+}
 
+/// Doesn't wait for the barrier, see `PendingSubmission`. It has to be finished
+/// before the acquire is submitted, nothing else orders the two halves of the transfer.
+pub fn transfer_release_pending(
+    base: &Base,
+    device_buffers: &[&DeviceBuffer],
+    device_images: &[&DeviceImage],
+    transfer_command: &Command,
+    combined_command: &Command,
+) -> Result<PendingSubmission, UrnError> {
     let memory_barriers = [];
     let buffer_memory_barriers: Vec<ash::vk::BufferMemoryBarrier> = device_buffers
         .iter()
-        .map(|device_buffer| ash::vk::BufferMemoryBarrier::builder()
-            .src_queue_family_index(transfer_command.family_idx)
-            .dst_queue_family_index(combined_command.family_idx)
-            .src_access_mask(ash::vk::AccessFlags::TRANSFER_WRITE)
-            .dst_access_mask(ash::vk::AccessFlags::default())
-            .buffer(device_buffer.buffer.0)
-            .offset(0)
-            .size(ash::vk::WHOLE_SIZE)
-            .build()
-        )
+        .map(|device_buffer| {
+            ash::vk::BufferMemoryBarrier::builder()
+                .src_queue_family_index(transfer_command.family_idx)
+                .dst_queue_family_index(combined_command.family_idx)
+                .src_access_mask(ash::vk::AccessFlags::TRANSFER_WRITE)
+                .dst_access_mask(ash::vk::AccessFlags::default())
+                .buffer(device_buffer.buffer.0)
+                .offset(0)
+                .size(ash::vk::WHOLE_SIZE)
+                .build()
+        })
         .collect();
     let image_memory_barriers: Vec<ash::vk::ImageMemoryBarrier> = device_images
         .iter()
-        .map(|device_image| ash::vk::ImageMemoryBarrier::builder()
-            .old_layout(ash::vk::ImageLayout::TRANSFER_DST_OPTIMAL)
-            .new_layout(ash::vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
-            .src_queue_family_index(transfer_command.family_idx)
-            .dst_queue_family_index(combined_command.family_idx)
-            .src_access_mask(ash::vk::AccessFlags::TRANSFER_WRITE)
-            .dst_access_mask(ash::vk::AccessFlags::default())
-            .image(device_image.image.0)
-            .subresource_range(
-                ash::vk::ImageSubresourceRange::builder()
-                    .aspect_mask(ash::vk::ImageAspectFlags::COLOR)
-                    .base_mip_level(0)
-                    .level_count(1)
-                    .base_array_layer(0)
-                    .layer_count(1)
-                    .build(),
-            )
-            .build()
-        )
+        .map(|device_image| {
+            ash::vk::ImageMemoryBarrier::builder()
+                .old_layout(ash::vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+                .new_layout(ash::vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                .src_queue_family_index(transfer_command.family_idx)
+                .dst_queue_family_index(combined_command.family_idx)
+                .src_access_mask(ash::vk::AccessFlags::TRANSFER_WRITE)
+                .dst_access_mask(ash::vk::AccessFlags::default())
+                .image(device_image.image.0)
+                .subresource_range(
+                    ash::vk::ImageSubresourceRange::builder()
+                        .aspect_mask(ash::vk::ImageAspectFlags::COLOR)
+                        .base_mip_level(0)
+                        .level_count(1)
+                        .base_array_layer(0)
+                        .layer_count(1)
+                        .build(),
+                )
+                .build()
+        })
         .collect();
 
     let transfer_command_buffer = single_time::begin(
@@ -70,7 +91,7 @@ pub fn transfer_release(
         )
     }
 
-    single_time::end(
+    single_time::submit(
         base,
         transfer_command.queue.0,
         transfer_command.pool.0,
@@ -85,44 +106,66 @@ pub fn combined_acquire(
     transfer_command: &Command,
     combined_command: &Command,
 ) -> Result<(), UrnError> {
+    combined_acquire_pending(
+        base,
+        device_buffers,
+        device_images,
+        transfer_command,
+        combined_command,
+    )?
+    .finish(base)
+}
 
+/// Doesn't wait for the barrier, see `PendingSubmission`. The submission does not
+/// wait for the release either, only submit it once the release is finished.
+pub fn combined_acquire_pending(
+    base: &Base,
+    device_buffers: &[&DeviceBuffer],
+    device_images: &[&DeviceImage],
+    transfer_command: &Command,
+    combined_command: &Command,
+) -> Result<PendingSubmission, UrnError> {
     let memory_barriers = [];
     let buffer_memory_barriers: Vec<ash::vk::BufferMemoryBarrier> = device_buffers
         .iter()
-        .map(|device_buffer| ash::vk::BufferMemoryBarrier::builder()
-            .src_queue_family_index(transfer_command.family_idx)
-            .dst_queue_family_index(combined_command.family_idx)
-            .src_access_mask(ash::vk::AccessFlags::default())
-            .dst_access_mask(ash::vk::AccessFlags::VERTEX_ATTRIBUTE_READ
-                | ash::vk::AccessFlags::SHADER_READ
-                | ash::vk::AccessFlags::SHADER_WRITE)
-            .buffer(device_buffer.buffer.0)
-            .offset(0)
-            .size(ash::vk::WHOLE_SIZE)
-            .build()
-        )
+        .map(|device_buffer| {
+            ash::vk::BufferMemoryBarrier::builder()
+                .src_queue_family_index(transfer_command.family_idx)
+                .dst_queue_family_index(combined_command.family_idx)
+                .src_access_mask(ash::vk::AccessFlags::default())
+                .dst_access_mask(
+                    ash::vk::AccessFlags::VERTEX_ATTRIBUTE_READ
+                        | ash::vk::AccessFlags::SHADER_READ
+                        | ash::vk::AccessFlags::SHADER_WRITE,
+                )
+                .buffer(device_buffer.buffer.0)
+                .offset(0)
+                .size(ash::vk::WHOLE_SIZE)
+                .build()
+        })
         .collect();
     let image_memory_barriers: Vec<ash::vk::ImageMemoryBarrier> = device_images
         .iter()
-        .map(|device_image| ash::vk::ImageMemoryBarrier::builder()
-            .old_layout(ash::vk::ImageLayout::TRANSFER_DST_OPTIMAL)
-            .new_layout(ash::vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
-            .src_queue_family_index(transfer_command.family_idx)
-            .dst_queue_family_index(combined_command.family_idx)
-            .src_access_mask(ash::vk::AccessFlags::default())
-            .dst_access_mask(ash::vk::AccessFlags::SHADER_READ)
-            .image(device_image.image.0)
-            .subresource_range(
-                ash::vk::ImageSubresourceRange::builder()
-                    .aspect_mask(ash::vk::ImageAspectFlags::COLOR)
-                    .base_mip_level(0)
-                    .level_count(1)
-                    .base_array_layer(0)
-                    .layer_count(1)
-                    .build(),
-            )
-            .build()
-        )
+        .map(|device_image| {
+            ash::vk::ImageMemoryBarrier::builder()
+                .old_layout(ash::vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+                .new_layout(ash::vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                .src_queue_family_index(transfer_command.family_idx)
+                .dst_queue_family_index(combined_command.family_idx)
+                .src_access_mask(ash::vk::AccessFlags::default())
+                .dst_access_mask(ash::vk::AccessFlags::SHADER_READ)
+                .image(device_image.image.0)
+                .subresource_range(
+                    ash::vk::ImageSubresourceRange::builder()
+                        .aspect_mask(ash::vk::ImageAspectFlags::COLOR)
+                        .base_mip_level(0)
+                        .level_count(1)
+                        .base_array_layer(0)
+                        .layer_count(1)
+                        .build(),
+                )
+                .build()
+        })
         .collect();
 
     let combined_command_buffer =
@@ -142,7 +185,7 @@ pub fn combined_acquire(
         )
     }
 
-    single_time::end(
+    single_time::submit(
         base,
         combined_command.queue.0,
         combined_command.pool.0,
@@ -158,7 +201,6 @@ pub fn transfer_to_combined(
     transfer_command: &Command,
     combined_command: &Command,
 ) -> Result<(), UrnError> {
-
     transfer_release(
         base,
         device_buffers,
@@ -0,0 +1,352 @@
+use crate::Base;
+use crate::DeviceBuffer;
+use crate::UrnError;
+
+use crate::command::single_time;
+use crate::memory_alignment::Pod;
+use crate::util::f16_to_f32;
+
+use super::create_staging_device_buffer;
+
+use ash::version::DeviceV1_0;
+
+/// Copies the whole buffer back to the host, its size has to be a multiple of `T`.
+/// The buffer needs `TRANSFER_SRC` usage, work writing it must have been submitted before.
+pub fn readback_buffer<T: Pod>(
+    base: &Base,
+    device_buffer: &DeviceBuffer,
+    queue: ash::vk::Queue,
+    pool: ash::vk::CommandPool,
+) -> Result<Vec<T>, UrnError> {
+    let stride = std::mem::size_of::<T>() as ash::vk::DeviceSize;
+    if stride == 0 || device_buffer.size % stride != 0 {
+        return Err(UrnError::GenericDynamic(format!(
+            "Buffer of {} bytes does not hold whole elements of {} bytes.",
+            device_buffer.size, stride
+        )));
+    }
+
+    let staging =
+        create_staging_device_buffer(base, device_buffer.size, "ReadbackStaging".to_string())?;
+
+    let command_buffer = match single_time::begin(base, pool, "ReadbackBuffer".to_string()) {
+        Ok(command_buffer) => command_buffer,
+        Err(e) => {
+            staging.destroy(base);
+            return Err(e);
+        }
+    };
+    let barrier = ash::vk::MemoryBarrier::builder()
+        .src_access_mask(ash::vk::AccessFlags::MEMORY_WRITE)
+        .dst_access_mask(ash::vk::AccessFlags::TRANSFER_READ)
+        .build();
+    let region = ash::vk::BufferCopy {
+        src_offset: 0,
+        dst_offset: 0,
+        size: device_buffer.size,
+    };
+    unsafe {
+        base.logical_device.0.cmd_pipeline_barrier(
+            command_buffer,
+            ash::vk::PipelineStageFlags::ALL_COMMANDS,
+            ash::vk::PipelineStageFlags::TRANSFER,
+            ash::vk::DependencyFlags::empty(),
+            &[barrier],
+            &[],
+            &[],
+        );
+        base.logical_device.0.cmd_copy_buffer(
+            command_buffer,
+            device_buffer.buffer.0,
+            staging.buffer.0,
+            &[region],
+        );
+    }
+    let finished = single_time::end(base, queue, pool, command_buffer);
+
+    let mut bytes = vec![0u8; device_buffer.size as usize];
+    let read = finished.and_then(|_| staging.read_slice(base, bytes.as_mut_slice()));
+    staging.destroy(base);
+    read?;
+
+    let len = (device_buffer.size / stride) as usize;
+    let mut res = Vec::<T>::with_capacity(len);
+    unsafe {
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), res.as_mut_ptr() as *mut u8, bytes.len());
+        res.set_len(len);
+    }
+
+    Ok(res)
+}
+
+pub struct ReadbackImageSettings {
+    pub queue: ash::vk::Queue,
+    pub pool: ash::vk::CommandPool,
+    pub image: ash::vk::Image,
+    pub width: u32,
+    pub height: u32,
+    pub format: ash::vk::Format,
+    /// Current layout, the image is returned to it afterwards.
+    /// `UNDEFINED` and `PREINITIALIZED` are rejected.
+    pub layout: ash::vk::ImageLayout,
+}
+
+/// Copies the first mip level of a color image back to the host, the image needs
+/// `TRANSFER_SRC` usage. See `to_rgba` for the supported formats.
+pub fn readback_image(
+    base: &Base,
+    settings: &ReadbackImageSettings,
+) -> Result<::image::RgbaImage, UrnError> {
+    let bytes = readback_image_bytes(base, settings)?;
+    to_rgba(settings.width, settings.height, settings.format, &bytes)
+}
+
+/// Same as `readback_image` but keeps the tightly packed texels as they are.
+pub fn readback_image_bytes(
+    base: &Base,
+    settings: &ReadbackImageSettings,
+) -> Result<Vec<u8>, UrnError> {
+    if settings.layout == ash::vk::ImageLayout::UNDEFINED
+        || settings.layout == ash::vk::ImageLayout::PREINITIALIZED
+    {
+        return Err(UrnError::Generic(
+            "Images in undefined or preinitialized layout have no content to read back.",
+        ));
+    }
+    let texel = texel_size(settings.format).ok_or_else(|| unsupported(settings.format))?;
+    let size = settings.width as ash::vk::DeviceSize
+        * settings.height as ash::vk::DeviceSize
+        * texel as ash::vk::DeviceSize;
+
+    let staging = create_staging_device_buffer(base, size, "ReadbackStaging".to_string())?;
+
+    let command_buffer = match single_time::begin(base, settings.pool, "ReadbackImage".to_string())
+    {
+        Ok(command_buffer) => command_buffer,
+        Err(e) => {
+            staging.destroy(base);
+            return Err(e);
+        }
+    };
+    let to_transfer = layout_barrier(
+        settings.image,
+        settings.layout,
+        ash::vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+        ash::vk::AccessFlags::MEMORY_WRITE,
+        ash::vk::AccessFlags::TRANSFER_READ,
+    );
+    let region = ash::vk::BufferImageCopy::builder()
+        .image_subresource(ash::vk::ImageSubresourceLayers {
+            aspect_mask: ash::vk::ImageAspectFlags::COLOR,
+            mip_level: 0,
+            base_array_layer: 0,
+            layer_count: 1,
+        })
+        .image_extent(ash::vk::Extent3D {
+            width: settings.width,
+            height: settings.height,
+            depth: 1,
+        })
+        .build();
+    unsafe {
+        base.logical_device.0.cmd_pipeline_barrier(
+            command_buffer,
+            ash::vk::PipelineStageFlags::ALL_COMMANDS,
+            ash::vk::PipelineStageFlags::TRANSFER,
+            ash::vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &[to_transfer],
+        );
+        base.logical_device.0.cmd_copy_image_to_buffer(
+            command_buffer,
+            settings.image,
+            ash::vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+            staging.buffer.0,
+            &[region],
+        );
+    }
+    let back = layout_barrier(
+        settings.image,
+        ash::vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+        settings.layout,
+        ash::vk::AccessFlags::empty(),
+        ash::vk::AccessFlags::MEMORY_READ | ash::vk::AccessFlags::MEMORY_WRITE,
+    );
+    unsafe {
+        base.logical_device.0.cmd_pipeline_barrier(
+            command_buffer,
+            ash::vk::PipelineStageFlags::TRANSFER,
+            ash::vk::PipelineStageFlags::ALL_COMMANDS,
+            ash::vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &[back],
+        )
+    };
+    let finished = single_time::end(base, settings.queue, settings.pool, command_buffer);
+
+    let mut bytes = vec![0u8; size as usize];
+    let read = finished.and_then(|_| staging.read_slice(base, bytes.as_mut_slice()));
+    staging.destroy(base);
+    read?;
+
+    Ok(bytes)
+}
+
+fn layout_barrier(
+    image: ash::vk::Image,
+    old_layout: ash::vk::ImageLayout,
+    new_layout: ash::vk::ImageLayout,
+    src_access: ash::vk::AccessFlags,
+    dst_access: ash::vk::AccessFlags,
+) -> ash::vk::ImageMemoryBarrier {
+    ash::vk::ImageMemoryBarrier::builder()
+        .old_layout(old_layout)
+        .new_layout(new_layout)
+        .src_queue_family_index(ash::vk::QUEUE_FAMILY_IGNORED)
+        .dst_queue_family_index(ash::vk::QUEUE_FAMILY_IGNORED)
+        .src_access_mask(src_access)
+        .dst_access_mask(dst_access)
+        .image(image)
+        .subresource_range(ash::vk::ImageSubresourceRange {
+            aspect_mask: ash::vk::ImageAspectFlags::COLOR,
+            base_mip_level: 0,
+            level_count: 1,
+            base_array_layer: 0,
+            layer_count: 1,
+        })
+        .build()
+}
+
+fn unsupported(format: ash::vk::Format) -> UrnError {
+    UrnError::GenericDynamic(format!("Readback of {:?} is not supported.", format))
+}
+
+/// Bytes per texel of the formats `to_rgba` understands
+pub fn texel_size(format: ash::vk::Format) -> Option<usize> {
+    match format {
+        ash::vk::Format::R8_UNORM | ash::vk::Format::R8_SRGB => Some(1),
+        ash::vk::Format::R8G8_UNORM => Some(2),
+        ash::vk::Format::R8G8B8A8_UNORM
+        | ash::vk::Format::R8G8B8A8_SRGB
+        | ash::vk::Format::B8G8R8A8_UNORM
+        | ash::vk::Format::B8G8R8A8_SRGB
+        | ash::vk::Format::A2B10G10R10_UNORM_PACK32
+        | ash::vk::Format::R32_SFLOAT => Some(4),
+        ash::vk::Format::R16G16B16A16_SFLOAT => Some(8),
+        ash::vk::Format::R32G32B32A32_SFLOAT => Some(16),
+        _ => None,
+    }
+}
+
+/// Tightly packed texels to 8 bit rgba. Channels are not converted between
+/// linear and sRGB, float channels are clamped to `[0, 1]`.
+/// Missing color channels are zero, missing alpha is opaque.
+pub fn to_rgba(
+    width: u32,
+    height: u32,
+    format: ash::vk::Format,
+    bytes: &[u8],
+) -> Result<::image::RgbaImage, UrnError> {
+    let texel = texel_size(format).ok_or_else(|| unsupported(format))?;
+    let n_texels = width as usize * height as usize;
+    if bytes.len() != n_texels * texel {
+        return Err(UrnError::GenericDynamic(format!(
+            "Expected {} bytes for {}x{} texels of {:?}, got {}.",
+            n_texels * texel,
+            width,
+            height,
+            format,
+            bytes.len()
+        )));
+    }
+
+    let unorm = |x: f32| (x.clamp(0.0, 1.0) * 255.0).round() as u8;
+    let f16 = |b: &[u8]| f16_to_f32(u16::from_le_bytes([b[0], b[1]]));
+    let f32 = |b: &[u8]| f32::from_le_bytes([b[0], b[1], b[2], b[3]]);
+
+    let mut rgba = Vec::with_capacity(n_texels * 4);
+    for t in bytes.chunks_exact(texel) {
+        let pixel = match format {
+            ash::vk::Format::R8_UNORM | ash::vk::Format::R8_SRGB => [t[0], 0, 0, 255],
+            ash::vk::Format::R8G8_UNORM => [t[0], t[1], 0, 255],
+            ash::vk::Format::R8G8B8A8_UNORM | ash::vk::Format::R8G8B8A8_SRGB => {
+                [t[0], t[1], t[2], t[3]]
+            }
+            ash::vk::Format::B8G8R8A8_UNORM | ash::vk::Format::B8G8R8A8_SRGB => {
+                [t[2], t[1], t[0], t[3]]
+            }
+            ash::vk::Format::A2B10G10R10_UNORM_PACK32 => {
+                let packed = u32::from_le_bytes([t[0], t[1], t[2], t[3]]);
+                let ten = |shift: u32| unorm(((packed >> shift) & 0x3ff) as f32 / 1023.0);
+                [ten(0), ten(10), ten(20), ((packed >> 30) * 85) as u8]
+            }
+            ash::vk::Format::R32_SFLOAT => [unorm(f32(t)), 0, 0, 255],
+            ash::vk::Format::R16G16B16A16_SFLOAT => [
+                unorm(f16(&t[0..2])),
+                unorm(f16(&t[2..4])),
+                unorm(f16(&t[4..6])),
+                unorm(f16(&t[6..8])),
+            ],
+            ash::vk::Format::R32G32B32A32_SFLOAT => [
+                unorm(f32(&t[0..4])),
+                unorm(f32(&t[4..8])),
+                unorm(f32(&t[8..12])),
+                unorm(f32(&t[12..16])),
+            ],
+            _ => return Err(unsupported(format)),
+        };
+        rgba.extend_from_slice(&pixel);
+    }
+
+    ::image::RgbaImage::from_raw(width, height, rgba).ok_or(UrnError::Generic(
+        "Pixel buffer does not match the image size.",
+    ))
+}
+
+/// Float texels to linear rgb, alpha is dropped.
+pub fn to_rgb_f32(
+    width: u32,
+    height: u32,
+    format: ash::vk::Format,
+    bytes: &[u8],
+) -> Result<Vec<::image::Rgb<f32>>, UrnError> {
+    let channels = match format {
+        ash::vk::Format::R32_SFLOAT => 1,
+        ash::vk::Format::R16G16B16A16_SFLOAT | ash::vk::Format::R32G32B32A32_SFLOAT => 4,
+        _ => return Err(unsupported(format)),
+    };
+    let texel = texel_size(format).ok_or_else(|| unsupported(format))?;
+    let n_texels = width as usize * height as usize;
+    if bytes.len() != n_texels * texel {
+        return Err(UrnError::GenericDynamic(format!(
+            "Expected {} bytes for {}x{} texels of {:?}, got {}.",
+            n_texels * texel,
+            width,
+            height,
+            format,
+            bytes.len()
+        )));
+    }
+
+    let channel_size = texel / channels;
+    let channel = |b: &[u8]| {
+        if channel_size == 2 {
+            f16_to_f32(u16::from_le_bytes([b[0], b[1]]))
+        } else {
+            f32::from_le_bytes([b[0], b[1], b[2], b[3]])
+        }
+    };
+
+    Ok(bytes
+        .chunks_exact(texel)
+        .map(|t| {
+            let mut rgb = [0.0; 3];
+            for (i, c) in rgb.iter_mut().enumerate().take(channels) {
+                *c = channel(&t[i * channel_size..]);
+            }
+            ::image::Rgb(rgb)
+        })
+        .collect())
+}
@@ -1,8 +1,12 @@
 use crate::Base;
 use crate::UrnError;
 
+use crate::command::PendingSubmission;
+use crate::memory_alignment::Pod;
 use crate::{DeviceBuffer, DeviceBufferSettings};
 
+use super::copy_buffer_to_buffer_pending;
+
 pub fn create_staging_device_buffer(
     base: &Base,
     size: ash::vk::DeviceSize,
@@ -22,3 +26,35 @@ pub fn create_staging_device_buffer(
         },
     )
 }
+
+/// Copies `data` to the start of `dst` through a staging buffer
+/// that lives until the returned submission is finished.
+pub fn upload_to_buffer<T: Pod>(
+    base: &Base,
+    data: &[T],
+    dst: &DeviceBuffer,
+    queue: ash::vk::Queue,
+    pool: ash::vk::CommandPool,
+) -> Result<PendingSubmission, UrnError> {
+    let size = std::mem::size_of_val(data) as ash::vk::DeviceSize;
+    if size > dst.size {
+        return Err(UrnError::GenericDynamic(format!(
+            "Upload of {} bytes does not fit into a buffer of {} bytes.",
+            size, dst.size
+        )));
+    }
+
+    let staging = create_staging_device_buffer(base, size, "UploadStaging".to_string())?;
+    staging.write_slice(base, data)?;
+
+    match copy_buffer_to_buffer_pending(base, queue, pool, staging.buffer.0, dst.buffer.0, size) {
+        Ok(mut pending) => {
+            pending.keep_alive(staging);
+            Ok(pending)
+        }
+        Err(e) => {
+            staging.destroy(base);
+            Err(e)
+        }
+    }
+}
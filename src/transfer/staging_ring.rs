@@ -0,0 +1,183 @@
+use crate::Base;
+use crate::UrnError;
+
+use crate::command::CommandRecorder;
+use crate::memory_alignment::Pod;
+use crate::{DeviceBuffer, DeviceBufferSettings, Timeline};
+
+use std::collections::VecDeque;
+
+struct Region {
+    /// Bytes consumed, including the padding in front
+    size: u64,
+    /// Free once the timeline reached this value
+    value: u64,
+}
+
+/// Bookkeeping of a ring of `capacity` bytes. Every allocation is tagged with
+/// the timeline value after which it is no longer in use, the values must
+/// not decrease from one allocation to the next.
+pub struct RingAllocator {
+    capacity: u64,
+    head: u64,
+    used: u64,
+    regions: VecDeque<Region>,
+}
+
+impl RingAllocator {
+    pub fn new(capacity: u64) -> Self {
+        Self {
+            capacity,
+            head: 0,
+            used: 0,
+            regions: VecDeque::new(),
+        }
+    }
+
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    /// Bytes still in use, including padding
+    pub fn used(&self) -> u64 {
+        self.used
+    }
+
+    /// Offset of `size` bytes aligned to `alignment` (a power of two).
+    /// Ranges never wrap, if the end of the ring is too short it is skipped.
+    pub fn alloc(&mut self, size: u64, alignment: u64, value: u64) -> Result<u64, UrnError> {
+        debug_assert!(
+            alignment.is_power_of_two(),
+            "Alignment must be a power of two."
+        );
+        if size > self.capacity {
+            return Err(UrnError::GenericDynamic(format!(
+                "Allocation of {} bytes is larger than the ring of {} bytes.",
+                size, self.capacity
+            )));
+        }
+        if let Some(last) = self.regions.back() {
+            debug_assert!(last.value <= value, "Timeline values must not decrease.");
+        } else {
+            // nothing in flight, start over to avoid padding
+            self.head = 0;
+        }
+
+        let aligned = (self.head + alignment - 1) & !(alignment - 1);
+        let (start, consumed) = if aligned + size <= self.capacity {
+            (aligned, aligned - self.head + size)
+        } else {
+            (0, self.capacity - self.head + size)
+        };
+        if self.used + consumed > self.capacity {
+            return Err(UrnError::GenericDynamic(format!(
+                "Ring is full, {} of {} bytes are still in use.",
+                self.used, self.capacity
+            )));
+        }
+
+        self.regions.push_back(Region {
+            size: consumed,
+            value,
+        });
+        self.used += consumed;
+        self.head = start + size;
+        Ok(start)
+    }
+
+    /// Frees everything tagged with a value up to `completed`.
+    pub fn release(&mut self, completed: u64) {
+        while let Some(region) = self.regions.front() {
+            if region.value > completed {
+                break;
+            }
+            self.used -= region.size;
+            self.regions.pop_front();
+        }
+    }
+}
+
+pub struct StagingRingSettings {
+    pub size: ash::vk::DeviceSize,
+    pub name: String,
+}
+
+/// Persistently mapped staging memory for data that changes every frame.
+/// Staged ranges stay in use until the timeline reaches the value they were staged with.
+pub struct StagingRing {
+    pub device_buffer: DeviceBuffer,
+    allocator: RingAllocator,
+    alignment: u64,
+}
+
+impl StagingRing {
+    pub fn new(base: &Base, settings: &StagingRingSettings) -> Result<Self, UrnError> {
+        let device_buffer = DeviceBuffer::new(
+            base,
+            &DeviceBufferSettings {
+                size: settings.size,
+                usage: ash::vk::BufferUsageFlags::TRANSFER_SRC,
+                properties: ash::vk::MemoryPropertyFlags::HOST_VISIBLE
+                    | ash::vk::MemoryPropertyFlags::HOST_COHERENT,
+                map: true,
+                shared: false,
+                name: settings.name.clone(),
+            },
+        )?;
+        let alignment = base
+            .physical_device
+            .limits(&base.instance.0)
+            .optimal_buffer_copy_offset_alignment
+            .max(16);
+
+        Ok(Self {
+            device_buffer,
+            allocator: RingAllocator::new(settings.size),
+            alignment,
+        })
+    }
+
+    /// Frees the ranges the GPU is done with.
+    pub fn release(&mut self, base: &Base, timeline: &Timeline) -> Result<(), UrnError> {
+        self.allocator.release(timeline.query(base)?);
+        Ok(())
+    }
+
+    /// Copies `data` into the ring, returns its offset.
+    pub fn stage<T: Pod>(&mut self, base: &Base, data: &[T], value: u64) -> Result<u64, UrnError> {
+        let size = std::mem::size_of_val(data) as u64;
+        let offset = self.allocator.alloc(size, self.alignment, value)?;
+        self.device_buffer.write_slice_at(base, offset, data)?;
+        Ok(offset)
+    }
+
+    /// Stages `data` and records the copy to `dst_offset` in `dst`.
+    /// The submission of `recorder` has to signal `value`.
+    pub fn upload<T: Pod>(
+        &mut self,
+        base: &Base,
+        recorder: &mut CommandRecorder,
+        data: &[T],
+        dst: &DeviceBuffer,
+        dst_offset: ash::vk::DeviceSize,
+        value: u64,
+    ) -> Result<(), UrnError> {
+        let size = std::mem::size_of_val(data) as ash::vk::DeviceSize;
+        DeviceBuffer::check_range(dst.size, dst_offset, size, "Uploading")?;
+        let src_offset = self.stage(base, data, value)?;
+        recorder.copy_buffer(
+            self.device_buffer.buffer.0,
+            dst.buffer.0,
+            &[ash::vk::BufferCopy {
+                src_offset,
+                dst_offset,
+                size,
+            }],
+        );
+        Ok(())
+    }
+
+    pub fn destroy(&self, base: &Base) {
+        self.device_buffer.destroy(base);
+    }
+}
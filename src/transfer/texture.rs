@@ -18,11 +18,41 @@ pub fn create_texture_device_image(
     let width = buffer.width();
     let height = buffer.height();
 
-    let size = (buffer.width() * buffer.height() * 4) as ash::vk::DeviceSize;
+    create_texture_device_image_from_rgba(
+        base,
+        width,
+        height,
+        buffer.into_raw().as_slice(),
+        queue,
+        pool,
+        name,
+    )
+}
+
+/// Same as `create_texture_device_image` for pixels already in memory, 4 bytes each.
+pub fn create_texture_device_image_from_rgba(
+    base: &Base,
+    width: u32,
+    height: u32,
+    pixels: &[u8],
+    queue: ash::vk::Queue,
+    pool: ash::vk::CommandPool,
+    name: String,
+) -> Result<DeviceImage, UrnError> {
+    let size = (width * height * 4) as ash::vk::DeviceSize;
+    if pixels.len() as ash::vk::DeviceSize != size {
+        return Err(UrnError::GenericDynamic(format!(
+            "Expected {} bytes for a {}x{} rgba texture, got {}.",
+            size,
+            width,
+            height,
+            pixels.len()
+        )));
+    }
 
     let staging = create_staging_device_buffer(base, size, format!("{}Staging", name.clone()))?;
 
-    staging.write_slice(base, buffer.into_raw().as_slice())?;
+    staging.write_slice(base, pixels)?;
 
     let texture = DeviceImage::new(
         base,
@@ -0,0 +1,540 @@
+use crate::Base;
+use crate::UrnError;
+
+use crate::command::single_time;
+use crate::command::PendingSubmission;
+use crate::memory_alignment::Pod;
+use crate::sync::Semaphore;
+use crate::Command;
+use crate::{DeviceBuffer, DeviceBufferSettings};
+use crate::{DeviceImage, DeviceImageSettings};
+
+use ash::version::DeviceV1_0;
+
+use std::mem::MaybeUninit;
+
+/// Offsets in the staging buffer are kept at this alignment,
+/// it covers the texel size of every format the batch uploads.
+pub const STAGING_ALIGNMENT: usize = 16;
+
+struct BufferUpload {
+    offset: usize,
+    size: usize,
+    usage: ash::vk::BufferUsageFlags,
+    name: String,
+}
+
+struct ImageUpload {
+    offset: usize,
+    width: u32,
+    height: u32,
+    name: String,
+}
+
+/// Device local resources created by `UploadBatch::submit`,
+/// in the order they were added.
+pub struct Uploads {
+    pub buffers: Vec<DeviceBuffer>,
+    pub images: Vec<DeviceImage>,
+}
+
+impl Uploads {
+    pub fn destroy(&self, base: &Base) {
+        for buffer in &self.buffers {
+            buffer.destroy(base);
+        }
+        for image in &self.images {
+            image.destroy(base);
+        }
+    }
+}
+
+/// Collects buffer and image uploads, packs them into one staging buffer
+/// and records all copies into a single submission.
+/// Images end up in `SHADER_READ_ONLY_OPTIMAL`.
+#[derive(Default)]
+pub struct UploadBatch {
+    // padding bytes of the uploaded types stay uninitialized
+    staging: Vec<MaybeUninit<u8>>,
+    buffers: Vec<BufferUpload>,
+    images: Vec<ImageUpload>,
+}
+
+impl UploadBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only copies through raw pointers, `T` may have padding.
+    fn push<T: Pod>(&mut self, data: &[T]) -> usize {
+        let offset = self.staging_size();
+        let size = std::mem::size_of_val(data);
+        self.staging.resize(offset + size, MaybeUninit::new(0));
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                data.as_ptr() as *const MaybeUninit<u8>,
+                self.staging[offset..].as_mut_ptr(),
+                size,
+            )
+        };
+        offset
+    }
+
+    /// Index into `Uploads::buffers`, `TRANSFER_DST` is added to `usage`.
+    pub fn add_buffer<T: Pod>(
+        &mut self,
+        data: &[T],
+        usage: ash::vk::BufferUsageFlags,
+        name: String,
+    ) -> Result<usize, UrnError> {
+        let size = std::mem::size_of_val(data);
+        if size == 0 {
+            return Err(UrnError::GenericDynamic(format!(
+                "Buffer upload {} is empty.",
+                name
+            )));
+        }
+        let offset = self.push(data);
+        self.buffers.push(BufferUpload {
+            offset,
+            size,
+            usage: usage | ash::vk::BufferUsageFlags::TRANSFER_DST,
+            name,
+        });
+        Ok(self.buffers.len() - 1)
+    }
+
+    /// Index into `Uploads::images`, 4 bytes per pixel.
+    pub fn add_image_rgba(
+        &mut self,
+        width: u32,
+        height: u32,
+        pixels: &[u8],
+        name: String,
+    ) -> Result<usize, UrnError> {
+        let size = width as usize * height as usize * 4;
+        if pixels.len() != size {
+            return Err(UrnError::GenericDynamic(format!(
+                "Expected {} bytes for a {}x{} rgba image, got {}.",
+                size,
+                width,
+                height,
+                pixels.len()
+            )));
+        }
+        let offset = self.push(pixels);
+        self.images.push(ImageUpload {
+            offset,
+            width,
+            height,
+            name,
+        });
+        Ok(self.images.len() - 1)
+    }
+
+    /// Bytes the staging buffer needs so far, aligned for the next upload.
+    pub fn staging_size(&self) -> usize {
+        let len = self.staging.len();
+        len.div_ceil(STAGING_ALIGNMENT) * STAGING_ALIGNMENT
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffers.is_empty() && self.images.is_empty()
+    }
+
+    /// Copies everything on the `transfer` queue and hands the ownership over to
+    /// the family of `combined`, a second submission waits for the first on the GPU.
+    /// The resources can be used once the returned submission is finished.
+    pub fn submit(
+        self,
+        base: &Base,
+        transfer: &Command,
+        combined: &Command,
+    ) -> Result<(Uploads, PendingSubmission), UrnError> {
+        if self.is_empty() {
+            return Err(UrnError::Generic("Upload batch is empty."));
+        }
+
+        let staging = super::create_staging_device_buffer(
+            base,
+            self.staging.len() as ash::vk::DeviceSize,
+            "UploadBatchStaging".to_string(),
+        )?;
+        let uploads = staging
+            .write_slice(base, &self.staging)
+            .and_then(|_| self.create_resources(base));
+        let uploads = match uploads {
+            Ok(uploads) => uploads,
+            Err(e) => {
+                staging.destroy(base);
+                return Err(e);
+            }
+        };
+
+        match self.submit_copies(base, transfer, combined, staging.buffer.0, &uploads) {
+            Ok(mut pending) => {
+                pending.keep_alive(staging);
+                Ok((uploads, pending))
+            }
+            Err(e) => {
+                uploads.destroy(base);
+                staging.destroy(base);
+                Err(e)
+            }
+        }
+    }
+
+    /// Frees the command buffers and the semaphore it created on errors,
+    /// the staging buffer and `uploads` are left to the caller.
+    fn submit_copies(
+        &self,
+        base: &Base,
+        transfer: &Command,
+        combined: &Command,
+        staging: ash::vk::Buffer,
+        uploads: &Uploads,
+    ) -> Result<PendingSubmission, UrnError> {
+        let transfer_buffer =
+            single_time::begin(base, transfer.pool.0, "UploadBatchTransfer".to_string())?;
+        let same_family = transfer.family_idx == combined.family_idx;
+        let families = if same_family {
+            None
+        } else {
+            Some((transfer.family_idx, combined.family_idx))
+        };
+        self.record_copies(base, transfer_buffer, staging, uploads);
+        self.record_release(base, transfer_buffer, uploads, families);
+
+        if same_family {
+            return single_time::submit(base, transfer.queue.0, transfer.pool.0, transfer_buffer);
+        }
+
+        let free_transfer_buffer = || unsafe {
+            base.logical_device
+                .0
+                .free_command_buffers(transfer.pool.0, &[transfer_buffer])
+        };
+        let semaphore = match Semaphore::new(base, "UploadBatchSemaphore".to_string()) {
+            Ok(semaphore) => semaphore,
+            Err(e) => {
+                free_transfer_buffer();
+                return Err(e);
+            }
+        };
+        let submitted = unsafe { base.logical_device.0.end_command_buffer(transfer_buffer) }
+            .and_then(|_| {
+                let command_buffers = [transfer_buffer];
+                let signal_semaphores = [semaphore.0];
+                let submit_info = ash::vk::SubmitInfo::builder()
+                    .command_buffers(&command_buffers)
+                    .signal_semaphores(&signal_semaphores);
+                unsafe {
+                    base.logical_device.0.queue_submit(
+                        transfer.queue.0,
+                        &[submit_info.build()],
+                        ash::vk::Fence::default(),
+                    )
+                }
+            });
+        if let Err(e) = submitted {
+            semaphore.destroy(base);
+            free_transfer_buffer();
+            return Err(e.into());
+        }
+
+        let acquired = single_time::begin(base, combined.pool.0, "UploadBatchAcquire".to_string())
+            .and_then(|acquire_buffer| {
+                self.record_acquire(
+                    base,
+                    acquire_buffer,
+                    uploads,
+                    (transfer.family_idx, combined.family_idx),
+                );
+                single_time::submit_waiting(
+                    base,
+                    combined.queue.0,
+                    combined.pool.0,
+                    acquire_buffer,
+                    semaphore.0,
+                    ash::vk::PipelineStageFlags::ALL_COMMANDS,
+                )
+            });
+        match acquired {
+            Ok(mut pending) => {
+                pending.keep_alive_command_buffer(transfer.pool.0, transfer_buffer);
+                pending.keep_alive_semaphore(semaphore);
+                Ok(pending)
+            }
+            Err(e) => {
+                // the copies are already in flight
+                let _ = unsafe { base.logical_device.0.queue_wait_idle(transfer.queue.0) };
+                semaphore.destroy(base);
+                free_transfer_buffer();
+                Err(e)
+            }
+        }
+    }
+
+    fn create_resources(&self, base: &Base) -> Result<Uploads, UrnError> {
+        let mut uploads = Uploads {
+            buffers: Vec::with_capacity(self.buffers.len()),
+            images: Vec::with_capacity(self.images.len()),
+        };
+        for upload in &self.buffers {
+            let buffer = DeviceBuffer::new(
+                base,
+                &DeviceBufferSettings {
+                    size: upload.size as ash::vk::DeviceSize,
+                    usage: upload.usage,
+                    properties: ash::vk::MemoryPropertyFlags::DEVICE_LOCAL,
+                    map: false,
+                    shared: false,
+                    name: upload.name.clone(),
+                },
+            );
+            match buffer {
+                Ok(buffer) => uploads.buffers.push(buffer),
+                Err(e) => {
+                    uploads.destroy(base);
+                    return Err(e);
+                }
+            }
+        }
+        for upload in &self.images {
+            let image = DeviceImage::new(
+                base,
+                &DeviceImageSettings {
+                    width: upload.width,
+                    height: upload.height,
+                    format: ash::vk::Format::R8G8B8A8_UNORM,
+                    tiling: ash::vk::ImageTiling::OPTIMAL,
+                    usage: ash::vk::ImageUsageFlags::TRANSFER_DST
+                        | ash::vk::ImageUsageFlags::SAMPLED,
+                    properties: ash::vk::MemoryPropertyFlags::DEVICE_LOCAL,
+                    aspect_flags: ash::vk::ImageAspectFlags::COLOR,
+                    name: upload.name.clone(),
+                },
+            );
+            match image {
+                Ok(image) => uploads.images.push(image),
+                Err(e) => {
+                    uploads.destroy(base);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(uploads)
+    }
+
+    fn record_copies(
+        &self,
+        base: &Base,
+        command_buffer: ash::vk::CommandBuffer,
+        staging: ash::vk::Buffer,
+        uploads: &Uploads,
+    ) {
+        let device = &base.logical_device.0;
+        for (upload, buffer) in self.buffers.iter().zip(&uploads.buffers) {
+            let region = ash::vk::BufferCopy {
+                src_offset: upload.offset as ash::vk::DeviceSize,
+                dst_offset: 0,
+                size: upload.size as ash::vk::DeviceSize,
+            };
+            unsafe { device.cmd_copy_buffer(command_buffer, staging, buffer.buffer.0, &[region]) };
+        }
+
+        if uploads.images.is_empty() {
+            return;
+        }
+        let to_transfer_dst: Vec<ash::vk::ImageMemoryBarrier> = uploads
+            .images
+            .iter()
+            .map(|image| {
+                ash::vk::ImageMemoryBarrier::builder()
+                    .old_layout(ash::vk::ImageLayout::UNDEFINED)
+                    .new_layout(ash::vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+                    .src_queue_family_index(ash::vk::QUEUE_FAMILY_IGNORED)
+                    .dst_queue_family_index(ash::vk::QUEUE_FAMILY_IGNORED)
+                    .src_access_mask(ash::vk::AccessFlags::empty())
+                    .dst_access_mask(ash::vk::AccessFlags::TRANSFER_WRITE)
+                    .image(image.image.0)
+                    .subresource_range(color_range())
+                    .build()
+            })
+            .collect();
+        unsafe {
+            device.cmd_pipeline_barrier(
+                command_buffer,
+                ash::vk::PipelineStageFlags::TOP_OF_PIPE,
+                ash::vk::PipelineStageFlags::TRANSFER,
+                ash::vk::DependencyFlags::empty(),
+                &[],
+                &[],
+                &to_transfer_dst,
+            )
+        };
+        for (upload, image) in self.images.iter().zip(&uploads.images) {
+            let region = ash::vk::BufferImageCopy::builder()
+                .buffer_offset(upload.offset as ash::vk::DeviceSize)
+                .image_subresource(ash::vk::ImageSubresourceLayers {
+                    aspect_mask: ash::vk::ImageAspectFlags::COLOR,
+                    mip_level: 0,
+                    base_array_layer: 0,
+                    layer_count: 1,
+                })
+                .image_extent(ash::vk::Extent3D {
+                    width: upload.width,
+                    height: upload.height,
+                    depth: 1,
+                })
+                .build();
+            unsafe {
+                device.cmd_copy_buffer_to_image(
+                    command_buffer,
+                    staging,
+                    image.image.0,
+                    ash::vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                    &[region],
+                )
+            };
+        }
+    }
+
+    /// Without `families` the resources stay on the transfer family
+    /// and this is the only barrier.
+    fn record_release(
+        &self,
+        base: &Base,
+        command_buffer: ash::vk::CommandBuffer,
+        uploads: &Uploads,
+        families: Option<(u32, u32)>,
+    ) {
+        let (src_family, dst_family) =
+            families.unwrap_or((ash::vk::QUEUE_FAMILY_IGNORED, ash::vk::QUEUE_FAMILY_IGNORED));
+        let (dst_stage, buffer_access, image_access) = if families.is_some() {
+            // the access of the release is ignored, the acquire makes it visible
+            (
+                ash::vk::PipelineStageFlags::BOTTOM_OF_PIPE,
+                ash::vk::AccessFlags::empty(),
+                ash::vk::AccessFlags::empty(),
+            )
+        } else {
+            (CONSUMER_STAGES, BUFFER_ACCESS, IMAGE_ACCESS)
+        };
+        let (buffer_barriers, image_barriers) = barriers(
+            uploads,
+            (src_family, dst_family),
+            ash::vk::AccessFlags::TRANSFER_WRITE,
+            (buffer_access, image_access),
+        );
+        unsafe {
+            base.logical_device.0.cmd_pipeline_barrier(
+                command_buffer,
+                ash::vk::PipelineStageFlags::TRANSFER,
+                dst_stage,
+                ash::vk::DependencyFlags::empty(),
+                &[],
+                &buffer_barriers,
+                &image_barriers,
+            )
+        };
+    }
+
+    fn record_acquire(
+        &self,
+        base: &Base,
+        command_buffer: ash::vk::CommandBuffer,
+        uploads: &Uploads,
+        families: (u32, u32),
+    ) {
+        let (buffer_barriers, image_barriers) = barriers(
+            uploads,
+            families,
+            ash::vk::AccessFlags::empty(),
+            (BUFFER_ACCESS, IMAGE_ACCESS),
+        );
+        unsafe {
+            base.logical_device.0.cmd_pipeline_barrier(
+                command_buffer,
+                ash::vk::PipelineStageFlags::TOP_OF_PIPE,
+                CONSUMER_STAGES,
+                ash::vk::DependencyFlags::empty(),
+                &[],
+                &buffer_barriers,
+                &image_barriers,
+            )
+        };
+    }
+}
+
+const CONSUMER_STAGES: ash::vk::PipelineStageFlags = ash::vk::PipelineStageFlags::from_raw(
+    ash::vk::PipelineStageFlags::DRAW_INDIRECT.as_raw()
+        | ash::vk::PipelineStageFlags::VERTEX_INPUT.as_raw()
+        | ash::vk::PipelineStageFlags::VERTEX_SHADER.as_raw()
+        | ash::vk::PipelineStageFlags::FRAGMENT_SHADER.as_raw()
+        | ash::vk::PipelineStageFlags::COMPUTE_SHADER.as_raw(),
+);
+
+const BUFFER_ACCESS: ash::vk::AccessFlags = ash::vk::AccessFlags::from_raw(
+    ash::vk::AccessFlags::INDIRECT_COMMAND_READ.as_raw()
+        | ash::vk::AccessFlags::INDEX_READ.as_raw()
+        | ash::vk::AccessFlags::VERTEX_ATTRIBUTE_READ.as_raw()
+        | ash::vk::AccessFlags::UNIFORM_READ.as_raw()
+        | ash::vk::AccessFlags::SHADER_READ.as_raw()
+        | ash::vk::AccessFlags::SHADER_WRITE.as_raw(),
+);
+
+const IMAGE_ACCESS: ash::vk::AccessFlags = ash::vk::AccessFlags::SHADER_READ;
+
+fn color_range() -> ash::vk::ImageSubresourceRange {
+    ash::vk::ImageSubresourceRange {
+        aspect_mask: ash::vk::ImageAspectFlags::COLOR,
+        base_mip_level: 0,
+        level_count: 1,
+        base_array_layer: 0,
+        layer_count: 1,
+    }
+}
+
+fn barriers(
+    uploads: &Uploads,
+    (src_family, dst_family): (u32, u32),
+    src_access: ash::vk::AccessFlags,
+    (buffer_access, image_access): (ash::vk::AccessFlags, ash::vk::AccessFlags),
+) -> (
+    Vec<ash::vk::BufferMemoryBarrier>,
+    Vec<ash::vk::ImageMemoryBarrier>,
+) {
+    let buffer_barriers = uploads
+        .buffers
+        .iter()
+        .map(|buffer| {
+            ash::vk::BufferMemoryBarrier::builder()
+                .src_queue_family_index(src_family)
+                .dst_queue_family_index(dst_family)
+                .src_access_mask(src_access)
+                .dst_access_mask(buffer_access)
+                .buffer(buffer.buffer.0)
+                .offset(0)
+                .size(ash::vk::WHOLE_SIZE)
+                .build()
+        })
+        .collect();
+    let image_barriers = uploads
+        .images
+        .iter()
+        .map(|image| {
+            ash::vk::ImageMemoryBarrier::builder()
+                .old_layout(ash::vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+                .new_layout(ash::vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                .src_queue_family_index(src_family)
+                .dst_queue_family_index(dst_family)
+                .src_access_mask(src_access)
+                .dst_access_mask(image_access)
+                .image(image.image.0)
+                .subresource_range(color_range())
+                .build()
+        })
+        .collect();
+    (buffer_barriers, image_barriers)
+}
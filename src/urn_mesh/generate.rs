@@ -0,0 +1,293 @@
+//! Generated meshes are centered at the origin with `y` up, triangles are counter
+//! clockwise seen from the outside and texture coordinates start top left, like in glTF.
+
+use super::vector::{self, Vec3};
+use super::{UrnMesh, UrnVertex};
+
+use std::f32::consts::PI;
+
+impl UrnMesh {
+    fn push_vertex(&mut self, pos: Vec3, nor: Vec3, tex: [f32; 2], col: [f32; 4]) -> u32 {
+        self.vertices.push(UrnVertex {
+            pos: pos.into(),
+            nor: nor.into(),
+            col: col.into(),
+            tex: tex.into(),
+        });
+        self.vertices.len() as u32 - 1
+    }
+
+    /// Rows of `columns` vertices, connected to a strip of quads.
+    /// Quads that collapse to a triangle at the poles of a sphere are skipped.
+    fn push_grid_indices(&mut self, first: u32, rows: u32, columns: u32) {
+        for row in 0..rows - 1 {
+            for column in 0..columns - 1 {
+                let a = first + row * columns + column;
+                let b = a + columns;
+                let corners = [a, b, b + 1, a + 1];
+                let pos = |i: u32| self.vertices[i as usize].pos.0;
+                let upper_collapsed = pos(corners[0]) == pos(corners[3]);
+                let lower_collapsed = pos(corners[1]) == pos(corners[2]);
+                if !lower_collapsed {
+                    self.indices.extend_from_slice(&[a, b, b + 1]);
+                }
+                if !upper_collapsed {
+                    self.indices.extend_from_slice(&[a, b + 1, a + 1]);
+                }
+            }
+        }
+    }
+
+    /// Fan around `center`, seen from the side `nor` points to.
+    fn push_disk(&mut self, center: Vec3, nor: Vec3, radius: f32, segments: u32, col: [f32; 4]) {
+        let first = self.push_vertex(center, nor, [0.5, 0.5], col);
+        // counter clockwise around +y, flipped when facing down
+        let sign = if nor[1] < 0.0 { -1.0 } else { 1.0 };
+        for segment in 0..segments {
+            let phi = 2.0 * PI * segment as f32 / segments as f32;
+            let (sin, cos) = phi.sin_cos();
+            self.push_vertex(
+                vector::add(center, [radius * cos, 0.0, -sign * radius * sin]),
+                nor,
+                [0.5 + 0.5 * cos, 0.5 + 0.5 * sign * sin],
+                col,
+            );
+        }
+        for segment in 0..segments {
+            let next = (segment + 1) % segments;
+            self.indices
+                .extend_from_slice(&[first, first + 1 + segment, first + 1 + next]);
+        }
+    }
+
+    /// Axis aligned cube with edge length `size`, 4 vertices per side for sharp normals.
+    pub fn cube(size: f32, col: [f32; 4]) -> Self {
+        let mut mesh = Self::new();
+        let h = 0.5 * size;
+        // normal, then the directions of u and v on that side
+        let sides: [(Vec3, Vec3, Vec3); 6] = [
+            ([1.0, 0.0, 0.0], [0.0, 0.0, -1.0], [0.0, -1.0, 0.0]),
+            ([-1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, -1.0, 0.0]),
+            ([0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]),
+            ([0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, -1.0]),
+            ([0.0, 0.0, 1.0], [1.0, 0.0, 0.0], [0.0, -1.0, 0.0]),
+            ([0.0, 0.0, -1.0], [-1.0, 0.0, 0.0], [0.0, -1.0, 0.0]),
+        ];
+        for (nor, u, v) in sides.iter() {
+            let first = mesh.vertices.len() as u32;
+            for (tex_u, tex_v) in [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)].iter() {
+                let pos = vector::add(
+                    vector::scale(*nor, h),
+                    vector::add(
+                        vector::scale(*u, (2.0 * tex_u - 1.0) * h),
+                        vector::scale(*v, (2.0 * tex_v - 1.0) * h),
+                    ),
+                );
+                mesh.push_vertex(pos, *nor, [*tex_u, *tex_v], col);
+            }
+            mesh.push_grid_indices(first, 2, 2);
+        }
+        mesh
+    }
+
+    /// Sphere from `rings` rows of `segments` quads, the texture wraps around once.
+    pub fn uv_sphere(radius: f32, segments: u32, rings: u32, col: [f32; 4]) -> Self {
+        assert!(segments >= 3 && rings >= 2);
+        let mut mesh = Self::new();
+        for ring in 0..=rings {
+            let theta = PI * ring as f32 / rings as f32;
+            for segment in 0..=segments {
+                let phi = 2.0 * PI * segment as f32 / segments as f32;
+                let nor = if ring == 0 || ring == rings {
+                    // exactly on the pole, so the collapsed quads are detected
+                    [0.0, theta.cos().round(), 0.0]
+                } else {
+                    [
+                        theta.sin() * phi.cos(),
+                        theta.cos(),
+                        -theta.sin() * phi.sin(),
+                    ]
+                };
+                mesh.push_vertex(
+                    vector::scale(nor, radius),
+                    nor,
+                    [segment as f32 / segments as f32, ring as f32 / rings as f32],
+                    col,
+                );
+            }
+        }
+        mesh.push_grid_indices(0, rings + 1, segments + 1);
+        mesh
+    }
+
+    /// Subdivided icosahedron, evenly spread triangles. Texture coordinates are
+    /// a spherical projection and are distorted along the seam.
+    pub fn icosphere(radius: f32, subdivisions: u32, col: [f32; 4]) -> Self {
+        let t = (1.0 + 5.0f32.sqrt()) / 2.0;
+        let mut positions: Vec<Vec3> = [
+            [-1.0, t, 0.0],
+            [1.0, t, 0.0],
+            [-1.0, -t, 0.0],
+            [1.0, -t, 0.0],
+            [0.0, -1.0, t],
+            [0.0, 1.0, t],
+            [0.0, -1.0, -t],
+            [0.0, 1.0, -t],
+            [t, 0.0, -1.0],
+            [t, 0.0, 1.0],
+            [-t, 0.0, -1.0],
+            [-t, 0.0, 1.0],
+        ]
+        .iter()
+        .map(|p| vector::normalize(*p))
+        .collect();
+        let mut triangles: Vec<[u32; 3]> = vec![
+            [0, 11, 5],
+            [0, 5, 1],
+            [0, 1, 7],
+            [0, 7, 10],
+            [0, 10, 11],
+            [1, 5, 9],
+            [5, 11, 4],
+            [11, 10, 2],
+            [10, 7, 6],
+            [7, 1, 8],
+            [3, 9, 4],
+            [3, 4, 2],
+            [3, 2, 6],
+            [3, 6, 8],
+            [3, 8, 9],
+            [4, 9, 5],
+            [2, 4, 11],
+            [6, 2, 10],
+            [8, 6, 7],
+            [9, 8, 1],
+        ];
+
+        for _ in 0..subdivisions {
+            let mut midpoints = std::collections::HashMap::new();
+            let mut midpoint = |a: u32, b: u32| {
+                *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                    let p = vector::add(positions[a as usize], positions[b as usize]);
+                    positions.push(vector::normalize(p));
+                    positions.len() as u32 - 1
+                })
+            };
+            triangles = triangles
+                .iter()
+                .flat_map(|&[a, b, c]| {
+                    let ab = midpoint(a, b);
+                    let bc = midpoint(b, c);
+                    let ca = midpoint(c, a);
+                    vec![[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]
+                })
+                .collect();
+        }
+
+        let mut mesh = Self::new();
+        for nor in positions {
+            let tex = [0.5 + nor[2].atan2(nor[0]) / (2.0 * PI), nor[1].acos() / PI];
+            mesh.push_vertex(vector::scale(nor, radius), nor, tex, col);
+        }
+        mesh.indices = triangles.iter().flatten().copied().collect();
+        mesh
+    }
+
+    /// Along `y` with closed caps.
+    pub fn cylinder(radius: f32, height: f32, segments: u32, col: [f32; 4]) -> Self {
+        assert!(segments >= 3);
+        let mut mesh = Self::new();
+        let h = 0.5 * height;
+        for (y, tex_v) in [(h, 0.0), (-h, 1.0)].iter() {
+            for segment in 0..=segments {
+                let phi = 2.0 * PI * segment as f32 / segments as f32;
+                let nor = [phi.cos(), 0.0, -phi.sin()];
+                let pos = [radius * nor[0], *y, radius * nor[2]];
+                mesh.push_vertex(pos, nor, [segment as f32 / segments as f32, *tex_v], col);
+            }
+        }
+        mesh.push_grid_indices(0, 2, segments + 1);
+        mesh.push_disk([0.0, h, 0.0], [0.0, 1.0, 0.0], radius, segments, col);
+        mesh.push_disk([0.0, -h, 0.0], [0.0, -1.0, 0.0], radius, segments, col);
+        mesh
+    }
+
+    /// Base at `y = 0`, tip at `y = height`, closed at the bottom.
+    pub fn cone(radius: f32, height: f32, segments: u32, col: [f32; 4]) -> Self {
+        assert!(segments >= 3);
+        let mut mesh = Self::new();
+        let first = mesh.vertices.len() as u32;
+        for segment in 0..=segments {
+            let phi = 2.0 * PI * segment as f32 / segments as f32;
+            let (sin, cos) = phi.sin_cos();
+            let nor = vector::normalize([height * cos, radius, -height * sin]);
+            let tex_u = segment as f32 / segments as f32;
+            // one tip per segment for the normals
+            mesh.push_vertex([0.0, height, 0.0], nor, [tex_u, 0.0], col);
+            mesh.push_vertex([radius * cos, 0.0, -radius * sin], nor, [tex_u, 1.0], col);
+        }
+        for segment in 0..segments {
+            let tip = first + 2 * segment;
+            mesh.indices.extend_from_slice(&[tip, tip + 1, tip + 3]);
+        }
+        mesh.push_disk([0.0, 0.0, 0.0], [0.0, -1.0, 0.0], radius, segments, col);
+        mesh
+    }
+
+    /// Flat grid in the `xz` plane facing `y`, `cells` quads along `x` and `z`.
+    pub fn plane_grid(size: [f32; 2], cells: [u32; 2], col: [f32; 4]) -> Self {
+        assert!(cells[0] >= 1 && cells[1] >= 1);
+        let mut mesh = Self::new();
+        for row in 0..=cells[1] {
+            let tex_v = row as f32 / cells[1] as f32;
+            for column in 0..=cells[0] {
+                let tex_u = column as f32 / cells[0] as f32;
+                mesh.push_vertex(
+                    [(tex_u - 0.5) * size[0], 0.0, (tex_v - 0.5) * size[1]],
+                    [0.0, 1.0, 0.0],
+                    [tex_u, tex_v],
+                    col,
+                );
+            }
+        }
+        mesh.push_grid_indices(0, cells[1] + 1, cells[0] + 1);
+        mesh
+    }
+
+    /// Arrow from `from` to `to`, a cylinder with a cone as head.
+    pub fn arrow(from: [f32; 3], to: [f32; 3], radius: f32, col: [f32; 4]) -> Self {
+        const SEGMENTS: u32 = 16;
+        let length = vector::length(vector::sub(to, from));
+        let head = (4.0 * radius).min(0.5 * length);
+        let shaft = length - head;
+
+        let frame = vector::frame(from, vector::sub(to, from));
+        let mut mesh = Self::new();
+        mesh.append(
+            &Self::cylinder(radius, shaft, SEGMENTS, col),
+            &vector::mul(&frame, &vector::translation([0.0, 0.5 * shaft, 0.0])),
+        );
+        mesh.append(
+            &Self::cone(2.0 * radius, head, SEGMENTS, col),
+            &vector::mul(&frame, &vector::translation([0.0, shaft, 0.0])),
+        );
+        mesh
+    }
+
+    /// Coordinate axes gizmo, red `x`, green `y` and blue `z` arrows.
+    pub fn axes(length: f32) -> Self {
+        let radius = 0.02 * length;
+        let origin = [0.0, 0.0, 0.0];
+        let mut mesh = Self::arrow(origin, [length, 0.0, 0.0], radius, [1.0, 0.0, 0.0, 1.0]);
+        let identity = vector::translation(origin);
+        mesh.append(
+            &Self::arrow(origin, [0.0, length, 0.0], radius, [0.0, 1.0, 0.0, 1.0]),
+            &identity,
+        );
+        mesh.append(
+            &Self::arrow(origin, [0.0, 0.0, length], radius, [0.0, 0.0, 1.0, 1.0]),
+            &identity,
+        );
+        mesh
+    }
+}
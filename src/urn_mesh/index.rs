@@ -0,0 +1,108 @@
+use crate::UrnError;
+
+use super::UrnMesh;
+
+/// Integer type of an index buffer.
+pub trait Index: Copy {
+    const TYPE: ash::vk::IndexType;
+    const MAX: u32;
+    /// Caller makes sure `idx <= MAX`
+    fn from_u32(idx: u32) -> Self;
+}
+
+/// Needs `VK_EXT_index_type_uint8` and `index_type_uint8` enabled on the device.
+impl Index for u8 {
+    const TYPE: ash::vk::IndexType = ash::vk::IndexType::UINT8_EXT;
+    const MAX: u32 = u8::MAX as u32;
+    fn from_u32(idx: u32) -> Self {
+        idx as u8
+    }
+}
+
+impl Index for u16 {
+    const TYPE: ash::vk::IndexType = ash::vk::IndexType::UINT16;
+    const MAX: u32 = u16::MAX as u32;
+    fn from_u32(idx: u32) -> Self {
+        idx as u16
+    }
+}
+
+impl Index for u32 {
+    const TYPE: ash::vk::IndexType = ash::vk::IndexType::UINT32;
+    const MAX: u32 = u32::MAX;
+    fn from_u32(idx: u32) -> Self {
+        idx
+    }
+}
+
+/// Indices in the smallest type that fits, see `UrnMesh::compact_indices`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Indices {
+    U8(Vec<u8>),
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+}
+
+impl Indices {
+    pub fn index_type(&self) -> ash::vk::IndexType {
+        match self {
+            Indices::U8(_) => u8::TYPE,
+            Indices::U16(_) => u16::TYPE,
+            Indices::U32(_) => u32::TYPE,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Indices::U8(indices) => indices.len(),
+            Indices::U16(indices) => indices.len(),
+            Indices::U32(indices) => indices.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl UrnMesh {
+    /// Fails if a vertex can't be addressed with `I`.
+    pub fn indices_as<I: Index>(&self) -> Result<Vec<I>, UrnError> {
+        if let Some(idx) = self.indices.iter().find(|idx| **idx > I::MAX) {
+            return Err(UrnError::GenericDynamic(format!(
+                "Index {} does not fit into {:?}.",
+                idx,
+                I::TYPE
+            )));
+        }
+        Ok(self.indices.iter().map(|idx| I::from_u32(*idx)).collect())
+    }
+
+    /// Smallest index type that can address every vertex and holds every index,
+    /// `uint8` if the device has `VK_EXT_index_type_uint8` enabled.
+    pub fn compact_index_type(&self, uint8: bool) -> ash::vk::IndexType {
+        // out of range indices are kept as they are instead of wrapping around
+        let n_indexed = self.indices.iter().max().map_or(0, |idx| *idx as usize + 1);
+        let n = self.vertices.len().max(n_indexed);
+        if uint8 && n <= u8::MAX as usize + 1 {
+            u8::TYPE
+        } else if n <= u16::MAX as usize + 1 {
+            u16::TYPE
+        } else {
+            u32::TYPE
+        }
+    }
+
+    /// The indices in the type picked by `compact_index_type`.
+    pub fn compact_indices(&self, uint8: bool) -> Indices {
+        match self.compact_index_type(uint8) {
+            ash::vk::IndexType::UINT8_EXT => {
+                Indices::U8(self.indices.iter().map(|i| u8::from_u32(*i)).collect())
+            }
+            ash::vk::IndexType::UINT16 => {
+                Indices::U16(self.indices.iter().map(|i| u16::from_u32(*i)).collect())
+            }
+            _ => Indices::U32(self.indices.clone()),
+        }
+    }
+}
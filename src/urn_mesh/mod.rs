@@ -0,0 +1,214 @@
+use crate::memory_alignment::{Align16, Align8, Pod};
+
+mod generate;
+pub mod index;
+pub mod packed;
+mod process;
+pub(crate) mod vector;
+
+pub use ash_urn_derive::Vertex;
+pub use index::{Index, Indices};
+pub use packed::{CompactVertex, PackedVertex};
+pub use process::{Aabb, BoundingSphere};
+
+pub trait Vertex {
+    fn get_binding_description() -> Vec<ash::vk::VertexInputBindingDescription>;
+    fn get_attribute_description() -> Vec<ash::vk::VertexInputAttributeDescription>;
+}
+
+/// Vertex data spread over several bindings, e.g. per-vertex and per-instance
+impl<A: Vertex, B: Vertex> Vertex for (A, B) {
+    fn get_binding_description() -> Vec<ash::vk::VertexInputBindingDescription> {
+        let mut res = A::get_binding_description();
+        res.extend(B::get_binding_description());
+        res
+    }
+
+    fn get_attribute_description() -> Vec<ash::vk::VertexInputAttributeDescription> {
+        let mut res = A::get_attribute_description();
+        res.extend(B::get_attribute_description());
+        res
+    }
+}
+
+impl<A: Vertex, B: Vertex, C: Vertex> Vertex for (A, B, C) {
+    fn get_binding_description() -> Vec<ash::vk::VertexInputBindingDescription> {
+        let mut res = <(A, B)>::get_binding_description();
+        res.extend(C::get_binding_description());
+        res
+    }
+
+    fn get_attribute_description() -> Vec<ash::vk::VertexInputAttributeDescription> {
+        let mut res = <(A, B)>::get_attribute_description();
+        res.extend(C::get_attribute_description());
+        res
+    }
+}
+
+/// Format of a vertex attribute, used by `#[derive(Vertex)]`.
+pub trait VertexFormat {
+    const FORMAT: ash::vk::Format;
+    /// Matrices take one location per column
+    const LOCATIONS: u32 = 1;
+}
+
+/// Integer data that the shader reads as normalized floats
+#[repr(transparent)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct Normalized<T: Copy>(pub T);
+impl<T: Copy> From<T> for Normalized<T> {
+    fn from(t: T) -> Self {
+        Self(t)
+    }
+}
+
+macro_rules! impl_vertex_format {
+    ($($ty:ty: $format:ident;)*) => {
+        $(
+            impl VertexFormat for $ty {
+                const FORMAT: ash::vk::Format = ash::vk::Format::$format;
+            }
+        )*
+    };
+}
+
+impl_vertex_format! {
+    f32: R32_SFLOAT;
+    [f32; 2]: R32G32_SFLOAT;
+    [f32; 3]: R32G32B32_SFLOAT;
+    [f32; 4]: R32G32B32A32_SFLOAT;
+    i32: R32_SINT;
+    [i32; 2]: R32G32_SINT;
+    [i32; 3]: R32G32B32_SINT;
+    [i32; 4]: R32G32B32A32_SINT;
+    u32: R32_UINT;
+    [u32; 2]: R32G32_UINT;
+    [u32; 3]: R32G32B32_UINT;
+    [u32; 4]: R32G32B32A32_UINT;
+    [u16; 2]: R16G16_UINT;
+    [u16; 4]: R16G16B16A16_UINT;
+    [i16; 2]: R16G16_SINT;
+    [i16; 4]: R16G16B16A16_SINT;
+    [u8; 4]: R8G8B8A8_UINT;
+    [i8; 4]: R8G8B8A8_SINT;
+    Normalized<[u16; 2]>: R16G16_UNORM;
+    Normalized<[u16; 4]>: R16G16B16A16_UNORM;
+    Normalized<[i16; 2]>: R16G16_SNORM;
+    Normalized<[i16; 4]>: R16G16B16A16_SNORM;
+    Normalized<[u8; 4]>: R8G8B8A8_UNORM;
+    Normalized<[i8; 4]>: R8G8B8A8_SNORM;
+}
+
+#[cfg(feature = "cgmath")]
+impl_vertex_format! {
+    cgmath::Vector2<f32>: R32G32_SFLOAT;
+    cgmath::Vector3<f32>: R32G32B32_SFLOAT;
+    cgmath::Vector4<f32>: R32G32B32A32_SFLOAT;
+}
+
+#[cfg(feature = "cgmath")]
+impl VertexFormat for cgmath::Matrix4<f32> {
+    const FORMAT: ash::vk::Format = ash::vk::Format::R32G32B32A32_SFLOAT;
+    const LOCATIONS: u32 = 4;
+}
+
+impl VertexFormat for [[f32; 4]; 4] {
+    const FORMAT: ash::vk::Format = ash::vk::Format::R32G32B32A32_SFLOAT;
+    const LOCATIONS: u32 = 4;
+}
+
+impl<T: Copy + VertexFormat> VertexFormat for Align8<T> {
+    const FORMAT: ash::vk::Format = T::FORMAT;
+    const LOCATIONS: u32 = T::LOCATIONS;
+}
+
+impl<T: Copy + VertexFormat> VertexFormat for Align16<T> {
+    const FORMAT: ash::vk::Format = T::FORMAT;
+    const LOCATIONS: u32 = T::LOCATIONS;
+}
+
+#[repr(C, align(64))]
+#[derive(Debug, Copy, Clone, Vertex)]
+pub struct UrnVertex {
+    pub pos: Align16<[f32; 3]>,
+    pub nor: Align16<[f32; 3]>,
+    pub col: Align16<[f32; 4]>,
+    pub tex: Align16<[f32; 2]>,
+}
+
+unsafe impl Pod for UrnVertex {}
+unsafe impl<T: Pod> Pod for Normalized<T> {}
+
+impl Default for UrnVertex {
+    fn default() -> Self {
+        Self {
+            pos: [0.0, 0.0, 0.0].into(),
+            nor: [0.0, 0.0, 0.0].into(),
+            col: [0.0, 0.0, 0.0, 0.0].into(),
+            tex: [0.0, 0.0].into(),
+        }
+    }
+}
+
+#[repr(C)]
+pub struct UrnMesh {
+    pub vertices: Vec<UrnVertex>,
+    pub indices: Vec<u32>,
+}
+
+impl UrnMesh {
+    pub fn new() -> Self {
+        Self {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+        }
+    }
+
+    /// Vertices in another layout, e.g. `PackedVertex` or `CompactVertex`,
+    /// the indices can be used as they are.
+    pub fn convert_vertices<V>(&self) -> Vec<V>
+    where
+        V: for<'a> From<&'a UrnVertex>,
+    {
+        self.vertices.iter().map(V::from).collect()
+    }
+
+    pub fn add_quad(
+        mut self,
+        c0: [f32; 3],
+        c1: [f32; 3],
+        c2: [f32; 3],
+        c3: [f32; 3],
+        col: [f32; 4],
+    ) -> Self {
+        let offset = self.vertices.len() as u32;
+        self.vertices.push(UrnVertex {
+            pos: c0.into(),
+            col: col.into(),
+            ..Default::default()
+        });
+        self.vertices.push(UrnVertex {
+            pos: c1.into(),
+            col: col.into(),
+            ..Default::default()
+        });
+        self.vertices.push(UrnVertex {
+            pos: c2.into(),
+            col: col.into(),
+            ..Default::default()
+        });
+        self.vertices.push(UrnVertex {
+            pos: c3.into(),
+            col: col.into(),
+            ..Default::default()
+        });
+        self.indices.push(offset + 1);
+        self.indices.push(offset + 0);
+        self.indices.push(offset + 2);
+        self.indices.push(offset + 3);
+        self.indices.push(offset + 2);
+        self.indices.push(offset + 0);
+
+        self
+    }
+}
@@ -0,0 +1,100 @@
+use crate::memory_alignment::Pod;
+use crate::util::f32_to_f16;
+
+use super::{Normalized, UrnVertex};
+
+/// `UrnVertex` without the padding, 48 instead of 64 bytes.
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, super::Vertex)]
+pub struct PackedVertex {
+    pub pos: [f32; 3],
+    pub nor: [f32; 3],
+    pub col: [f32; 4],
+    pub tex: [f32; 2],
+}
+
+/// 20 bytes per vertex, the shader inputs stay the same except for the normal:
+/// `pos` is a half float vec4 with w = 1,
+/// `nor` an octahedral encoded vec2, see `oct_decode` for the shader side,
+/// `col` and `tex` are normalized, texture coordinates are clamped to [0, 1].
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, super::Vertex)]
+pub struct CompactVertex {
+    #[vertex(format = "R16G16B16A16_SFLOAT")]
+    pub pos: [u16; 4],
+    pub nor: Normalized<[i16; 2]>,
+    pub col: Normalized<[u8; 4]>,
+    pub tex: Normalized<[u16; 2]>,
+}
+
+unsafe impl Pod for PackedVertex {}
+unsafe impl Pod for CompactVertex {}
+
+impl From<&UrnVertex> for PackedVertex {
+    fn from(v: &UrnVertex) -> Self {
+        Self {
+            pos: v.pos.0,
+            nor: v.nor.0,
+            col: v.col.0,
+            tex: v.tex.0,
+        }
+    }
+}
+
+impl From<&UrnVertex> for CompactVertex {
+    fn from(v: &UrnVertex) -> Self {
+        let [x, y, z] = v.pos.0;
+        let unorm8 = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        let unorm16 = |c: f32| (c.clamp(0.0, 1.0) * 65535.0).round() as u16;
+        Self {
+            pos: [f32_to_f16(x), f32_to_f16(y), f32_to_f16(z), f32_to_f16(1.0)],
+            nor: oct_encode(v.nor.0).into(),
+            col: [
+                unorm8(v.col.0[0]),
+                unorm8(v.col.0[1]),
+                unorm8(v.col.0[2]),
+                unorm8(v.col.0[3]),
+            ]
+            .into(),
+            tex: [unorm16(v.tex.0[0]), unorm16(v.tex.0[1])].into(),
+        }
+    }
+}
+
+fn sign(x: f32) -> f32 {
+    if x >= 0.0 {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+/// Maps a normal onto the octahedron unfolded into [-1, 1]², a zero vector becomes +z.
+pub fn oct_encode(n: [f32; 3]) -> [i16; 2] {
+    let l1 = n[0].abs() + n[1].abs() + n[2].abs();
+    if l1 == 0.0 {
+        return [0, 0];
+    }
+    let (x, y) = (n[0] / l1, n[1] / l1);
+    let (x, y) = if n[2] < 0.0 {
+        ((1.0 - y.abs()) * sign(x), (1.0 - x.abs()) * sign(y))
+    } else {
+        (x, y)
+    };
+    let snorm = |c: f32| (c.clamp(-1.0, 1.0) * 32767.0).round() as i16;
+    [snorm(x), snorm(y)]
+}
+
+/// Inverse of `oct_encode`, the same math works in a shader on the normalized vec2.
+pub fn oct_decode(e: [i16; 2]) -> [f32; 3] {
+    let x = (e[0] as f32 / 32767.0).max(-1.0);
+    let y = (e[1] as f32 / 32767.0).max(-1.0);
+    let z = 1.0 - x.abs() - y.abs();
+    let (x, y) = if z < 0.0 {
+        ((1.0 - y.abs()) * sign(x), (1.0 - x.abs()) * sign(y))
+    } else {
+        (x, y)
+    };
+    let l = (x * x + y * y + z * z).sqrt();
+    [x / l, y / l, z / l]
+}
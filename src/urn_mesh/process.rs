@@ -0,0 +1,255 @@
+use super::vector::{self, Vec3};
+use super::{UrnMesh, UrnVertex};
+
+use std::collections::HashMap;
+
+/// Axis aligned bounding box
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Aabb {
+    pub min: [f32; 3],
+    pub max: [f32; 3],
+}
+
+impl Aabb {
+    pub fn center(&self) -> [f32; 3] {
+        vector::scale(vector::add(self.min, self.max), 0.5)
+    }
+
+    pub fn extent(&self) -> [f32; 3] {
+        vector::sub(self.max, self.min)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BoundingSphere {
+    pub center: [f32; 3],
+    pub radius: f32,
+}
+
+fn corners(mesh: &UrnMesh, triangle: &[u32]) -> [Vec3; 3] {
+    [
+        mesh.vertices[triangle[0] as usize].pos.0,
+        mesh.vertices[triangle[1] as usize].pos.0,
+        mesh.vertices[triangle[2] as usize].pos.0,
+    ]
+}
+
+/// Not normalized, the length is twice the area
+fn face_normal([p0, p1, p2]: [Vec3; 3]) -> Vec3 {
+    vector::cross(vector::sub(p1, p0), vector::sub(p2, p0))
+}
+
+impl UrnMesh {
+    /// Every triangle gets its own vertices with the face normal.
+    pub fn compute_flat_normals(&mut self) {
+        let mut vertices = Vec::with_capacity(self.indices.len());
+        for triangle in self.indices.chunks_exact(3) {
+            let normal = vector::normalize(face_normal(corners(self, triangle)));
+            for idx in triangle {
+                let mut vertex = self.vertices[*idx as usize];
+                vertex.nor = normal.into();
+                vertices.push(vertex);
+            }
+        }
+        self.indices = (0..vertices.len() as u32).collect();
+        self.vertices = vertices;
+    }
+
+    /// Area weighted average of the adjacent face normals. Only shared vertices
+    /// are smoothed, `weld` first to smooth over seams.
+    pub fn compute_smooth_normals(&mut self) {
+        let mut normals = vec![[0.0; 3]; self.vertices.len()];
+        for triangle in self.indices.chunks_exact(3) {
+            let normal = face_normal(corners(self, triangle));
+            for idx in triangle {
+                normals[*idx as usize] = vector::add(normals[*idx as usize], normal);
+            }
+        }
+        for (vertex, normal) in self.vertices.iter_mut().zip(normals) {
+            vertex.nor = vector::normalize(normal).into();
+        }
+    }
+
+    /// Per vertex tangents from the texture coordinates, `w` is the handedness
+    /// of the bitangent (`cross(nor, tangent) * w`). Needs normals.
+    pub fn compute_tangents(&self) -> Vec<[f32; 4]> {
+        let mut tangents = vec![[0.0; 3]; self.vertices.len()];
+        let mut bitangents = vec![[0.0; 3]; self.vertices.len()];
+        for triangle in self.indices.chunks_exact(3) {
+            let [p0, p1, p2] = corners(self, triangle);
+            let t0 = self.vertices[triangle[0] as usize].tex.0;
+            let t1 = self.vertices[triangle[1] as usize].tex.0;
+            let t2 = self.vertices[triangle[2] as usize].tex.0;
+
+            let e1 = vector::sub(p1, p0);
+            let e2 = vector::sub(p2, p0);
+            let (du1, dv1) = (t1[0] - t0[0], t1[1] - t0[1]);
+            let (du2, dv2) = (t2[0] - t0[0], t2[1] - t0[1]);
+            let det = du1 * dv2 - du2 * dv1;
+            if det.abs() < f32::EPSILON {
+                continue;
+            }
+            let r = 1.0 / det;
+            let tangent = vector::scale(
+                vector::sub(vector::scale(e1, dv2), vector::scale(e2, dv1)),
+                r,
+            );
+            let bitangent = vector::scale(
+                vector::sub(vector::scale(e2, du1), vector::scale(e1, du2)),
+                r,
+            );
+            for idx in triangle {
+                let idx = *idx as usize;
+                tangents[idx] = vector::add(tangents[idx], tangent);
+                bitangents[idx] = vector::add(bitangents[idx], bitangent);
+            }
+        }
+
+        self.vertices
+            .iter()
+            .zip(tangents.iter().zip(bitangents.iter()))
+            .map(|(vertex, (t, b))| {
+                let n = vertex.nor.0;
+                // Gram-Schmidt, keeps the tangent orthogonal to the normal
+                let t = vector::normalize(vector::sub(*t, vector::scale(n, vector::dot(n, *t))));
+                let w = if vector::dot(vector::cross(n, t), *b) < 0.0 {
+                    -1.0
+                } else {
+                    1.0
+                };
+                [t[0], t[1], t[2], w]
+            })
+            .collect()
+    }
+
+    /// Appends `other` with positions and normals transformed by the column major `transform`.
+    /// Mirroring transforms flip the winding to keep the triangles front facing.
+    pub fn append(&mut self, other: &UrnMesh, transform: &[[f32; 4]; 4]) {
+        let offset = self.vertices.len() as u32;
+        self.vertices
+            .extend(other.vertices.iter().map(|vertex| UrnVertex {
+                pos: vector::transform_point(transform, vertex.pos.0).into(),
+                nor: vector::transform_normal(transform, vertex.nor.0).into(),
+                ..*vertex
+            }));
+
+        let mirrored = vector::determinant(transform) < 0.0;
+        for triangle in other.indices.chunks_exact(3) {
+            if mirrored {
+                self.indices.extend_from_slice(&[
+                    triangle[0] + offset,
+                    triangle[2] + offset,
+                    triangle[1] + offset,
+                ]);
+            } else {
+                self.indices.extend(triangle.iter().map(|idx| idx + offset));
+            }
+        }
+    }
+
+    /// One mesh from several, each with its own transform, see `append`.
+    pub fn merge(parts: &[(&UrnMesh, [[f32; 4]; 4])]) -> Self {
+        let mut res = Self::new();
+        for (mesh, transform) in parts {
+            res.append(mesh, transform);
+        }
+        res
+    }
+
+    /// `None` without vertices
+    pub fn bounding_box(&self) -> Option<Aabb> {
+        let first = self.vertices.first()?.pos.0;
+        let mut aabb = Aabb {
+            min: first,
+            max: first,
+        };
+        for vertex in &self.vertices {
+            for i in 0..3 {
+                aabb.min[i] = aabb.min[i].min(vertex.pos.0[i]);
+                aabb.max[i] = aabb.max[i].max(vertex.pos.0[i]);
+            }
+        }
+        Some(aabb)
+    }
+
+    /// Centered on the bounding box, so not the smallest sphere but close to it.
+    /// `None` without vertices
+    pub fn bounding_sphere(&self) -> Option<BoundingSphere> {
+        let center = self.bounding_box()?.center();
+        let radius = self
+            .vertices
+            .iter()
+            .map(|vertex| vector::length(vector::sub(vertex.pos.0, center)))
+            .fold(0.0, f32::max);
+        Some(BoundingSphere { center, radius })
+    }
+
+    /// Merges vertices whose attributes all differ by at most `epsilon`
+    /// and drops the triangles that collapse.
+    pub fn weld(&mut self, epsilon: f32) {
+        let cell_size = epsilon.max(f32::MIN_POSITIVE) * 2.0;
+        let cell = |p: Vec3| -> [i64; 3] {
+            [
+                (p[0] / cell_size).floor() as i64,
+                (p[1] / cell_size).floor() as i64,
+                (p[2] / cell_size).floor() as i64,
+            ]
+        };
+        let close = |a: &UrnVertex, b: &UrnVertex| {
+            let pairs = a.pos.0.iter().zip(b.pos.0.iter());
+            let pairs = pairs.chain(a.nor.0.iter().zip(b.nor.0.iter()));
+            let pairs = pairs.chain(a.col.0.iter().zip(b.col.0.iter()));
+            let mut pairs = pairs.chain(a.tex.0.iter().zip(b.tex.0.iter()));
+            pairs.all(|(a, b)| (a - b).abs() <= epsilon)
+        };
+
+        let mut vertices: Vec<UrnVertex> = Vec::new();
+        let mut grid: HashMap<[i64; 3], Vec<u32>> = HashMap::new();
+        let mut remap = Vec::with_capacity(self.vertices.len());
+        for vertex in &self.vertices {
+            let c = cell(vertex.pos.0);
+            // candidates can sit in the neighbouring cells as well
+            let mut found = None;
+            'search: for dx in -1..=1 {
+                for dy in -1..=1 {
+                    for dz in -1..=1 {
+                        let key = [c[0] + dx, c[1] + dy, c[2] + dz];
+                        if let Some(candidates) = grid.get(&key) {
+                            if let Some(idx) = candidates
+                                .iter()
+                                .find(|idx| close(&vertices[**idx as usize], vertex))
+                            {
+                                found = Some(*idx);
+                                break 'search;
+                            }
+                        }
+                    }
+                }
+            }
+            let idx = found.unwrap_or_else(|| {
+                vertices.push(*vertex);
+                let idx = vertices.len() as u32 - 1;
+                grid.entry(c).or_default().push(idx);
+                idx
+            });
+            remap.push(idx);
+        }
+
+        let indices = self
+            .indices
+            .chunks_exact(3)
+            .map(|triangle| {
+                [
+                    remap[triangle[0] as usize],
+                    remap[triangle[1] as usize],
+                    remap[triangle[2] as usize],
+                ]
+            })
+            .filter(|[a, b, c]| a != b && b != c && c != a)
+            .flatten()
+            .collect();
+
+        self.vertices = vertices;
+        self.indices = indices;
+    }
+}
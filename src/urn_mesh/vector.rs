@@ -0,0 +1,112 @@
+//! Just enough linear algebra for the generators and processing,
+//! matrices are column major like in glTF.
+
+pub(crate) type Vec3 = [f32; 3];
+pub(crate) type Mat4 = [[f32; 4]; 4];
+
+pub(crate) fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+pub(crate) fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+pub(crate) fn scale(a: Vec3, s: f32) -> Vec3 {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+pub(crate) fn dot(a: Vec3, b: Vec3) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub(crate) fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+pub(crate) fn length(a: Vec3) -> f32 {
+    dot(a, a).sqrt()
+}
+
+/// Zero vectors stay zero
+pub(crate) fn normalize(a: Vec3) -> Vec3 {
+    let l = length(a);
+    if l > 0.0 {
+        scale(a, 1.0 / l)
+    } else {
+        a
+    }
+}
+
+pub(crate) fn transform_point(m: &Mat4, p: Vec3) -> Vec3 {
+    let mut res = [m[3][0], m[3][1], m[3][2]];
+    for (i, r) in res.iter_mut().enumerate() {
+        *r += m[0][i] * p[0] + m[1][i] * p[1] + m[2][i] * p[2];
+    }
+    res
+}
+
+fn column(m: &Mat4, i: usize) -> Vec3 {
+    [m[i][0], m[i][1], m[i][2]]
+}
+
+/// Determinant of the upper 3x3, negative for mirroring transforms
+pub(crate) fn determinant(m: &Mat4) -> f32 {
+    dot(column(m, 0), cross(column(m, 1), column(m, 2)))
+}
+
+/// Inverse transpose of the upper 3x3 applied to `n`, normalized
+pub(crate) fn transform_normal(m: &Mat4, n: Vec3) -> Vec3 {
+    // the cofactor matrix is the inverse transpose scaled by the determinant
+    let cofactor = [
+        cross(column(m, 1), column(m, 2)),
+        cross(column(m, 2), column(m, 0)),
+        cross(column(m, 0), column(m, 1)),
+    ];
+    let res = add(
+        add(scale(cofactor[0], n[0]), scale(cofactor[1], n[1])),
+        scale(cofactor[2], n[2]),
+    );
+    normalize(scale(res, determinant(m).signum()))
+}
+
+/// Rotates `y` onto `dir` and moves the origin to `origin`
+pub(crate) fn frame(origin: Vec3, dir: Vec3) -> Mat4 {
+    let y = normalize(dir);
+    let helper = if y[1].abs() < 0.9 {
+        [0.0, 1.0, 0.0]
+    } else {
+        [1.0, 0.0, 0.0]
+    };
+    let x = normalize(cross(y, helper));
+    let z = cross(x, y);
+    [
+        [x[0], x[1], x[2], 0.0],
+        [y[0], y[1], y[2], 0.0],
+        [z[0], z[1], z[2], 0.0],
+        [origin[0], origin[1], origin[2], 1.0],
+    ]
+}
+
+pub(crate) fn translation(t: Vec3) -> Mat4 {
+    [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [t[0], t[1], t[2], 1.0],
+    ]
+}
+
+pub(crate) fn mul(a: &Mat4, b: &Mat4) -> Mat4 {
+    let mut res = [[0.0; 4]; 4];
+    for (col, res_col) in res.iter_mut().enumerate() {
+        for (row, value) in res_col.iter_mut().enumerate() {
+            *value = (0..4).map(|k| a[k][row] * b[col][k]).sum();
+        }
+    }
+    res
+}
@@ -0,0 +1,29 @@
+use ash_urn::descriptor::bindless::HandleAllocator;
+
+#[test]
+fn handles_are_reused_after_free() {
+    let mut handles = HandleAllocator::new(3);
+    assert_eq!(handles.alloc(), Some(0));
+    assert_eq!(handles.alloc(), Some(1));
+    assert_eq!(handles.alloc(), Some(2));
+    assert_eq!(handles.alloc(), None);
+
+    handles.free(1).unwrap();
+    assert_eq!(handles.alloc(), Some(1));
+    assert_eq!(handles.alloc(), None);
+}
+
+#[test]
+fn invalid_frees_are_rejected() {
+    let mut handles = HandleAllocator::new(4);
+    assert!(handles.free(0).is_err());
+
+    let idx = handles.alloc().unwrap();
+    handles.free(idx).unwrap();
+    assert!(handles.free(idx).is_err());
+    assert!(handles.free(3).is_err());
+
+    // the rejected frees do not hand out a slot twice
+    assert_eq!(handles.alloc(), Some(idx));
+    assert_eq!(handles.alloc(), Some(1));
+}
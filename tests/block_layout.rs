@@ -0,0 +1,109 @@
+use ash_urn::memory_alignment::{Align16, Align8, Std140, Std430};
+
+use std::collections::HashMap;
+
+#[path = "../examples/basic_compute/blocks.rs"]
+mod blocks;
+use blocks::{GraphicsUBO, Particle, Simulation};
+
+/// Member offsets of every named struct in a SPIR-V module
+fn spirv_offsets(path: &str) -> HashMap<String, Vec<usize>> {
+    let bytes = std::fs::read(path).unwrap();
+    let words: Vec<u32> = bytes
+        .chunks_exact(4)
+        .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect();
+    assert_eq!(words[0], 0x0723_0203, "not a SPIR-V module");
+
+    let mut names = HashMap::new();
+    let mut offsets: HashMap<u32, Vec<(u32, usize)>> = HashMap::new();
+    let mut i = 5;
+    while i < words.len() {
+        let count = (words[i] >> 16) as usize;
+        let opcode = words[i] & 0xffff;
+        let operands = &words[i + 1..i + count];
+        match opcode {
+            // OpName
+            5 => {
+                let name: Vec<u8> = operands[1..]
+                    .iter()
+                    .flat_map(|w| w.to_le_bytes().to_vec())
+                    .take_while(|b| *b != 0)
+                    .collect();
+                names.insert(operands[0], String::from_utf8(name).unwrap());
+            }
+            // OpMemberDecorate with Offset
+            72 if operands[2] == 35 => {
+                offsets
+                    .entry(operands[0])
+                    .or_default()
+                    .push((operands[1], operands[3] as usize));
+            }
+            _ => {}
+        }
+        i += count;
+    }
+
+    offsets
+        .into_iter()
+        .map(|(id, mut members)| {
+            members.sort();
+            (
+                names[&id].clone(),
+                members.into_iter().map(|(_, offset)| offset).collect(),
+            )
+        })
+        .collect()
+}
+
+fn field_offsets(offsets: &[(&str, usize)]) -> Vec<usize> {
+    offsets.iter().map(|(_, offset)| *offset).collect()
+}
+
+#[repr(C)]
+#[derive(Std140)]
+struct Padded {
+    a: f32,
+    b: Align16<[f32; 4]>,
+    c: f32,
+    d: Align8<[f32; 2]>,
+    e: [Inner; 2],
+}
+
+#[repr(C)]
+#[derive(Std140)]
+struct Inner {
+    x: Align16<f32>,
+}
+
+#[test]
+fn graphics_ubo_matches_shader() {
+    let spirv = spirv_offsets("examples/basic_compute/shaders/vert.spv");
+    assert_eq!(field_offsets(GraphicsUBO::OFFSETS), spirv["UBO"]);
+}
+
+#[test]
+fn simulation_matches_shader() {
+    for shader in &["calculate", "integrate", "cull"] {
+        let spirv = spirv_offsets(&format!("examples/basic_compute/shaders/{}.spv", shader));
+        assert_eq!(field_offsets(Simulation::OFFSETS), spirv["Simulation"]);
+    }
+}
+
+#[test]
+fn particle_matches_shader() {
+    let spirv = spirv_offsets("examples/basic_compute/shaders/calculate.spv");
+    assert_eq!(field_offsets(Particle::OFFSETS), spirv["Particle"]);
+}
+
+#[test]
+fn padded_offsets() {
+    assert_eq!(field_offsets(Padded::OFFSETS), vec![0, 16, 32, 40, 48]);
+    assert_eq!(Padded::OFFSETS[4].0, "e");
+}
+
+/// `TRYBUILD=overwrite` rewrites the expected errors after message changes
+#[test]
+fn misaligned_blocks_fail_to_compile() {
+    trybuild::TestCases::new().compile_fail("tests/compile_fail/*.rs");
+}
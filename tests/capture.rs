@@ -0,0 +1,119 @@
+use ash::vk::Format;
+use ash_urn::capture::{FrameSequence, FrameSequenceSettings};
+use ash_urn::Capture;
+
+mod common;
+use common::scratch_dir;
+
+fn bgra_capture() -> Capture {
+    Capture {
+        width: 2,
+        height: 1,
+        format: Format::B8G8R8A8_SRGB,
+        bytes: vec![0, 0, 255, 255, 255, 0, 0, 255],
+    }
+}
+
+#[test]
+fn png_round_trip() {
+    let dir = scratch_dir("capture_png");
+    let path = dir.join("frame.png");
+    bgra_capture().save(&path).unwrap();
+
+    let loaded = image::open(&path).unwrap().to_rgba();
+    assert_eq!(loaded.dimensions(), (2, 1));
+    assert_eq!(loaded.into_raw(), vec![255, 0, 0, 255, 0, 0, 255, 255]);
+}
+
+#[test]
+fn float_targets_save_as_hdr() {
+    let dir = scratch_dir("capture_hdr");
+    let bytes = [4.0f32, 0.5, 0.0, 1.0]
+        .iter()
+        .flat_map(|c| c.to_le_bytes().to_vec())
+        .collect();
+    let capture = Capture {
+        width: 1,
+        height: 1,
+        format: Format::R32G32B32A32_SFLOAT,
+        bytes,
+    };
+    assert!(capture.is_float());
+    assert!(!bgra_capture().is_float());
+
+    let path = dir.join("frame.hdr");
+    capture.save(&path).unwrap();
+    let loaded = image::open(&path).unwrap().to_rgb();
+    assert_eq!(loaded.dimensions(), (1, 1));
+
+    assert!(bgra_capture().save_hdr(dir.join("bgra.hdr")).is_err());
+    assert!(capture.save(dir.join("frame.jpg")).is_err());
+}
+
+#[test]
+fn float_targets_are_encoded_as_srgb_png() {
+    let dir = scratch_dir("capture_float_png");
+    let bytes = [4.0f32, 0.5, 0.0, 0.5]
+        .iter()
+        .flat_map(|c| c.to_le_bytes().to_vec())
+        .collect();
+    let capture = Capture {
+        width: 1,
+        height: 1,
+        format: Format::R32G32B32A32_SFLOAT,
+        bytes,
+    };
+    let path = dir.join("frame.png");
+    capture.save(&path).unwrap();
+
+    let loaded = image::open(&path).unwrap().to_rgba();
+    assert_eq!(loaded.into_raw(), vec![255, 188, 0, 128]);
+}
+
+#[cfg(feature = "exr")]
+#[test]
+fn float_targets_save_as_exr() {
+    let dir = scratch_dir("capture_exr");
+    let bytes = [4.0f32, 0.5, 0.0, 1.0]
+        .iter()
+        .flat_map(|c| c.to_le_bytes().to_vec())
+        .collect();
+    let capture = Capture {
+        width: 1,
+        height: 1,
+        format: Format::R32G32B32A32_SFLOAT,
+        bytes,
+    };
+    let path = dir.join("frame.exr");
+    capture.save(&path).unwrap();
+
+    let image = exr::prelude::read_first_flat_layer_from_file(&path).unwrap();
+    assert_eq!(image.layer_data.size, exr::math::Vec2(1, 1));
+    let red = image
+        .layer_data
+        .channel_data
+        .list
+        .iter()
+        .find(|channel| channel.name.eq("R"))
+        .unwrap();
+    assert_eq!(red.sample_data.value_by_flat_index(0).to_f32(), 4.0);
+}
+
+#[test]
+fn sequence_numbers_frames() {
+    let dir = scratch_dir("capture_sequence");
+    let mut sequence = FrameSequence::new(&FrameSequenceSettings {
+        directory: dir.clone(),
+        prefix: "run".to_string(),
+        extension: "png".to_string(),
+    })
+    .unwrap();
+    assert_eq!(sequence.next_path(), dir.join("run_00000.png"));
+
+    let capture = bgra_capture();
+    sequence.save(&capture).unwrap();
+    let second = sequence.save(&capture).unwrap();
+    assert_eq!(second, dir.join("run_00001.png"));
+    assert_eq!(sequence.frames(), 2);
+    assert!(dir.join("run_00000.png").exists());
+}
@@ -0,0 +1,35 @@
+use ash_urn::base::HeadlessSettings;
+use ash_urn::{Base, UrnError};
+
+use std::path::PathBuf;
+
+/// Skips only without a Vulkan loader or device, other errors fail the test.
+#[allow(dead_code)]
+pub fn headless_base(name: &str) -> Option<Base> {
+    match Base::headless(&HeadlessSettings {
+        name: name.to_string(),
+        enable_validation: false,
+        timelines: false,
+        prefer_cpu: true,
+    }) {
+        Ok(base) => Some(base),
+        Err(UrnError::AshLoadingError(e)) => {
+            println!("Skipping, no Vulkan loader: {:?}", e);
+            None
+        }
+        Err(UrnError::NoDevice) => {
+            println!("Skipping, no Vulkan device.");
+            None
+        }
+        Err(e) => panic!("Failed to create a headless base: {:?}", e),
+    }
+}
+
+/// Empty directory for the files of one test
+#[allow(dead_code)]
+pub fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ash_urn_{}", name));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -0,0 +1,16 @@
+use ash_urn::memory_alignment::Std140;
+
+// std140 rounds the stride of `Inner` up to 16, rust keeps it at 4
+#[repr(C)]
+#[derive(Std140)]
+struct Inner {
+    x: f32,
+}
+
+#[repr(C)]
+#[derive(Std140)]
+struct Outer {
+    inner: [Inner; 2],
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use ash_urn::memory_alignment::Std140;
+
+// `b` has to start at 16, a vec3 is aligned like a vec4
+#[repr(C)]
+#[derive(Std140)]
+struct Light {
+    a: f32,
+    b: [f32; 3],
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use ash_urn::memory_alignment::Std430;
+
+#[repr(C)]
+#[derive(Std430)]
+struct Particle {
+    mass: f32,
+    pos: [f32; 4],
+}
+
+fn main() {}
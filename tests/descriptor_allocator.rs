@@ -0,0 +1,189 @@
+use ash::vk::{DescriptorType, ShaderStageFlags};
+use ash_urn::descriptor::allocator::signature;
+use ash_urn::descriptor::{Allocator, AllocatorSettings, Setup};
+use ash_urn::Base;
+
+use std::collections::HashMap;
+
+mod common;
+use common::headless_base;
+
+fn setup_map(bindings: &[(u32, DescriptorType, ShaderStageFlags)]) -> HashMap<u32, Setup> {
+    bindings
+        .iter()
+        .map(|&(binding, ty, stage)| {
+            (
+                binding,
+                Setup {
+                    ty,
+                    stage,
+                    count: 1,
+                },
+            )
+        })
+        .collect()
+}
+
+fn uniform_allocator(sets_per_pool: u32) -> Allocator {
+    Allocator::new(&AllocatorSettings {
+        sets_per_pool,
+        pool_ratios: vec![(DescriptorType::UNIFORM_BUFFER, 1.0)],
+        name: "TestAllocator".to_string(),
+    })
+}
+
+fn allocate_sets(base: &Base, allocator: &mut Allocator, count: usize) {
+    let setup_map = setup_map(&[(0, DescriptorType::UNIFORM_BUFFER, ShaderStageFlags::VERTEX)]);
+    let layout = allocator.layout(base, &setup_map).unwrap();
+    for i in 0..count {
+        allocator
+            .allocate_with_layout(base, layout, format!("TestSet_{}", i))
+            .unwrap();
+    }
+}
+
+#[test]
+fn signatures_ignore_the_binding_order() {
+    let vertex = ShaderStageFlags::VERTEX;
+    let uniform = setup_map(&[
+        (0, DescriptorType::UNIFORM_BUFFER, vertex),
+        (1, DescriptorType::COMBINED_IMAGE_SAMPLER, vertex),
+    ]);
+    let reversed = setup_map(&[
+        (1, DescriptorType::COMBINED_IMAGE_SAMPLER, vertex),
+        (0, DescriptorType::UNIFORM_BUFFER, vertex),
+    ]);
+    assert_eq!(signature(&uniform), signature(&reversed));
+    assert_eq!(
+        signature(&uniform).iter().map(|s| s.0).collect::<Vec<_>>(),
+        vec![0, 1]
+    );
+}
+
+#[test]
+fn signatures_tell_bindings_apart() {
+    let uniform = setup_map(&[(0, DescriptorType::UNIFORM_BUFFER, ShaderStageFlags::VERTEX)]);
+    let storage = setup_map(&[(0, DescriptorType::STORAGE_BUFFER, ShaderStageFlags::VERTEX)]);
+    let fragment = setup_map(&[(
+        0,
+        DescriptorType::UNIFORM_BUFFER,
+        ShaderStageFlags::FRAGMENT,
+    )]);
+    let moved = setup_map(&[(1, DescriptorType::UNIFORM_BUFFER, ShaderStageFlags::VERTEX)]);
+    let mut array = uniform.clone();
+    array.get_mut(&0).unwrap().count = 4;
+
+    let signatures = [
+        signature(&uniform),
+        signature(&storage),
+        signature(&fragment),
+        signature(&moved),
+        signature(&array),
+    ];
+    for (i, a) in signatures.iter().enumerate() {
+        for b in &signatures[i + 1..] {
+            assert_ne!(a, b);
+        }
+    }
+}
+
+#[test]
+fn layouts_are_cached_by_signature() {
+    let base = match headless_base("AllocatorLayouts") {
+        Some(base) => base,
+        None => return,
+    };
+    let mut allocator = uniform_allocator(4);
+    let vertex = ShaderStageFlags::VERTEX;
+
+    let first = allocator
+        .layout(
+            &base,
+            &setup_map(&[
+                (0, DescriptorType::UNIFORM_BUFFER, vertex),
+                (1, DescriptorType::STORAGE_BUFFER, vertex),
+            ]),
+        )
+        .unwrap();
+    let same = allocator
+        .layout(
+            &base,
+            &setup_map(&[
+                (1, DescriptorType::STORAGE_BUFFER, vertex),
+                (0, DescriptorType::UNIFORM_BUFFER, vertex),
+            ]),
+        )
+        .unwrap();
+    let other = allocator
+        .layout(
+            &base,
+            &setup_map(&[(0, DescriptorType::UNIFORM_BUFFER, vertex)]),
+        )
+        .unwrap();
+    assert_eq!(first, same);
+    assert_ne!(first, other);
+    assert_eq!(allocator.layout_count(), 2);
+
+    allocator.destroy(&base);
+}
+
+#[test]
+fn exhausted_pools_are_replaced() {
+    let base = match headless_base("AllocatorRetry") {
+        Some(base) => base,
+        None => return,
+    };
+    let mut allocator = uniform_allocator(1);
+
+    // the second and fourth set do not fit, they go through the retry
+    allocate_sets(&base, &mut allocator, 1);
+    assert_eq!(allocator.pool_count(), 1);
+    allocate_sets(&base, &mut allocator, 1);
+    assert_eq!(allocator.pool_count(), 2);
+    allocate_sets(&base, &mut allocator, 2);
+    assert_eq!(allocator.pool_count(), 3);
+    assert_eq!(allocator.next_pool_size(), 8);
+
+    allocator.destroy(&base);
+}
+
+#[test]
+fn pools_grow_up_to_the_maximum() {
+    let base = match headless_base("AllocatorGrowth") {
+        Some(base) => base,
+        None => return,
+    };
+    let mut allocator = uniform_allocator(1);
+
+    // pools of 1, 2, .., 2048 sets hold 4095, the next one is capped
+    allocate_sets(&base, &mut allocator, 4095);
+    assert_eq!(allocator.pool_count(), 12);
+    assert_eq!(allocator.next_pool_size(), Allocator::MAX_POOL_SIZE);
+    allocate_sets(&base, &mut allocator, 1);
+    assert_eq!(allocator.pool_count(), 13);
+    assert_eq!(allocator.next_pool_size(), Allocator::MAX_POOL_SIZE);
+
+    allocator.destroy(&base);
+}
+
+#[test]
+fn reset_recycles_the_pools() {
+    let base = match headless_base("AllocatorReset") {
+        Some(base) => base,
+        None => return,
+    };
+    let mut allocator = uniform_allocator(1);
+
+    allocate_sets(&base, &mut allocator, 7);
+    assert_eq!(allocator.pool_count(), 3);
+    let next_pool_size = allocator.next_pool_size();
+
+    for _ in 0..3 {
+        allocator.reset(&base).unwrap();
+        allocate_sets(&base, &mut allocator, 7);
+        assert_eq!(allocator.pool_count(), 3);
+        assert_eq!(allocator.next_pool_size(), next_pool_size);
+    }
+
+    allocator.destroy(&base);
+}
@@ -0,0 +1,136 @@
+#![cfg(feature = "gltf")]
+
+use ash_urn::import::gltf;
+
+const ASSETS: &str = "examples/basic_graphics/assets";
+
+fn assert_close(a: [[f32; 4]; 4], b: [[f32; 4]; 4]) {
+    for col in 0..4 {
+        for row in 0..4 {
+            assert!(
+                (a[col][row] - b[col][row]).abs() < 1.0e-5,
+                "{:?} != {:?}",
+                a,
+                b
+            );
+        }
+    }
+}
+
+fn check_test_mesh(scene: &gltf::Scene) {
+    assert_eq!(scene.meshes.len(), 1);
+    assert_eq!(scene.meshes[0].primitives.len(), 1);
+    let primitive = &scene.meshes[0].primitives[0];
+    assert_eq!(primitive.material, Some(0));
+    assert!(!primitive.mesh.vertices.is_empty());
+    assert_eq!(primitive.mesh.indices.len() % 3, 0);
+
+    assert_eq!(scene.nodes.len(), 1);
+    assert_eq!(scene.nodes[0].name.as_deref(), Some("Cube"));
+    assert_eq!(scene.nodes[0].mesh, Some(0));
+
+    let material = &scene.materials[0];
+    assert_eq!(material.name.as_deref(), Some("Material"));
+    assert!((material.roughness_factor - 0.4).abs() < 1.0e-6);
+    assert!(material.base_color_texture.is_none());
+    assert!(scene.images.is_empty());
+}
+
+#[test]
+fn embedded_gltf() {
+    let scene = gltf::load(format!("{}/test.gltf", ASSETS)).unwrap();
+    check_test_mesh(&scene);
+}
+
+#[test]
+fn binary_glb() {
+    let scene = gltf::load(format!("{}/test.glb", ASSETS)).unwrap();
+    check_test_mesh(&scene);
+
+    let bytes = std::fs::read(format!("{}/test.glb", ASSETS)).unwrap();
+    let from_memory = gltf::load_slice(&bytes).unwrap();
+    assert_eq!(
+        from_memory.meshes[0].primitives[0].mesh.indices,
+        scene.meshes[0].primitives[0].mesh.indices
+    );
+}
+
+#[test]
+fn node_hierarchy() {
+    let scene = gltf::load(format!("{}/tank.gltf", ASSETS)).unwrap();
+    assert!(scene.meshes.is_empty());
+    assert_eq!(scene.nodes.len(), 10);
+
+    let gun = scene
+        .nodes
+        .iter()
+        .position(|n| n.name.as_deref() == Some("R_Gun"))
+        .unwrap();
+    let barrels = &scene.nodes[scene.nodes[gun].children[0]];
+    assert_eq!(barrels.name.as_deref(), Some("R_Barrels"));
+    assert_eq!(barrels.parent, Some(gun));
+
+    // world = parent world * local, all the way up
+    let mut expected = barrels.local_transform;
+    let mut parent = barrels.parent;
+    while let Some(idx) = parent {
+        let p = &scene.nodes[idx];
+        let mut m = [[0.0; 4]; 4];
+        for (col, m_col) in m.iter_mut().enumerate() {
+            for (row, value) in m_col.iter_mut().enumerate() {
+                *value = (0..4)
+                    .map(|k| p.local_transform[k][row] * expected[col][k])
+                    .sum();
+            }
+        }
+        expected = m;
+        parent = p.parent;
+    }
+    assert_close(barrels.world_transform, expected);
+}
+
+#[test]
+fn external_buffer_and_textures() {
+    let scene = gltf::load("tests/fixtures/quad.gltf").unwrap();
+
+    let mesh = &scene.meshes[0].primitives[0].mesh;
+    assert_eq!(mesh.indices, vec![0, 1, 2, 0, 2, 3]);
+    assert_eq!(mesh.vertices[2].pos.0, [1.0, 1.0, 0.0]);
+    assert_eq!(mesh.vertices[2].tex.0, [1.0, 1.0]);
+    assert_eq!(mesh.vertices[2].col.0, [1.0, 1.0, 1.0, 1.0]);
+
+    let quad = &scene.nodes[1];
+    assert_eq!(quad.parent, Some(0));
+    assert_close(
+        quad.world_transform,
+        [
+            [2.0, 0.0, 0.0, 0.0],
+            [0.0, 2.0, 0.0, 0.0],
+            [0.0, 0.0, 2.0, 0.0],
+            [1.0, 2.0, 3.0, 1.0],
+        ],
+    );
+
+    let material = &scene.materials[0];
+    assert_eq!(material.base_color_texture, Some(0));
+    assert_eq!(material.normal_texture, Some(0));
+    assert_eq!(material.metallic_roughness_texture, None);
+    assert_eq!(material.metallic_factor, 0.5);
+
+    let image = &scene.images[0];
+    assert_eq!((image.width, image.height), (2, 2));
+    assert_eq!(
+        image.pixels,
+        vec![255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 255, 255, 255, 255]
+    );
+
+    // luminance and alpha, 8 and 16 bit
+    assert_eq!(
+        scene.images[1].pixels,
+        vec![128, 128, 128, 64, 255, 255, 255, 0]
+    );
+    assert_eq!(
+        scene.images[2].pixels,
+        vec![0x80, 0x80, 0x80, 0x40, 0xff, 0xff, 0xff, 0x00]
+    );
+}
@@ -0,0 +1,308 @@
+use ash_urn::base::queue_families::COMBINED;
+use ash_urn::capture::golden::{compare, sibling};
+use ash_urn::capture::{check_golden, GoldenSettings, OffscreenTarget, OffscreenTargetSettings};
+use ash_urn::command::CommandRecorder;
+use ash_urn::descriptor;
+use ash_urn::transfer::UploadBatch;
+use ash_urn::{Base, Command, CommandBuffer, CommandSettings, Descriptor, DescriptorSettings};
+use ash_urn::{DeviceBuffer, DeviceBufferSettings, Sampler, UrnVertex};
+use ash_urn::{Fence, UrnError};
+use ash_urn::{GraphicsPipeline, GraphicsPipelineSettings, PipelineLayout, PipelineLayoutSettings};
+use ash_urn::{RenderPass, RenderPassSettings};
+
+use ash::version::DeviceV1_0;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+mod common;
+use common::{headless_base, scratch_dir};
+
+fn gradient(offset: u8) -> image::RgbaImage {
+    image::RgbaImage::from_fn(4, 4, |x, y| {
+        image::Rgba([x as u8 * 60 + offset, y as u8 * 60, 128, 255])
+    })
+}
+
+#[test]
+fn compare_respects_tolerance() {
+    let comparison = compare(&gradient(2), &gradient(0), 2).unwrap();
+    assert_eq!(comparison.mismatched, 0);
+    assert_eq!(comparison.max_difference, 2);
+
+    let comparison = compare(&gradient(3), &gradient(0), 2).unwrap();
+    assert_eq!(comparison.mismatched, 16);
+    assert_eq!(comparison.diff.get_pixel(0, 0)[1], 0);
+
+    let small = image::RgbaImage::new(2, 2);
+    assert!(compare(&small, &gradient(0), 0).is_err());
+}
+
+#[test]
+fn golden_rejects_missing_reference_and_writes_diff_on_failure() {
+    let dir = scratch_dir("golden_check");
+    let settings = GoldenSettings {
+        reference: dir.join("gradient.png"),
+        tolerance: 1,
+        max_mismatched: 0,
+    };
+
+    // only blessing writes references
+    assert!(check_golden(&gradient(0), &settings).is_err());
+    assert!(!settings.reference.exists());
+    gradient(0).save(&settings.reference).unwrap();
+    check_golden(&gradient(1), &settings).unwrap();
+
+    assert!(check_golden(&gradient(20), &settings).is_err());
+    assert!(sibling(&settings.reference, "diff").exists());
+    assert_eq!(
+        sibling(&settings.reference, "actual"),
+        dir.join("gradient.actual.png")
+    );
+}
+
+const WIDTH: u32 = 64;
+const HEIGHT: u32 = 64;
+
+fn vertex(pos: [f32; 2], tex: [f32; 2]) -> UrnVertex {
+    UrnVertex {
+        pos: [pos[0], pos[1], 0.5].into(),
+        nor: [0.0, 0.0, 1.0].into(),
+        col: [1.0, 1.0, 1.0, 1.0].into(),
+        tex: tex.into(),
+    }
+}
+
+/// Textured quad with the shaders of the graphics example.
+fn render_textured_quad(base: &Base, command: &Command) -> Result<image::RgbaImage, UrnError> {
+    let extent = ash::vk::Extent2D {
+        width: WIDTH,
+        height: HEIGHT,
+    };
+    let format = ash::vk::Format::R8G8B8A8_UNORM;
+    let final_layout = ash::vk::ImageLayout::TRANSFER_SRC_OPTIMAL;
+
+    let render_pass = RenderPass::new(
+        base,
+        &RenderPassSettings {
+            swap_chain_format: format,
+            final_layout,
+            name: "GoldenRenderPass".to_string(),
+        },
+    )?;
+    let target = OffscreenTarget::new(
+        base,
+        &OffscreenTargetSettings {
+            extent,
+            format,
+            render_pass: render_pass.0,
+            name: "Golden".to_string(),
+        },
+    )?;
+
+    // counter clockwise in framebuffer space, where y points down
+    let vertices = [
+        vertex([-0.75, -0.75], [0.0, 0.0]),
+        vertex([0.75, -0.75], [1.0, 0.0]),
+        vertex([0.75, 0.75], [1.0, 1.0]),
+        vertex([-0.75, 0.75], [0.0, 1.0]),
+    ];
+    let indices: [u16; 6] = [0, 3, 2, 2, 1, 0];
+    let checker = [
+        255, 0, 0, 255, 0, 255, 0, 255, //
+        0, 0, 255, 255, 255, 255, 255, 255,
+    ];
+    let mut batch = UploadBatch::new();
+    let vertex_idx = batch.add_buffer(
+        &vertices,
+        ash::vk::BufferUsageFlags::VERTEX_BUFFER,
+        "GoldenVertices".to_string(),
+    )?;
+    let index_idx = batch.add_buffer(
+        &indices,
+        ash::vk::BufferUsageFlags::INDEX_BUFFER,
+        "GoldenIndices".to_string(),
+    )?;
+    let texture_idx = batch.add_image_rgba(2, 2, &checker, "GoldenTexture".to_string())?;
+    let (uploads, pending) = batch.submit(base, command, command)?;
+    pending.finish(base)?;
+    let sampler = Sampler::new(base, "GoldenSampler".to_string())?;
+
+    // model, view and projection
+    let mut identity = [0.0f32; 16];
+    for i in 0..4 {
+        identity[i * 5] = 1.0;
+    }
+    let uniform_buffer = DeviceBuffer::new(
+        base,
+        &DeviceBufferSettings {
+            size: 3 * std::mem::size_of_val(&identity) as ash::vk::DeviceSize,
+            usage: ash::vk::BufferUsageFlags::UNIFORM_BUFFER,
+            properties: ash::vk::MemoryPropertyFlags::HOST_VISIBLE
+                | ash::vk::MemoryPropertyFlags::HOST_COHERENT,
+            map: true,
+            shared: false,
+            name: "GoldenUniforms".to_string(),
+        },
+    )?;
+    uniform_buffer.write_slice(base, &[identity; 3])?;
+
+    let mut setup_map = HashMap::new();
+    setup_map.insert(
+        0,
+        descriptor::Setup {
+            ty: ash::vk::DescriptorType::UNIFORM_BUFFER,
+            stage: ash::vk::ShaderStageFlags::VERTEX,
+            count: 1,
+        },
+    );
+    setup_map.insert(
+        1,
+        descriptor::Setup {
+            ty: ash::vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
+            stage: ash::vk::ShaderStageFlags::FRAGMENT,
+            count: 1,
+        },
+    );
+    let mut usages = HashMap::new();
+    usages.insert(0, descriptor::Usage::Buffer(uniform_buffer.buffer.0));
+    usages.insert(
+        1,
+        descriptor::Usage::ImageSampler(
+            ash::vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+            uploads.images[texture_idx].view.0,
+            sampler.0,
+        ),
+    );
+    let descriptor = Descriptor::new(
+        base,
+        &DescriptorSettings {
+            setup_map,
+            set_usages: vec![descriptor::SetUsage {
+                usages,
+                name: "GoldenSet".to_string(),
+            }],
+            name: "GoldenDescriptor".to_string(),
+        },
+    )?;
+
+    let pipeline_layout = PipelineLayout::new(
+        base,
+        &PipelineLayoutSettings {
+            set_layouts: vec![descriptor.layout.0],
+            push_constant_ranges: vec![],
+            name: "GoldenPipelineLayout".to_string(),
+        },
+    )?;
+    let pipeline = GraphicsPipeline::new::<UrnVertex>(
+        base,
+        &GraphicsPipelineSettings {
+            layout: pipeline_layout.0,
+            vert_spv: std::path::Path::new("examples/basic_graphics/shaders/vert.spv"),
+            frag_spv: std::path::Path::new("examples/basic_graphics/shaders/frag.spv"),
+            extent,
+            render_pass: render_pass.0,
+            name: "GoldenPipeline".to_string(),
+        },
+    )?;
+
+    let command_buffer = CommandBuffer::alloc(base, command.pool.0, "GoldenDraw".to_string())?;
+    let clear_values = [
+        ash::vk::ClearValue {
+            color: ash::vk::ClearColorValue {
+                float32: [0.1, 0.1, 0.1, 1.0],
+            },
+        },
+        ash::vk::ClearValue {
+            depth_stencil: ash::vk::ClearDepthStencilValue {
+                depth: 1.0,
+                stencil: 0,
+            },
+        },
+    ];
+    let mut recorder = CommandRecorder::begin(
+        base,
+        command_buffer.0,
+        ash::vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT,
+    )?;
+    recorder
+        .begin_render_pass(render_pass.0, target.frame_buffer, extent, &clear_values)
+        .bind_pipeline(ash::vk::PipelineBindPoint::GRAPHICS, pipeline.0)
+        .bind_descriptor_sets(
+            ash::vk::PipelineBindPoint::GRAPHICS,
+            pipeline_layout.0,
+            0,
+            &[descriptor.sets[0].0],
+            &[],
+        )
+        .bind_vertex_buffers(0, &[uploads.buffers[vertex_idx].buffer.0])
+        .bind_index_buffer(
+            uploads.buffers[index_idx].buffer.0,
+            ash::vk::IndexType::UINT16,
+        )
+        .draw_indexed(indices.len() as u32, 1, 0, 0, 0)
+        .end_render_pass();
+    recorder.end()?;
+
+    let fence = Fence::new(base, false, "GoldenFence".to_string())?;
+    let command_buffers = [command_buffer.0];
+    let submit_info = ash::vk::SubmitInfo::builder()
+        .command_buffers(&command_buffers)
+        .build();
+    unsafe {
+        base.logical_device
+            .0
+            .queue_submit(command.queue.0, &[submit_info], fence.0)?
+    };
+    fence.wait(base)?;
+    fence.destroy(base);
+    command_buffer.free(base, command.pool.0);
+
+    let rendered = target
+        .capture(base, command.queue.0, command.pool.0, final_layout)?
+        .to_rgba();
+
+    pipeline.destroy(base);
+    pipeline_layout.destroy(base);
+    descriptor.destroy(base);
+    uniform_buffer.destroy(base);
+    sampler.destroy(base);
+    uploads.destroy(base);
+    target.destroy(base);
+    render_pass.destroy(base);
+
+    rendered
+}
+
+/// The reference is rendered on lavapipe, re-bless it there after intended changes
+/// with `URN_BLESS=1 cargo test --test golden` and commit the new png.
+#[test]
+fn textured_quad_matches_reference() {
+    let base = match headless_base("Golden") {
+        Some(base) => base,
+        None => return,
+    };
+    let command = Command::new(
+        &base,
+        &CommandSettings {
+            queue_family_idx: base.queue_map.get(&COMBINED).unwrap().idx,
+            queue_idx: 0,
+            pool_flags: ash::vk::CommandPoolCreateFlags::TRANSIENT,
+            name: "GoldenCommand".to_string(),
+        },
+    )
+    .unwrap();
+
+    let rendered = render_textured_quad(&base, &command);
+    command.destroy(&base);
+
+    check_golden(
+        &rendered.unwrap(),
+        &GoldenSettings {
+            reference: PathBuf::from("tests/golden/textured_quad.png"),
+            tolerance: 2,
+            max_mismatched: 0,
+        },
+    )
+    .unwrap();
+}
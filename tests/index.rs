@@ -0,0 +1,69 @@
+use ash::vk::IndexType;
+use ash_urn::urn_mesh::{Index, Indices};
+use ash_urn::UrnMesh;
+
+fn grid(n_vertices: usize) -> UrnMesh {
+    let mut mesh = UrnMesh::new();
+    mesh.vertices.resize_with(n_vertices, Default::default);
+    mesh.indices = vec![0, 1, n_vertices as u32 - 1];
+    mesh
+}
+
+#[test]
+fn index_types() {
+    assert_eq!(u8::TYPE, IndexType::UINT8_EXT);
+    assert_eq!(u16::TYPE, IndexType::UINT16);
+    assert_eq!(u32::TYPE, IndexType::UINT32);
+    assert_eq!(Indices::U16(vec![0, 1, 2]).index_type(), IndexType::UINT16);
+    assert_eq!(Indices::U8(vec![]).len(), 0);
+    assert!(Indices::U32(vec![]).is_empty());
+}
+
+#[test]
+fn compact_indices() {
+    let mesh = grid(256);
+    assert_eq!(mesh.compact_index_type(true), IndexType::UINT8_EXT);
+    assert_eq!(mesh.compact_index_type(false), IndexType::UINT16);
+    assert_eq!(mesh.compact_indices(true), Indices::U8(vec![0, 1, 255]));
+
+    let mesh = grid(257);
+    assert_eq!(mesh.compact_index_type(true), IndexType::UINT16);
+    assert_eq!(mesh.compact_indices(true), Indices::U16(vec![0, 1, 256]));
+
+    let mesh = grid(65536);
+    assert_eq!(mesh.compact_indices(false), Indices::U16(vec![0, 1, 65535]));
+
+    let mesh = grid(65537);
+    assert_eq!(mesh.compact_index_type(true), IndexType::UINT32);
+    assert_eq!(mesh.compact_indices(true), Indices::U32(vec![0, 1, 65536]));
+
+    // indices past the vertices widen the type instead of wrapping
+    let mut mesh = grid(10);
+    mesh.indices.push(300);
+    assert_eq!(mesh.compact_index_type(true), IndexType::UINT16);
+    assert_eq!(mesh.compact_indices(true), Indices::U16(vec![0, 1, 9, 300]));
+
+    let mesh = UrnMesh::uv_sphere(1.0, 16, 8, [1.0; 4]);
+    match mesh.compact_indices(false) {
+        Indices::U16(indices) => {
+            assert!(indices
+                .iter()
+                .zip(&mesh.indices)
+                .all(|(a, b)| *a as u32 == *b))
+        }
+        other => panic!("expected u16 indices, got {:?}", other.index_type()),
+    }
+}
+
+#[test]
+fn indices_as() {
+    let mesh = grid(300);
+    assert_eq!(mesh.indices_as::<u16>().unwrap(), vec![0, 1, 299]);
+    assert_eq!(mesh.indices_as::<u32>().unwrap(), vec![0, 1, 299]);
+    match mesh.indices_as::<u8>() {
+        Err(ash_urn::UrnError::GenericDynamic(message)) => {
+            assert_eq!(message, "Index 299 does not fit into UINT8_EXT.")
+        }
+        _ => panic!("299 should not fit into u8"),
+    }
+}
@@ -0,0 +1,109 @@
+use ash::vk::{DispatchIndirectCommand, DrawIndexedIndirectCommand, DrawIndirectCommand};
+use ash_urn::base::queue_families::COMBINED;
+use ash_urn::command::IndirectCommand;
+use ash_urn::transfer::{
+    create_indirect_count_device_buffer, create_indirect_device_buffer,
+    create_indirect_device_buffer_uninitialized, indirect_size, readback_buffer,
+};
+use ash_urn::{Command, CommandSettings};
+
+mod common;
+use common::headless_base;
+
+#[test]
+fn strides_match_the_spec() {
+    assert_eq!(DrawIndirectCommand::STRIDE, 16);
+    assert_eq!(DrawIndexedIndirectCommand::STRIDE, 20);
+    assert_eq!(DispatchIndirectCommand::STRIDE, 12);
+}
+
+#[test]
+fn commands_are_tightly_packed() {
+    let commands = [
+        DrawIndexedIndirectCommand {
+            index_count: 36,
+            instance_count: 1,
+            first_index: 0,
+            vertex_offset: 0,
+            first_instance: 0,
+        },
+        DrawIndexedIndirectCommand {
+            index_count: 36,
+            instance_count: 0,
+            first_index: 36,
+            vertex_offset: -4,
+            first_instance: 1,
+        },
+    ];
+    let words: &[u32] = unsafe { std::slice::from_raw_parts(commands.as_ptr() as *const u32, 10) };
+    assert_eq!(words, &[36, 1, 0, 0, 0, 36, 0, 36, -4i32 as u32, 1]);
+}
+
+#[test]
+fn indirect_sizes_are_checked() {
+    assert_eq!(indirect_size(3, 20).unwrap(), 60);
+    assert!(indirect_size(0, 20).is_err());
+    assert!(indirect_size(usize::MAX, 2).is_err());
+}
+
+#[test]
+fn indirect_buffers_hold_the_commands() {
+    let base = match headless_base("Indirect") {
+        Some(base) => base,
+        None => return,
+    };
+    let command = Command::new(
+        &base,
+        &CommandSettings {
+            queue_family_idx: base.queue_map.get(&COMBINED).unwrap().idx,
+            queue_idx: 0,
+            pool_flags: ash::vk::CommandPoolCreateFlags::TRANSIENT,
+            name: "IndirectCommand".to_string(),
+        },
+    )
+    .unwrap();
+
+    let commands = [DrawIndirectCommand {
+        vertex_count: 3,
+        instance_count: 2,
+        first_vertex: 1,
+        first_instance: 4,
+    }];
+    let indirect = create_indirect_device_buffer(
+        &base,
+        &commands,
+        command.queue.0,
+        command.pool.0,
+        "Indirect".to_string(),
+    )
+    .unwrap();
+    assert_eq!(indirect.size, 16);
+    let words = readback_buffer::<u32>(&base, &indirect, command.queue.0, command.pool.0).unwrap();
+    assert_eq!(words, vec![3, 2, 1, 4]);
+    indirect.destroy(&base);
+
+    let empty: [DrawIndirectCommand; 0] = [];
+    assert!(create_indirect_device_buffer(
+        &base,
+        &empty,
+        command.queue.0,
+        command.pool.0,
+        "Empty".to_string()
+    )
+    .is_err());
+
+    let uninitialized = create_indirect_device_buffer_uninitialized::<DrawIndexedIndirectCommand>(
+        &base,
+        4,
+        "Uninitialized".to_string(),
+    )
+    .unwrap();
+    assert_eq!(uninitialized.size, 80);
+    uninitialized.destroy(&base);
+
+    let count = create_indirect_count_device_buffer(&base, 2, "Count".to_string()).unwrap();
+    assert_eq!(count.size, 8);
+    count.destroy(&base);
+
+    command.destroy(&base);
+}
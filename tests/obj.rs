@@ -0,0 +1,132 @@
+use ash_urn::import::obj;
+
+fn error_message(source: &str) -> String {
+    match obj::parse(source) {
+        Err(ash_urn::UrnError::GenericDynamic(message)) => message,
+        Err(e) => panic!("unexpected error {:?}", e),
+        Ok(_) => panic!("`{}` should not parse", source),
+    }
+}
+
+#[test]
+fn cube_fixture() {
+    let mesh = obj::load("tests/fixtures/cube.obj").unwrap();
+
+    // every face has its own normal, so corners are only shared within a face
+    assert_eq!(mesh.vertices.len(), 24);
+    assert_eq!(mesh.indices.len(), 6 * 2 * 3);
+    assert!(mesh
+        .indices
+        .iter()
+        .all(|i| (*i as usize) < mesh.vertices.len()));
+
+    for vertex in &mesh.vertices {
+        assert!(vertex.pos.0.iter().all(|c| c.abs() == 0.5));
+        assert_eq!(vertex.col.0, [1.0, 1.0, 1.0, 1.0]);
+        let length: f32 = vertex.nor.0.iter().map(|c| c * c).sum();
+        assert_eq!(length, 1.0);
+    }
+
+    // the last face uses relative indices for the top side
+    let top = &mesh.indices[30..];
+    assert_eq!(mesh.vertices[top[0] as usize].pos.0, [-0.5, 0.5, 0.5]);
+    assert_eq!(mesh.vertices[top[0] as usize].nor.0, [0.0, 1.0, 0.0]);
+    assert_eq!(mesh.vertices[top[0] as usize].tex.0, [0.0, 1.0]);
+    assert_eq!(mesh.vertices[top[2] as usize].pos.0, [0.5, 0.5, -0.5]);
+    assert_eq!(mesh.vertices[top[2] as usize].tex.0, [1.0, 0.0]);
+}
+
+#[test]
+fn shared_corners() {
+    let mesh = obj::parse(
+        "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\n\
+         f 1 2 3\nf 1 3 4\n",
+    )
+    .unwrap();
+    assert_eq!(mesh.vertices.len(), 4);
+    assert_eq!(mesh.indices, vec![0, 1, 2, 0, 2, 3]);
+
+    // same position, different texture coordinate
+    let mesh = obj::parse(
+        "v 0 0 0\nv 1 0 0\nv 1 1 0\nvt 0 0\nvt 1 1\n\
+         f 1/1 2/1 3/1\nf 1/2 3/1 2/2\n",
+    )
+    .unwrap();
+    assert_eq!(mesh.vertices.len(), 5);
+    assert_eq!(mesh.indices, vec![0, 1, 2, 3, 2, 4]);
+}
+
+#[test]
+fn fan_triangulation() {
+    let mesh = obj::parse("v 0 0 0\nv 1 0 0\nv 2 1 0\nv 1 2 0\nv 0 1 0\nf 1 2 3 4 5\n").unwrap();
+    assert_eq!(mesh.indices, vec![0, 1, 2, 0, 2, 3, 0, 3, 4]);
+}
+
+#[test]
+fn corner_formats() {
+    let mesh = obj::parse(
+        "v 0 0 0 1 0 0\nv 1 0 0 0 1 0 0.5\nv 0 1 0\nvt 0.25 0.75\nvn 0 0 1\n\
+         f 1//1 2/1/1 3/1\n",
+    )
+    .unwrap();
+    assert_eq!(mesh.vertices[0].col.0, [1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(mesh.vertices[0].tex.0, [0.0, 0.0]);
+    assert_eq!(mesh.vertices[0].nor.0, [0.0, 0.0, 1.0]);
+    assert_eq!(mesh.vertices[1].col.0, [0.0, 1.0, 0.0, 0.5]);
+    assert_eq!(mesh.vertices[1].tex.0, [0.25, 0.25]);
+    assert_eq!(mesh.vertices[2].col.0, [1.0, 1.0, 1.0, 1.0]);
+    assert_eq!(mesh.vertices[2].nor.0, [0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn malformed_input() {
+    assert_eq!(
+        error_message("v 0 0 0\nv 1 0 0\nf 1 2\n"),
+        "OBJ line 3: a face needs at least 3 corners, got 2"
+    );
+    assert_eq!(
+        error_message("v 0 0\n"),
+        "OBJ line 1: `v` expects 3 to 7 numbers, got 2"
+    );
+    assert_eq!(
+        error_message("v 1 2 3 4 5\nf 1 1 1\n"),
+        "OBJ line 1: `v` expects 3, 4, 6 or 7 numbers, got 5"
+    );
+    assert_eq!(
+        error_message("# comment\nvn 0 zero 1\n"),
+        "OBJ line 2: `zero` is not a number"
+    );
+    assert_eq!(
+        error_message("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 4\n"),
+        "OBJ line 4: position index 4 is out of range, 3 defined so far"
+    );
+    assert_eq!(
+        error_message("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 0\n"),
+        "OBJ line 4: position index 0 is out of range, 3 defined so far"
+    );
+    assert_eq!(
+        error_message("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 -4\n"),
+        "OBJ line 4: position index -4 is out of range, 3 defined so far"
+    );
+    assert_eq!(
+        error_message("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1/1 2/1 3/1\n"),
+        "OBJ line 4: texture coordinate index 1 is out of range, 0 defined so far"
+    );
+    assert_eq!(
+        error_message("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1/x 2 3\n"),
+        "OBJ line 4: `x` is not a texture coordinate index"
+    );
+    assert_eq!(
+        error_message("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1/1/1/1 2 3\n"),
+        "OBJ line 4: malformed face corner `1/1/1/1`"
+    );
+    assert_eq!(
+        error_message("\n\ncstype bezier\n"),
+        "OBJ line 3: unknown keyword `cstype`"
+    );
+}
+
+#[test]
+fn missing_file() {
+    assert!(obj::load("tests/fixtures/missing.obj").is_err());
+}
@@ -0,0 +1,78 @@
+use ash_urn::urn_mesh::packed::{oct_decode, oct_encode};
+use ash_urn::urn_mesh::{CompactVertex, PackedVertex};
+use ash_urn::util::{f16_to_f32, f32_to_f16};
+use ash_urn::{UrnMesh, Vertex};
+
+#[test]
+fn sizes() {
+    assert_eq!(std::mem::size_of::<PackedVertex>(), 48);
+    assert_eq!(std::mem::size_of::<CompactVertex>(), 20);
+    assert_eq!(CompactVertex::get_binding_description()[0].stride, 20);
+
+    let formats: Vec<_> = CompactVertex::get_attribute_description()
+        .iter()
+        .map(|a| (a.format, a.offset))
+        .collect();
+    assert_eq!(
+        formats,
+        vec![
+            (ash::vk::Format::R16G16B16A16_SFLOAT, 0),
+            (ash::vk::Format::R16G16_SNORM, 8),
+            (ash::vk::Format::R8G8B8A8_UNORM, 12),
+            (ash::vk::Format::R16G16_UNORM, 16),
+        ]
+    );
+}
+
+#[test]
+fn half_floats() {
+    assert_eq!(f32_to_f16(1.0), 0x3c00);
+    assert_eq!(f32_to_f16(-2.0), 0xc000);
+    assert_eq!(f32_to_f16(65504.0), 0x7bff);
+    assert_eq!(f32_to_f16(1.0e6), 0x7c00);
+    assert_eq!(f32_to_f16(0.0), 0);
+    assert_eq!(f32_to_f16(5.960_464_5e-8), 1);
+    assert!(f16_to_f32(f32_to_f16(f32::NAN)).is_nan());
+
+    for &x in &[0.1f32, -0.333, 2.5, 1000.5, 6.1e-5] {
+        let y = f16_to_f32(f32_to_f16(x));
+        assert!((x - y).abs() <= x.abs() * 1.0e-3, "{} became {}", x, y);
+    }
+}
+
+#[test]
+fn octahedral_normals() {
+    let normals: [[f32; 3]; 6] = [
+        [0.0, 0.0, 1.0],
+        [0.0, 0.0, -1.0],
+        [1.0, 0.0, 0.0],
+        [0.0, -1.0, 0.0],
+        [0.577, -0.577, -0.577],
+        [-0.267, 0.534, 0.802],
+    ];
+    for n in normals.iter() {
+        let l = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+        let d = oct_decode(oct_encode(*n));
+        for i in 0..3 {
+            assert!((n[i] / l - d[i]).abs() < 1.0e-3, "{:?} became {:?}", n, d);
+        }
+    }
+    assert_eq!(oct_decode(oct_encode([0.0, 0.0, 0.0])), [0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn convert_mesh() {
+    let mesh = UrnMesh::new().add_quad(
+        [0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [1.0, 1.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [1.0, 0.5, 0.0, 1.0],
+    );
+    let packed: Vec<PackedVertex> = mesh.convert_vertices();
+    let compact: Vec<CompactVertex> = mesh.convert_vertices();
+    assert_eq!(packed.len(), 4);
+    assert_eq!(packed[2].pos, [1.0, 1.0, 0.0]);
+    assert_eq!(compact[2].pos, [0x3c00, 0x3c00, 0, 0x3c00]);
+    assert_eq!(compact[2].col.0, [255, 128, 0, 255]);
+}
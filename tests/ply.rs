@@ -0,0 +1,168 @@
+use ash_urn::import::ply;
+
+fn error_message(bytes: &[u8]) -> String {
+    match ply::parse(bytes) {
+        Err(ash_urn::UrnError::Generic(message)) => message.to_string(),
+        Err(ash_urn::UrnError::GenericDynamic(message)) => message,
+        Err(e) => panic!("unexpected error {:?}", e),
+        Ok(_) => panic!("input should not parse"),
+    }
+}
+
+const TRIANGLE_HEADER: &str = "ply\nformat ascii 1.0\n\
+    element vertex 3\nproperty float x\nproperty float y\nproperty float z\n\
+    element face 1\nproperty list uchar int vertex_indices\nend_header\n";
+
+#[test]
+fn ascii_fixture() {
+    let mesh = ply::load("tests/fixtures/colored_quad.ply").unwrap();
+
+    assert_eq!(mesh.vertices.len(), 4);
+    assert_eq!(mesh.indices, vec![0, 1, 2, 0, 2, 3]);
+
+    assert_eq!(mesh.vertices[1].pos.0, [1.0, 0.0, 0.0]);
+    assert_eq!(mesh.vertices[1].nor.0, [0.0, 0.0, 1.0]);
+    assert_eq!(mesh.vertices[1].col.0, [0.0, 1.0, 0.0, 1.0]);
+    // v is flipped like in the other importers
+    assert_eq!(mesh.vertices[1].tex.0, [1.0, 1.0]);
+    assert_eq!(mesh.vertices[2].tex.0, [1.0, 0.0]);
+    assert_eq!(mesh.vertices[3].col.0, [1.0, 1.0, 1.0, 1.0]);
+}
+
+#[test]
+fn binary_fixture() {
+    // has a double property and an extra element that are skipped
+    let mesh = ply::load("tests/fixtures/triangle_le.ply").unwrap();
+
+    assert_eq!(mesh.vertices.len(), 3);
+    assert_eq!(mesh.indices, vec![0, 1, 2]);
+    assert_eq!(mesh.vertices[2].pos.0, [0.0, 1.0, 0.0]);
+    assert_eq!(mesh.vertices[2].col.0, [1.0, 1.0, 1.0, 1.0]);
+}
+
+#[test]
+fn binary_big_endian() {
+    let mut bytes = b"ply\nformat binary_big_endian 1.0\n\
+        element vertex 3\nproperty float x\nproperty float y\nproperty float z\n\
+        property ushort red\n\
+        element face 1\nproperty list uchar ushort vertex_indices\nend_header\n"
+        .to_vec();
+    for (pos, red) in [
+        ([0.0f32, 0.0, 0.0], 0u16),
+        ([2.0, 0.0, 0.0], 65535),
+        ([0.0, 2.0, 0.0], 0),
+    ] {
+        for c in pos.iter() {
+            bytes.extend_from_slice(&c.to_be_bytes());
+        }
+        bytes.extend_from_slice(&red.to_be_bytes());
+    }
+    bytes.push(3);
+    for idx in [2u16, 1, 0].iter() {
+        bytes.extend_from_slice(&idx.to_be_bytes());
+    }
+
+    let mesh = ply::parse(&bytes).unwrap();
+    assert_eq!(mesh.vertices[1].pos.0, [2.0, 0.0, 0.0]);
+    assert_eq!(mesh.vertices[1].col.0, [1.0, 1.0, 1.0, 1.0]);
+    assert_eq!(mesh.vertices[0].col.0, [0.0, 1.0, 1.0, 1.0]);
+    assert_eq!(mesh.indices, vec![2, 1, 0]);
+
+    bytes.push(0);
+    assert_eq!(
+        error_message(&bytes),
+        "PLY: 1 bytes left after the last element."
+    );
+    bytes.truncate(bytes.len() - 2);
+    assert_eq!(
+        error_message(&bytes),
+        "PLY: body ends at byte 48 while reading face 0 `vertex_indices`"
+    );
+}
+
+#[test]
+fn malformed_header() {
+    assert_eq!(
+        error_message(b"obj\nformat ascii 1.0\nend_header\n"),
+        "PLY header line 1: not a PLY file"
+    );
+    assert_eq!(
+        error_message(b"ply\nformat ascii 2.0\nend_header\n"),
+        "PLY header line 2: can not parse `format ascii 2.0`"
+    );
+    assert_eq!(
+        error_message(b"ply\nformat utf8 1.0\nend_header\n"),
+        "PLY header line 2: unknown format `utf8`"
+    );
+    assert_eq!(
+        error_message(b"ply\nformat ascii 1.0\nproperty float x\nend_header\n"),
+        "PLY header line 3: property before any element"
+    );
+    assert_eq!(
+        error_message(b"ply\nformat ascii 1.0\nelement vertex 1\nproperty half x\nend_header\n"),
+        "PLY header line 4: unknown type `half`"
+    );
+    assert_eq!(
+        error_message(b"ply\nformat ascii 1.0\nelement vertex many\nend_header\n"),
+        "PLY header line 3: `many` is not an element count"
+    );
+    assert_eq!(
+        error_message(b"ply\nformat ascii 1.0\nelement vertex 1\n"),
+        "PLY: header without `end_header`."
+    );
+    assert_eq!(
+        error_message(b"ply\nend_header\n"),
+        "PLY: header without format."
+    );
+}
+
+#[test]
+fn malformed_body() {
+    let parse = |body: &str| error_message(format!("{}{}", TRIANGLE_HEADER, body).as_bytes());
+
+    assert_eq!(
+        parse("0 0 0\n1 0 0\n0 1 0\n3 0 1\n"),
+        "PLY: body ends while reading face 0 `vertex_indices`"
+    );
+    assert_eq!(
+        parse("0 0 0\n1 0 0\n0 one 0\n3 0 1 2\n"),
+        "PLY: `one` is not a valid vertex 2 `y`"
+    );
+    assert_eq!(
+        parse("0 0 0\n1 0 0\n0 1 0\n3 0 1 3\n"),
+        "PLY: face 0 uses vertex 3, but there are only 3."
+    );
+    assert_eq!(
+        parse("0 0 0\n1 0 0\n0 1 0\n2 0 1\n"),
+        "PLY: face 0 has only 2 corners."
+    );
+    assert_eq!(
+        error_message(
+            b"ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nproperty float y\n\
+              end_header\n0 0\n"
+        ),
+        "PLY: vertices need x, y and z properties."
+    );
+}
+
+#[test]
+fn list_count_beyond_body() {
+    // checked against the rest of the body before anything is allocated
+    let mut bytes = b"ply\nformat binary_little_endian 1.0\n\
+        element face 1\nproperty list uint int vertex_indices\nend_header\n"
+        .to_vec();
+    bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+    bytes.extend_from_slice(&[0; 12]);
+    assert_eq!(
+        error_message(&bytes),
+        "PLY: body ends at byte 16 while reading face 0 `vertex_indices`"
+    );
+
+    let ascii = "ply\nformat ascii 1.0\n\
+        element face 1\nproperty list uint int vertex_indices\nend_header\n\
+        4000000000 0 1 2\n";
+    assert_eq!(
+        error_message(ascii.as_bytes()),
+        "PLY: body ends while reading face 0 `vertex_indices`"
+    );
+}
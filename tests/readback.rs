@@ -0,0 +1,99 @@
+use ash::vk::Format;
+use ash_urn::transfer::{readback_image_bytes, texel_size, to_rgba, ReadbackImageSettings};
+use ash_urn::util::f16_to_f32;
+
+mod common;
+use common::headless_base;
+
+#[test]
+fn rgba8_is_copied() {
+    let bytes = [1, 2, 3, 4, 5, 6, 7, 8];
+    let image = to_rgba(2, 1, Format::R8G8B8A8_UNORM, &bytes).unwrap();
+    assert_eq!(image.into_raw(), bytes.to_vec());
+}
+
+#[test]
+fn bgra8_is_swizzled() {
+    let image = to_rgba(1, 1, Format::B8G8R8A8_SRGB, &[10, 20, 30, 40]).unwrap();
+    assert_eq!(image.into_raw(), vec![30, 20, 10, 40]);
+}
+
+#[test]
+fn single_channel_is_opaque() {
+    let image = to_rgba(1, 2, Format::R8_UNORM, &[7, 9]).unwrap();
+    assert_eq!(image.into_raw(), vec![7, 0, 0, 255, 9, 0, 0, 255]);
+}
+
+#[test]
+fn float_channels_are_clamped() {
+    let texel: Vec<u8> = [-1.0f32, 0.5, 2.0, 1.0]
+        .iter()
+        .flat_map(|c| c.to_le_bytes().to_vec())
+        .collect();
+    let image = to_rgba(1, 1, Format::R32G32B32A32_SFLOAT, &texel).unwrap();
+    assert_eq!(image.into_raw(), vec![0, 128, 255, 255]);
+}
+
+#[test]
+fn half_floats_are_converted() {
+    assert_eq!(f16_to_f32(0x3c00), 1.0);
+    assert_eq!(f16_to_f32(0xc000), -2.0);
+    assert_eq!(f16_to_f32(0x3800), 0.5);
+    assert_eq!(f16_to_f32(0x0001), 2f32.powi(-24));
+    assert!(f16_to_f32(0x7c00).is_infinite());
+    assert!(f16_to_f32(0x7e00).is_nan());
+
+    let texel: Vec<u8> = [0x3c00u16, 0x3800, 0x0000, 0x3c00]
+        .iter()
+        .flat_map(|c| c.to_le_bytes().to_vec())
+        .collect();
+    let image = to_rgba(1, 1, Format::R16G16B16A16_SFLOAT, &texel).unwrap();
+    assert_eq!(image.into_raw(), vec![255, 128, 0, 255]);
+}
+
+#[test]
+fn packed_10_bit() {
+    let packed: u32 = 1023 | (1023 << 20) | (3 << 30);
+    let image = to_rgba(
+        1,
+        1,
+        Format::A2B10G10R10_UNORM_PACK32,
+        &packed.to_le_bytes(),
+    )
+    .unwrap();
+    assert_eq!(image.into_raw(), vec![255, 0, 255, 255]);
+}
+
+#[test]
+fn size_mismatch_and_unknown_formats_fail() {
+    assert!(to_rgba(2, 2, Format::R8G8B8A8_UNORM, &[0; 15]).is_err());
+    assert!(to_rgba(1, 1, Format::D32_SFLOAT, &[0; 4]).is_err());
+    assert_eq!(texel_size(Format::D32_SFLOAT), None);
+    assert_eq!(texel_size(Format::R16G16B16A16_SFLOAT), Some(8));
+}
+
+#[test]
+fn undefined_layouts_are_rejected() {
+    let base = match headless_base("ReadbackLayout") {
+        Some(base) => base,
+        None => return,
+    };
+    for &layout in &[
+        ash::vk::ImageLayout::UNDEFINED,
+        ash::vk::ImageLayout::PREINITIALIZED,
+    ] {
+        let read = readback_image_bytes(
+            &base,
+            &ReadbackImageSettings {
+                queue: ash::vk::Queue::null(),
+                pool: ash::vk::CommandPool::null(),
+                image: ash::vk::Image::null(),
+                width: 1,
+                height: 1,
+                format: Format::R8G8B8A8_UNORM,
+                layout,
+            },
+        );
+        assert!(read.is_err());
+    }
+}
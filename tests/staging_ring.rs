@@ -0,0 +1,65 @@
+use ash_urn::transfer::RingAllocator;
+
+#[test]
+fn allocations_are_aligned() {
+    let mut ring = RingAllocator::new(256);
+    assert_eq!(ring.alloc(10, 16, 1).unwrap(), 0);
+    assert_eq!(ring.alloc(10, 16, 1).unwrap(), 16);
+    assert_eq!(ring.alloc(1, 64, 2).unwrap(), 64);
+    assert_eq!(ring.used(), 65);
+}
+
+#[test]
+fn too_large_is_rejected() {
+    let mut ring = RingAllocator::new(128);
+    assert!(ring.alloc(129, 4, 1).is_err());
+    assert_eq!(ring.alloc(128, 4, 1).unwrap(), 0);
+    assert!(ring.alloc(1, 4, 1).is_err());
+}
+
+#[test]
+fn regions_are_freed_by_timeline_value() {
+    let mut ring = RingAllocator::new(100);
+    ring.alloc(40, 1, 1).unwrap();
+    ring.alloc(40, 1, 2).unwrap();
+    assert!(ring.alloc(40, 1, 3).is_err());
+
+    ring.release(0);
+    assert_eq!(ring.used(), 80);
+    ring.release(1);
+    assert_eq!(ring.used(), 40);
+    ring.release(5);
+    assert_eq!(ring.used(), 0);
+}
+
+#[test]
+fn wraps_around_without_overlap() {
+    let mut ring = RingAllocator::new(100);
+    assert_eq!(ring.alloc(30, 1, 1).unwrap(), 0);
+    assert_eq!(ring.alloc(50, 1, 2).unwrap(), 30);
+    ring.release(1);
+
+    // 20 bytes left at the end, too short, so it starts over at 0
+    // while [30, 80) is still in use
+    assert_eq!(ring.alloc(25, 1, 3).unwrap(), 0);
+    assert_eq!(ring.used(), 50 + 20 + 25);
+
+    // only [25, 30) is free
+    assert!(ring.alloc(10, 1, 3).is_err());
+    assert_eq!(ring.alloc(5, 1, 3).unwrap(), 25);
+
+    // the skipped end stays in use with the allocation that wrapped
+    ring.release(2);
+    assert_eq!(ring.used(), 20 + 30);
+    assert!(ring.alloc(51, 1, 4).is_err());
+    assert_eq!(ring.alloc(50, 1, 4).unwrap(), 30);
+    assert_eq!(ring.used(), 100);
+}
+
+#[test]
+fn empty_ring_starts_over() {
+    let mut ring = RingAllocator::new(64);
+    ring.alloc(60, 1, 1).unwrap();
+    ring.release(1);
+    assert_eq!(ring.alloc(64, 1, 2).unwrap(), 0);
+}
@@ -0,0 +1,114 @@
+use ash_urn::memory_alignment::{Align16, Pod};
+use ash_urn::urn_mesh::{CompactVertex, PackedVertex};
+use ash_urn::UrnVertex;
+use ash_urn::{DeviceBuffer, DeviceBufferSettings, TypedBuffer, TypedBufferSettings};
+
+mod common;
+use common::headless_base;
+
+fn assert_pod<T: Pod>() {}
+
+#[test]
+fn crate_types_are_pod() {
+    assert_pod::<[[f32; 4]; 4]>();
+    assert_pod::<Align16<[f32; 3]>>();
+    assert_pod::<UrnVertex>();
+    assert_pod::<PackedVertex>();
+    assert_pod::<CompactVertex>();
+    assert_pod::<ash::vk::DrawIndexedIndirectCommand>();
+}
+
+#[test]
+fn element_ranges_are_checked() {
+    let check = TypedBuffer::<u32>::check;
+    check(4, 0, 4).unwrap();
+    check(4, 4, 0).unwrap();
+    assert!(check(4, 3, 2).is_err());
+    assert!(check(4, 5, 0).is_err());
+    assert!(check(4, usize::MAX, 1).is_err());
+}
+
+#[test]
+fn byte_ranges_are_checked() {
+    DeviceBuffer::check_range(12, 4, 8, "Writing").unwrap();
+    DeviceBuffer::check_range(12, 12, 0, "Writing").unwrap();
+    assert!(DeviceBuffer::check_range(12, 8, 8, "Reading").is_err());
+    assert!(DeviceBuffer::check_range(12, u64::MAX, 1, "Reading").is_err());
+}
+
+fn host_settings(len: usize, map: bool) -> TypedBufferSettings {
+    TypedBufferSettings {
+        len,
+        usage: ash::vk::BufferUsageFlags::STORAGE_BUFFER,
+        properties: ash::vk::MemoryPropertyFlags::HOST_VISIBLE
+            | ash::vk::MemoryPropertyFlags::HOST_COHERENT,
+        map,
+        shared: false,
+        name: "Typed".to_string(),
+    }
+}
+
+#[test]
+fn reads_and_writes_are_bounds_checked() {
+    let base = match headless_base("TypedBuffer") {
+        Some(base) => base,
+        None => return,
+    };
+
+    for map in [false, true] {
+        let buffer = TypedBuffer::<[u32; 2]>::new(&base, &host_settings(4, map)).unwrap();
+        assert_eq!(buffer.len(), 4);
+        assert_eq!(buffer.offset(3), 24);
+
+        buffer
+            .write_slice(&base, 0, &[[0, 1], [2, 3], [4, 5], [6, 7]])
+            .unwrap();
+        buffer.write(&base, 2, &[40, 50]).unwrap();
+        assert_eq!(buffer.read(&base, 2).unwrap(), [40, 50]);
+        assert_eq!(
+            buffer.read_all(&base).unwrap(),
+            vec![[0, 1], [2, 3], [40, 50], [6, 7]]
+        );
+
+        assert!(buffer.write(&base, 4, &[0, 0]).is_err());
+        assert!(buffer.write_slice(&base, 3, &[[0, 0], [0, 0]]).is_err());
+        assert!(buffer.write_slice(&base, usize::MAX, &[[0, 0]]).is_err());
+        assert!(buffer.read(&base, 4).is_err());
+        let mut too_many = [[0; 2]; 5];
+        assert!(buffer.read_slice(&base, 0, &mut too_many).is_err());
+        buffer.destroy(&base);
+    }
+
+    assert!(TypedBuffer::<u32>::new(&base, &host_settings(0, false)).is_err());
+}
+
+#[test]
+fn untyped_buffers_reject_overflows() {
+    let base = match headless_base("TypedBuffer") {
+        Some(base) => base,
+        None => return,
+    };
+
+    let device_buffer = DeviceBuffer::new(
+        &base,
+        &DeviceBufferSettings {
+            size: 12,
+            usage: ash::vk::BufferUsageFlags::STORAGE_BUFFER,
+            properties: ash::vk::MemoryPropertyFlags::HOST_VISIBLE
+                | ash::vk::MemoryPropertyFlags::HOST_COHERENT,
+            map: false,
+            shared: false,
+            name: "Untyped".to_string(),
+        },
+    )
+    .unwrap();
+    assert!(device_buffer.write_slice(&base, &[0u32; 4]).is_err());
+    assert!(device_buffer.write(&base, &[0u64; 2]).is_err());
+    let mut read = [0u32; 2];
+    assert!(device_buffer.read_slice_at(&base, 8, &mut read).is_err());
+    device_buffer.read_slice_at(&base, 4, &mut read).unwrap();
+
+    let typed = TypedBuffer::<u32>::from_device_buffer(device_buffer).unwrap();
+    assert_eq!(typed.len(), 3);
+    typed.destroy(&base);
+}
@@ -0,0 +1,44 @@
+use ash::vk::BufferUsageFlags;
+use ash_urn::transfer::upload_batch::STAGING_ALIGNMENT;
+use ash_urn::transfer::UploadBatch;
+
+#[test]
+fn uploads_are_packed_aligned() {
+    let mut batch = UploadBatch::new();
+    assert!(batch.is_empty());
+    assert_eq!(batch.staging_size(), 0);
+
+    let vertices = batch
+        .add_buffer(&[1u8, 2, 3], BufferUsageFlags::VERTEX_BUFFER, "V".into())
+        .unwrap();
+    assert_eq!(vertices, 0);
+    assert_eq!(batch.staging_size(), STAGING_ALIGNMENT);
+
+    let indices = batch
+        .add_buffer(&[0u32; 5], BufferUsageFlags::INDEX_BUFFER, "I".into())
+        .unwrap();
+    assert_eq!(indices, 1);
+    assert_eq!(batch.staging_size(), 3 * STAGING_ALIGNMENT);
+
+    let image = batch.add_image_rgba(2, 2, &[255; 16], "T".into()).unwrap();
+    assert_eq!(image, 0);
+    assert_eq!(batch.staging_size(), 4 * STAGING_ALIGNMENT);
+    assert!(!batch.is_empty());
+}
+
+#[test]
+fn image_size_is_checked() {
+    let mut batch = UploadBatch::new();
+    assert!(batch.add_image_rgba(2, 2, &[0; 15], "T".into()).is_err());
+    assert!(batch.is_empty());
+}
+
+#[test]
+fn empty_buffers_are_rejected() {
+    let mut batch = UploadBatch::new();
+    let empty: [u32; 0] = [];
+    assert!(batch
+        .add_buffer(&empty, BufferUsageFlags::VERTEX_BUFFER, "V".into())
+        .is_err());
+    assert!(batch.is_empty());
+}
@@ -0,0 +1,254 @@
+use ash_urn::UrnMesh;
+
+const WHITE: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn assert_close(a: f32, b: f32, epsilon: f32) {
+    assert!((a - b).abs() <= epsilon, "{} != {}", a, b);
+}
+
+/// Valid indices, unit normals and triangles facing the way of their normals
+fn check_consistent(mesh: &UrnMesh) {
+    assert!(!mesh.indices.is_empty());
+    assert_eq!(mesh.indices.len() % 3, 0);
+    assert!(mesh
+        .indices
+        .iter()
+        .all(|i| (*i as usize) < mesh.vertices.len()));
+
+    for vertex in &mesh.vertices {
+        assert_close(dot(vertex.nor.0, vertex.nor.0), 1.0, 1.0e-4);
+    }
+    for triangle in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| &mesh.vertices[triangle[i] as usize]);
+        let face = cross(sub(b.pos.0, a.pos.0), sub(c.pos.0, a.pos.0));
+        assert!(dot(face, face) > 0.0, "degenerate triangle {:?}", triangle);
+        let normals = [a.nor.0, b.nor.0, c.nor.0];
+        let normal_sum = [0, 1, 2].map(|i| normals.iter().map(|n| n[i]).sum());
+        assert!(
+            dot(face, normal_sum) > 0.0,
+            "{:?} is facing inwards",
+            triangle
+        );
+    }
+}
+
+/// Positive for closed meshes with outward facing triangles
+fn volume(mesh: &UrnMesh) -> f32 {
+    mesh.indices
+        .chunks_exact(3)
+        .map(|triangle| {
+            let [a, b, c] = [0, 1, 2].map(|i| mesh.vertices[triangle[i] as usize].pos.0);
+            dot(a, cross(b, c)) / 6.0
+        })
+        .sum()
+}
+
+#[test]
+fn cube() {
+    let mesh = UrnMesh::cube(2.0, WHITE);
+    check_consistent(&mesh);
+    assert_eq!(mesh.vertices.len(), 24);
+    assert_eq!(mesh.indices.len(), 36);
+    assert_close(volume(&mesh), 8.0, 1.0e-5);
+
+    let aabb = mesh.bounding_box().unwrap();
+    assert_eq!(aabb.min, [-1.0, -1.0, -1.0]);
+    assert_eq!(aabb.max, [1.0, 1.0, 1.0]);
+    assert_eq!(aabb.extent(), [2.0, 2.0, 2.0]);
+    let sphere = mesh.bounding_sphere().unwrap();
+    assert_eq!(sphere.center, [0.0, 0.0, 0.0]);
+    assert_close(sphere.radius, 3.0f32.sqrt(), 1.0e-6);
+}
+
+#[test]
+fn spheres() {
+    let sphere_volume = 4.0 / 3.0 * std::f32::consts::PI;
+
+    let mesh = UrnMesh::uv_sphere(1.0, 32, 16, WHITE);
+    check_consistent(&mesh);
+    assert_eq!(mesh.vertices.len(), 33 * 17);
+    // the pole rows are triangles instead of quads
+    assert_eq!(mesh.indices.len(), 3 * 32 * (2 * 16 - 2));
+    assert_close(volume(&mesh), sphere_volume, 0.05 * sphere_volume);
+
+    let mesh = UrnMesh::icosphere(2.0, 0, WHITE);
+    check_consistent(&mesh);
+    assert_eq!(mesh.vertices.len(), 12);
+    assert_eq!(mesh.indices.len(), 60);
+
+    let mesh = UrnMesh::icosphere(1.0, 3, WHITE);
+    check_consistent(&mesh);
+    assert_eq!(mesh.vertices.len(), 642);
+    assert_eq!(mesh.indices.len(), 3 * 1280);
+    assert_close(volume(&mesh), sphere_volume, 0.02 * sphere_volume);
+    for vertex in &mesh.vertices {
+        assert_close(dot(vertex.pos.0, vertex.pos.0), 1.0, 1.0e-5);
+    }
+}
+
+#[test]
+fn cylinder_and_cone() {
+    let pi = std::f32::consts::PI;
+
+    let mesh = UrnMesh::cylinder(0.5, 2.0, 64, WHITE);
+    check_consistent(&mesh);
+    assert_close(volume(&mesh), pi * 0.25 * 2.0, 0.01);
+    let aabb = mesh.bounding_box().unwrap();
+    assert_close(aabb.min[1], -1.0, 1.0e-6);
+    assert_close(aabb.max[1], 1.0, 1.0e-6);
+
+    let mesh = UrnMesh::cone(1.0, 3.0, 64, WHITE);
+    check_consistent(&mesh);
+    assert_close(volume(&mesh), pi, 0.01);
+    assert_close(mesh.bounding_box().unwrap().max[1], 3.0, 1.0e-6);
+}
+
+#[test]
+fn plane_grid() {
+    let mesh = UrnMesh::plane_grid([4.0, 2.0], [4, 2], WHITE);
+    check_consistent(&mesh);
+    assert_eq!(mesh.vertices.len(), 5 * 3);
+    assert_eq!(mesh.indices.len(), 4 * 2 * 6);
+    let aabb = mesh.bounding_box().unwrap();
+    assert_eq!(aabb.min, [-2.0, 0.0, -1.0]);
+    assert_eq!(aabb.max, [2.0, 0.0, 1.0]);
+    assert!(mesh.vertices.iter().all(|v| v.nor.0 == [0.0, 1.0, 0.0]));
+}
+
+#[test]
+fn gizmos() {
+    let mesh = UrnMesh::arrow([1.0, 1.0, 1.0], [1.0, 1.0, -3.0], 0.1, WHITE);
+    check_consistent(&mesh);
+    let aabb = mesh.bounding_box().unwrap();
+    assert_close(aabb.min[2], -3.0, 1.0e-5);
+    assert_close(aabb.max[2], 1.0, 1.0e-5);
+    assert_close(aabb.max[0], 1.2, 1.0e-5);
+
+    let mesh = UrnMesh::axes(1.0);
+    check_consistent(&mesh);
+    let aabb = mesh.bounding_box().unwrap();
+    for i in 0..3 {
+        assert_close(aabb.max[i], 1.0, 1.0e-5);
+    }
+    let tip_color = |axis: usize| {
+        mesh.vertices
+            .iter()
+            .find(|v| (v.pos.0[axis] - 1.0).abs() < 1.0e-5)
+            .unwrap()
+            .col
+            .0
+    };
+    assert_eq!(tip_color(0), [1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(tip_color(1), [0.0, 1.0, 0.0, 1.0]);
+    assert_eq!(tip_color(2), [0.0, 0.0, 1.0, 1.0]);
+}
+
+#[test]
+fn normals() {
+    let mut mesh = UrnMesh::icosphere(1.0, 1, WHITE);
+    let n_triangles = mesh.indices.len() / 3;
+
+    mesh.compute_flat_normals();
+    check_consistent(&mesh);
+    assert_eq!(mesh.vertices.len(), 3 * n_triangles);
+
+    let mut mesh = UrnMesh::icosphere(1.0, 2, WHITE);
+    for vertex in mesh.vertices.iter_mut() {
+        vertex.nor = [0.0, 0.0, 0.0].into();
+    }
+    mesh.compute_smooth_normals();
+    check_consistent(&mesh);
+    // close to the exact normals of the sphere
+    for vertex in &mesh.vertices {
+        assert!(dot(vertex.nor.0, vertex.pos.0) > 0.99);
+    }
+}
+
+#[test]
+fn tangents() {
+    let mesh = UrnMesh::plane_grid([1.0, 1.0], [2, 2], WHITE);
+    let tangents = mesh.compute_tangents();
+    assert_eq!(tangents.len(), mesh.vertices.len());
+    // `u` grows along `x`, `v` along `z` which is mirrored with the normal `y`
+    for tangent in tangents {
+        assert_eq!(tangent, [1.0, 0.0, 0.0, -1.0]);
+    }
+
+    let mesh = UrnMesh::uv_sphere(1.0, 16, 8, WHITE);
+    for (vertex, tangent) in mesh.vertices.iter().zip(mesh.compute_tangents()) {
+        let t = [tangent[0], tangent[1], tangent[2]];
+        assert_close(dot(t, vertex.nor.0), 0.0, 1.0e-5);
+    }
+}
+
+#[test]
+fn merge() {
+    let cube = UrnMesh::cube(1.0, WHITE);
+    let translated = [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [5.0, 0.0, 0.0, 1.0],
+    ];
+    // mirrored and stretched along x
+    let mirrored = [
+        [-2.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [-5.0, 0.0, 0.0, 1.0],
+    ];
+    let mesh = UrnMesh::merge(&[(&cube, translated), (&cube, mirrored)]);
+    check_consistent(&mesh);
+    assert_eq!(mesh.vertices.len(), 48);
+    assert_eq!(mesh.indices.len(), 72);
+    assert_close(volume(&mesh), 3.0, 1.0e-4);
+
+    let aabb = mesh.bounding_box().unwrap();
+    assert_eq!(aabb.min, [-6.0, -0.5, -0.5]);
+    assert_eq!(aabb.max, [5.5, 0.5, 0.5]);
+}
+
+#[test]
+fn weld() {
+    let mut mesh = UrnMesh::cube(1.0, WHITE);
+    mesh.weld(1.0e-4);
+    // normals and texture coordinates keep the sides apart
+    assert_eq!(mesh.vertices.len(), 24);
+
+    for vertex in mesh.vertices.iter_mut() {
+        vertex.nor = [0.0, 0.0, 0.0].into();
+        vertex.tex = [0.0, 0.0].into();
+        vertex.pos.0[0] += 1.0e-5 * vertex.pos.0[1];
+    }
+    mesh.weld(1.0e-4);
+    assert_eq!(mesh.vertices.len(), 8);
+    assert_eq!(mesh.indices.len(), 36);
+    assert_close(volume(&mesh), 1.0, 1.0e-4);
+
+    mesh.compute_smooth_normals();
+    check_consistent(&mesh);
+
+    // everything collapses
+    mesh.weld(10.0);
+    assert_eq!(mesh.vertices.len(), 1);
+    assert!(mesh.indices.is_empty());
+
+    assert!(UrnMesh::new().bounding_box().is_none());
+    assert!(UrnMesh::new().bounding_sphere().is_none());
+}
@@ -0,0 +1,80 @@
+use ash_urn::urn_mesh::Normalized;
+use ash_urn::{UrnVertex, Vertex};
+
+#[repr(C)]
+#[derive(Vertex)]
+#[vertex(binding = 1, rate = "instance", location = 4)]
+struct Instance {
+    model: [[f32; 4]; 4],
+    #[vertex(format = "R8G8B8A8_UNORM")]
+    tint: [u8; 4],
+    #[vertex(skip)]
+    _id: u32,
+    #[vertex(location = 10)]
+    uv: Normalized<[u16; 2]>,
+}
+
+fn summary<V: Vertex>() -> Vec<(u32, u32, ash::vk::Format, u32)> {
+    V::get_attribute_description()
+        .iter()
+        .map(|a| (a.binding, a.location, a.format, a.offset))
+        .collect()
+}
+
+#[test]
+fn urn_vertex() {
+    let binding = UrnVertex::get_binding_description();
+    assert_eq!(binding.len(), 1);
+    assert_eq!(binding[0].stride, 64);
+    assert_eq!(binding[0].input_rate, ash::vk::VertexInputRate::VERTEX);
+
+    assert_eq!(
+        summary::<UrnVertex>(),
+        vec![
+            (0, 0, ash::vk::Format::R32G32B32_SFLOAT, 0),
+            (0, 1, ash::vk::Format::R32G32B32_SFLOAT, 16),
+            (0, 2, ash::vk::Format::R32G32B32A32_SFLOAT, 32),
+            (0, 3, ash::vk::Format::R32G32_SFLOAT, 48),
+        ]
+    );
+}
+
+#[test]
+fn instance_attributes() {
+    let binding = Instance::get_binding_description();
+    assert_eq!(binding[0].binding, 1);
+    assert_eq!(binding[0].stride, 76);
+    assert_eq!(binding[0].input_rate, ash::vk::VertexInputRate::INSTANCE);
+
+    let rgba = ash::vk::Format::R32G32B32A32_SFLOAT;
+    assert_eq!(
+        summary::<Instance>(),
+        vec![
+            (1, 4, rgba, 0),
+            (1, 5, rgba, 16),
+            (1, 6, rgba, 32),
+            (1, 7, rgba, 48),
+            (1, 8, ash::vk::Format::R8G8B8A8_UNORM, 64),
+            (1, 10, ash::vk::Format::R16G16_UNORM, 72),
+        ]
+    );
+}
+
+#[test]
+fn vertex_and_instance_bindings() {
+    let bindings = <(UrnVertex, Instance)>::get_binding_description();
+    let rates: Vec<_> = bindings.iter().map(|b| (b.binding, b.input_rate)).collect();
+    assert_eq!(
+        rates,
+        vec![
+            (0, ash::vk::VertexInputRate::VERTEX),
+            (1, ash::vk::VertexInputRate::INSTANCE),
+        ]
+    );
+
+    let locations: Vec<_> = <(UrnVertex, Instance)>::get_attribute_description()
+        .iter()
+        .map(|a| a.location)
+        .collect();
+    assert_eq!(locations, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 10]);
+}